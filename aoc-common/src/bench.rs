@@ -0,0 +1,200 @@
+use crate::params::Settings;
+use crate::solution::{Solution, SolveError};
+use std::{
+    fmt,
+    hint::black_box,
+    time::{Duration, Instant},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Step {
+    Parse,
+    Part1,
+    Part2,
+}
+
+impl fmt::Display for Step {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Step::Parse => write!(f, "parse"),
+            Step::Part1 => write!(f, "part 1"),
+            Step::Part2 => write!(f, "part 2"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Stats {
+    pub runs: usize,
+    pub mean: Duration,
+    pub median: Duration,
+    pub min: Duration,
+    pub max: Duration,
+}
+
+impl Stats {
+    pub fn from_samples(mut samples: Vec<Duration>) -> Self {
+        assert!(!samples.is_empty(), "can't summarise zero runs");
+        samples.sort();
+
+        let runs = samples.len();
+        let middle = runs / 2;
+        let median = if runs.is_multiple_of(2) {
+            (samples[middle - 1] + samples[middle]) / 2
+        } else {
+            samples[middle]
+        };
+
+        Stats {
+            runs,
+            mean: samples.iter().sum::<Duration>() / runs as u32,
+            median,
+            min: samples[0],
+            max: samples[runs - 1],
+        }
+    }
+}
+
+// how long each step of a day took, in the order they ran
+pub type Timings = Vec<(Step, Stats)>;
+
+// what a year crate registers next to `DayFn` to time a day: the input, the parts, the settings
+// and how many timed runs to do of each step
+pub type BenchFn = fn(&str, &[u32], &Settings, usize) -> Result<Timings, SolveError>;
+
+// runs `f` once untimed to warm up, then `runs` more times under the clock
+pub fn time<T>(runs: usize, mut f: impl FnMut() -> T) -> Stats {
+    black_box(f());
+
+    let samples = (0..runs)
+        .map(|_| {
+            let start = Instant::now();
+            black_box(f());
+            start.elapsed()
+        })
+        .collect();
+
+    Stats::from_samples(samples)
+}
+
+// times parsing and each requested part on its own. the parts all work from a single parse, the
+// same as when a day is solved
+pub fn bench<S: Solution>(
+    input: &str,
+    parts: &[u32],
+    settings: &Settings,
+    runs: usize,
+) -> Result<Timings, SolveError> {
+    let params = settings.build::<S::Params>()?;
+    let parsed = S::parse(input)?;
+
+    let mut timings = vec![(Step::Parse, time(runs, || S::parse(input)))];
+    for part in parts {
+        timings.push(match part {
+            1 => (Step::Part1, time(runs, || S::part1(&parsed, &params))),
+            _ => (Step::Part2, time(runs, || S::part2(&parsed, &params))),
+        });
+    }
+
+    Ok(timings)
+}
+
+// `1.25ms`, `830.00µs`, always with two decimals so the table lines up
+pub fn format_duration(duration: Duration) -> String {
+    let nanos = duration.as_nanos() as f64;
+    if nanos >= 1e9 {
+        format!("{:.2}s", nanos / 1e9)
+    } else if nanos >= 1e6 {
+        format!("{:.2}ms", nanos / 1e6)
+    } else if nanos >= 1e3 {
+        format!("{:.2}µs", nanos / 1e3)
+    } else {
+        format!("{:.2}ns", nanos)
+    }
+}
+
+// one line per step, labelled with the day it belongs to
+pub fn table(rows: &[(String, Step, Stats)]) -> String {
+    let label_width = rows
+        .iter()
+        .map(|(label, _, _)| label.len())
+        .chain([3])
+        .max()
+        .unwrap();
+
+    let mut lines = vec![format!(
+        "{:<label_width$}  {:<6}  {:>5}  {:>10}  {:>10}  {:>10}  {:>10}",
+        "day", "step", "runs", "mean", "median", "min", "max"
+    )];
+    for (label, step, stats) in rows {
+        lines.push(format!(
+            "{:<label_width$}  {:<6}  {:>5}  {:>10}  {:>10}  {:>10}  {:>10}",
+            label,
+            step.to_string(),
+            stats.runs,
+            format_duration(stats.mean),
+            format_duration(stats.median),
+            format_duration(stats.min),
+            format_duration(stats.max),
+        ));
+    }
+
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod bench_tests {
+    use crate::bench::{format_duration, table, time, Stats, Step};
+    use std::time::Duration;
+
+    fn millis(values: &[u64]) -> Vec<Duration> {
+        values.iter().copied().map(Duration::from_millis).collect()
+    }
+
+    #[test]
+    fn stats_of_samples() {
+        let stats = Stats::from_samples(millis(&[9, 1, 2]));
+        assert_eq!(stats.runs, 3);
+        assert_eq!(stats.mean, Duration::from_millis(4));
+        assert_eq!(stats.median, Duration::from_millis(2));
+        assert_eq!(
+            (stats.min, stats.max),
+            (Duration::from_millis(1), Duration::from_millis(9))
+        );
+
+        let even = Stats::from_samples(millis(&[4, 1, 2, 10]));
+        assert_eq!(even.median, Duration::from_micros(3_000));
+    }
+
+    #[test]
+    fn times_every_run_after_the_warm_up() {
+        let mut calls = 0;
+        let stats = time(5, || calls += 1);
+
+        assert_eq!(stats.runs, 5);
+        assert_eq!(calls, 6);
+    }
+
+    #[test]
+    fn durations_pick_a_readable_unit() {
+        assert_eq!(format_duration(Duration::from_nanos(800)), "800.00ns");
+        assert_eq!(format_duration(Duration::from_nanos(12_345)), "12.35µs");
+        assert_eq!(format_duration(Duration::from_micros(1_250)), "1.25ms");
+        assert_eq!(format_duration(Duration::from_millis(3_500)), "3.50s");
+    }
+
+    #[test]
+    fn table_has_a_line_per_step() {
+        let stats = Stats::from_samples(millis(&[1]));
+        let rows = vec![
+            ("2022/1".to_owned(), Step::Parse, stats),
+            ("2022/1".to_owned(), Step::Part1, stats),
+        ];
+
+        let lines: Vec<String> = table(&rows).lines().map(str::to_owned).collect();
+        assert_eq!(lines.len(), 3);
+        assert!(lines[0].starts_with("day     step"));
+        assert!(lines[2].starts_with("2022/1  part 1"));
+        assert!(lines[2].ends_with("1.00ms"));
+    }
+}
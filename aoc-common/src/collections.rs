@@ -0,0 +1,52 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::ops::Add;
+
+// adds `val` to the entry for `key`, starting from `val` when it is missing
+pub fn upsert<K: Eq + Hash, V: Copy + Add<Output = V>>(map: &mut HashMap<K, V>, key: K, val: V) {
+    upsert_with(map, key, val, |current_val| current_val + val);
+}
+
+// inserts `val` when `key` is missing, otherwise replaces the entry with `f` of its current value
+pub fn upsert_with<K: Eq + Hash, V: Copy>(
+    map: &mut HashMap<K, V>,
+    key: K,
+    val: V,
+    f: impl Fn(V) -> V,
+) {
+    match map.get(&key) {
+        Some(&current_val) => {
+            map.insert(key, f(current_val));
+        }
+        None => {
+            map.insert(key, val);
+        }
+    }
+}
+
+#[cfg(test)]
+mod collections_tests {
+    use crate::collections::{upsert, upsert_with};
+    use std::collections::HashMap;
+
+    #[test]
+    fn upsert_adds_to_existing_counts() {
+        let mut counts: HashMap<char, usize> = HashMap::new();
+        for c in "abacab".chars() {
+            upsert(&mut counts, c, 1);
+        }
+        upsert(&mut counts, 'c', 10);
+        assert_eq!(counts[&'a'], 3);
+        assert_eq!(counts[&'b'], 2);
+        assert_eq!(counts[&'c'], 11);
+    }
+
+    #[test]
+    fn upsert_with_applies_the_update_only_when_present() {
+        let mut map: HashMap<(i32, i32), u64> = HashMap::new();
+        upsert_with(&mut map, (1, 2), 5, |current| current * 2);
+        assert_eq!(map[&(1, 2)], 5);
+        upsert_with(&mut map, (1, 2), 5, |current| current * 2);
+        assert_eq!(map[&(1, 2)], 10);
+    }
+}
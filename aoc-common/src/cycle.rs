@@ -0,0 +1,146 @@
+use std::{collections::HashMap, hash::Hash};
+
+// a simulation that has started repeating itself. `history[n]` is the state after n steps, up to and
+// including the first repeat, so `history[start]` and `history[start + length]` share a fingerprint
+#[derive(Debug, Clone)]
+pub struct Cycle<S> {
+    pub start: usize,
+    pub length: usize,
+    pub history: Vec<S>,
+}
+
+// steps from `initial` until a fingerprint comes round again. the fingerprint has to capture
+// everything the next steps depend on, otherwise the cycle found may not be a real one
+pub fn find_cycle<S, K>(
+    initial: S,
+    mut step: impl FnMut(&S) -> S,
+    mut fingerprint: impl FnMut(&S) -> K,
+) -> Cycle<S>
+where
+    K: Eq + Hash,
+{
+    let mut seen: HashMap<K, usize> = HashMap::new();
+    let mut history = vec![initial];
+
+    loop {
+        let index = history.len() - 1;
+        let current = &history[index];
+        let key = fingerprint(current);
+        if let Some(&start) = seen.get(&key) {
+            return Cycle {
+                start,
+                length: index - start,
+                history,
+            };
+        }
+
+        seen.insert(key, index);
+        let next = step(current);
+        history.push(next);
+    }
+}
+
+impl<S> Cycle<S> {
+    // where step n falls in the history and how many whole cycles were skipped to get there
+    fn locate(&self, n: u64) -> (usize, u64) {
+        if n < self.history.len() as u64 {
+            return (n as usize, 0);
+        }
+
+        let since_start = n - self.start as u64;
+        let length = self.length as u64;
+        (
+            self.start + (since_start % length) as usize,
+            since_start / length,
+        )
+    }
+
+    // the state after n steps
+    pub fn state_at(&self, n: u64) -> &S {
+        &self.history[self.locate(n).0]
+    }
+
+    // for values that keep growing while the state repeats (a height, a score) each skipped cycle
+    // adds what one trip round the cycle did
+    pub fn extrapolate(&self, n: u64, mut measure: impl FnMut(&S) -> i64) -> i64 {
+        let (index, cycles) = self.locate(n);
+        let per_cycle =
+            measure(&self.history[self.start + self.length]) - measure(&self.history[self.start]);
+
+        measure(&self.history[index]) + cycles as i64 * per_cycle
+    }
+}
+
+// steps until a step leaves the state as it was. the step is told which round (from 0) it is, and
+// the number of rounds returned includes the one where nothing changed
+pub fn run_until_stable<S>(initial: S, mut step: impl FnMut(&S, usize) -> S) -> (usize, S)
+where
+    S: PartialEq,
+{
+    let mut state = initial;
+    let mut round = 0;
+    loop {
+        let next = step(&state, round);
+        round += 1;
+
+        if next == state {
+            return (round, next);
+        }
+
+        state = next;
+    }
+}
+
+#[cfg(test)]
+mod cycle_tests {
+    use crate::cycle::{find_cycle, run_until_stable, Cycle};
+
+    // 1, 4, 16, 64, 256 % 100 = 56, 24, 96, 84, 36, 44, 76, 4, ...
+    fn powers_of_four() -> Cycle<u64> {
+        find_cycle(1, |&n| n * 4 % 100, |&n| n)
+    }
+
+    #[test]
+    fn finds_where_the_cycle_starts() {
+        let cycle = powers_of_four();
+
+        assert_eq!(cycle.start, 1);
+        assert_eq!(cycle.length, 10);
+        assert_eq!(cycle.history.len(), 12);
+    }
+
+    #[test]
+    fn states_past_the_history() {
+        let cycle = powers_of_four();
+
+        let mut n = 1;
+        for steps in 0..50 {
+            assert_eq!(*cycle.state_at(steps), n, "after {} steps", steps);
+            n = n * 4 % 100;
+        }
+    }
+
+    #[test]
+    fn extrapolates_growing_values() {
+        // a counter that goes round 0..3 while the total keeps going up
+        let cycle = find_cycle((0, 0), |&(i, total)| ((i + 1) % 3, total + i), |&(i, _)| i);
+
+        let total_at = |n: u64| (0..n).map(|step| (step % 3) as i64).sum::<i64>();
+        for n in [0, 2, 3, 7, 100, 1_000] {
+            assert_eq!(cycle.extrapolate(n, |&(_, total)| total), total_at(n));
+        }
+
+        assert_eq!(
+            cycle.extrapolate(1_000_000_000_000, |&(_, total)| total),
+            999_999_999_999
+        );
+    }
+
+    #[test]
+    fn stops_when_nothing_changes() {
+        let (rounds, state) = run_until_stable(10, |&n, _| if n > 3 { n - 2 } else { n });
+
+        assert_eq!(state, 2);
+        assert_eq!(rounds, 5);
+    }
+}
@@ -0,0 +1,104 @@
+use std::ops::Add;
+
+pub const ORTHOGONAL: [(i8, i8); 4] = [(-1, 0), (1, 0), (0, 1), (0, -1)];
+
+pub const SURROUNDING: [(i8, i8); 8] = [
+    (-1, -1),
+    (-1, 0),
+    (-1, 1),
+    (1, 0),
+    (1, 1),
+    (0, 1),
+    (1, -1),
+    (0, -1),
+];
+
+// points that can be measured on a grid, with the distance always coming back unsigned
+pub trait Manhattan {
+    type Distance;
+
+    fn manhattan_distance(&self, other: &Self) -> Self::Distance;
+}
+
+macro_rules! manhattan_for {
+    ($($t:ty => $d:ty),*) => {
+        $(
+            impl Manhattan for ($t, $t) {
+                type Distance = $d;
+
+                fn manhattan_distance(&self, other: &Self) -> $d {
+                    self.0.abs_diff(other.0) + self.1.abs_diff(other.1)
+                }
+            }
+
+            impl Manhattan for ($t, $t, $t) {
+                type Distance = $d;
+
+                fn manhattan_distance(&self, other: &Self) -> $d {
+                    self.0.abs_diff(other.0) + self.1.abs_diff(other.1) + self.2.abs_diff(other.2)
+                }
+            }
+        )*
+    };
+}
+
+manhattan_for!(i32 => u32, i64 => u64, isize => usize, u32 => u32, u64 => u64, usize => usize);
+
+pub fn manhattan_distance<P: Manhattan>(a: P, b: P) -> P::Distance {
+    a.manhattan_distance(&b)
+}
+
+// pushes every neighbor of `position` reached by `offsets` (ORTHOGONAL or SURROUNDING)
+pub fn push_neighbors<T, C>(position: &(T, T), offsets: &[(i8, i8)], to_visit: &mut C)
+where
+    T: Copy + Add<Output = T> + From<i8>,
+    C: Extend<(T, T)>,
+{
+    to_visit.extend(offsets.iter().map(|&(x_offset, y_offset)| {
+        (
+            position.0 + T::from(x_offset),
+            position.1 + T::from(y_offset),
+        )
+    }));
+}
+
+#[cfg(test)]
+mod geometry_tests {
+    use crate::geometry::{manhattan_distance, push_neighbors, ORTHOGONAL, SURROUNDING};
+    use std::collections::HashSet;
+
+    #[test]
+    fn manhattan_distance_in_two_dimensions() {
+        assert_eq!(manhattan_distance((0isize, 0isize), (-3, 4)), 7usize);
+        assert_eq!(manhattan_distance((5usize, 1usize), (2, 3)), 5);
+    }
+
+    #[test]
+    fn manhattan_distance_in_three_dimensions() {
+        assert_eq!(
+            manhattan_distance((1105, -1205, 1229), (-92, -2380, -20)),
+            3621u32
+        );
+    }
+
+    #[test]
+    fn orthogonal_neighbors_into_a_set() {
+        let mut to_visit: HashSet<(i32, i32)> = HashSet::new();
+        push_neighbors(&(0, 0), &ORTHOGONAL, &mut to_visit);
+        push_neighbors(&(1, 0), &ORTHOGONAL, &mut to_visit);
+        assert_eq!(to_visit.len(), 8);
+        assert!(to_visit.contains(&(0, 0)));
+        assert!(to_visit.contains(&(0, -1)));
+        assert!(to_visit.contains(&(2, 0)));
+    }
+
+    #[test]
+    fn surrounding_neighbors_into_a_vec() {
+        let mut to_visit: Vec<(i64, i64)> = vec![];
+        push_neighbors(&(10, 10), &SURROUNDING, &mut to_visit);
+        assert_eq!(to_visit.len(), 8);
+        assert!(to_visit.contains(&(9, 9)));
+        assert!(to_visit.contains(&(11, 11)));
+        assert!(!to_visit.contains(&(10, 10)));
+    }
+}
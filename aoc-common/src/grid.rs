@@ -0,0 +1,457 @@
+use crate::geometry::{ORTHOGONAL, SURROUNDING};
+use crate::parse::ParseError;
+use std::{
+    fmt,
+    ops::{Index, IndexMut},
+};
+
+// (x, y) with y growing downwards, signed so stepping off an edge doesn't underflow
+pub type Position = (isize, isize);
+
+// what the grid answers for positions outside of it
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Edges<T> {
+    // nothing is out there
+    Bounded,
+    // walking off one side comes back in on the other
+    Wrapping,
+    // the grid sits in an infinite plane of this value
+    Background(T),
+}
+
+// a rectangle of cells stored row by row
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+    edges: Edges<T>,
+}
+
+impl<T> Grid<T> {
+    pub fn new(width: usize, height: usize, fill: T) -> Self
+    where
+        T: Clone,
+    {
+        Grid {
+            width,
+            height,
+            cells: vec![fill; width * height],
+            edges: Edges::Bounded,
+        }
+    }
+
+    // each cell is whatever `cell` gives for its position
+    pub fn from_fn(width: usize, height: usize, mut cell: impl FnMut(Position) -> T) -> Self {
+        Grid {
+            width,
+            height,
+            cells: (0..width * height)
+                .map(|index| cell(((index % width) as isize, (index / width) as isize)))
+                .collect(),
+            edges: Edges::Bounded,
+        }
+    }
+
+    // `None` when the rows aren't all the same length
+    pub fn from_rows<R: IntoIterator<Item = Vec<T>>>(rows: R) -> Option<Self> {
+        let mut width = None;
+        let mut height = 0;
+        let mut cells = vec![];
+
+        for row in rows {
+            if *width.get_or_insert(row.len()) != row.len() {
+                return None;
+            }
+            cells.extend(row);
+            height += 1;
+        }
+
+        Some(Grid {
+            width: width.unwrap_or(0),
+            height,
+            cells,
+            edges: Edges::Bounded,
+        })
+    }
+
+    // one character per cell, one line per row. `cell` turns a character into a cell
+    // and `expected` says what it accepts for the error message
+    pub fn parse(
+        input: &str,
+        expected: &str,
+        cell: impl FnMut(char) -> Option<T>,
+    ) -> Result<Self, ParseError>
+    where
+        T: Clone,
+    {
+        Self::parse_lines(input, expected, None, cell)
+    }
+
+    // like `parse`, but short lines are filled out with `padding` up to the longest one
+    pub fn parse_padded(
+        input: &str,
+        expected: &str,
+        padding: T,
+        cell: impl FnMut(char) -> Option<T>,
+    ) -> Result<Self, ParseError>
+    where
+        T: Clone,
+    {
+        let width = input
+            .lines()
+            .map(|line| line.chars().count())
+            .max()
+            .unwrap_or(0);
+
+        Self::parse_lines(input, expected, Some((width, padding)), cell)
+    }
+
+    fn parse_lines(
+        input: &str,
+        expected: &str,
+        padding: Option<(usize, T)>,
+        mut cell: impl FnMut(char) -> Option<T>,
+    ) -> Result<Self, ParseError>
+    where
+        T: Clone,
+    {
+        let mut rows = vec![];
+        for line in input.lines() {
+            let mut row = Vec::with_capacity(line.len());
+            for (index, c) in line.char_indices() {
+                match cell(c) {
+                    Some(value) => row.push(value),
+                    None => return Err(ParseError::at(input, &line[index..], expected)),
+                }
+            }
+
+            match &padding {
+                Some((width, padding)) => row.resize(*width, padding.clone()),
+                None => {
+                    if rows
+                        .first()
+                        .is_some_and(|first: &Vec<T>| first.len() != row.len())
+                    {
+                        let end = &line[line.len()..];
+                        return Err(ParseError::at(input, end, "a line as wide as the first"));
+                    }
+                }
+            }
+            rows.push(row);
+        }
+
+        Ok(Self::from_rows(rows).unwrap())
+    }
+
+    pub fn wrapping(mut self) -> Self {
+        self.edges = Edges::Wrapping;
+        self
+    }
+
+    pub fn with_background(mut self, background: T) -> Self {
+        self.edges = Edges::Background(background);
+        self
+    }
+
+    pub fn edges(&self) -> &Edges<T> {
+        &self.edges
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, (x, y): Position) -> bool {
+        x >= 0 && y >= 0 && (x as usize) < self.width && (y as usize) < self.height
+    }
+
+    // where a position ends up once wrapping is taken into account
+    pub fn normalize(&self, (x, y): Position) -> Position {
+        match self.edges {
+            Edges::Wrapping if self.width > 0 && self.height > 0 => (
+                x.rem_euclid(self.width as isize),
+                y.rem_euclid(self.height as isize),
+            ),
+            _ => (x, y),
+        }
+    }
+
+    fn index_of(&self, position: Position) -> Option<usize> {
+        let (x, y) = self.normalize(position);
+        if self.contains((x, y)) {
+            Some(y as usize * self.width + x as usize)
+        } else {
+            None
+        }
+    }
+
+    pub fn get(&self, position: Position) -> Option<&T> {
+        match (self.index_of(position), &self.edges) {
+            (Some(index), _) => Some(&self.cells[index]),
+            (None, Edges::Background(background)) => Some(background),
+            (None, _) => None,
+        }
+    }
+
+    // only cells inside the grid can be changed, the background stays the same everywhere
+    pub fn get_mut(&mut self, position: Position) -> Option<&mut T> {
+        self.index_of(position).map(|index| &mut self.cells[index])
+    }
+
+    pub fn positions(&self) -> impl Iterator<Item = Position> {
+        let width = self.width;
+        (0..self.cells.len()).map(move |index| ((index % width) as isize, (index / width) as isize))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Position, &T)> {
+        self.positions().zip(self.cells.iter())
+    }
+
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (Position, &mut T)> {
+        self.positions().zip(self.cells.iter_mut())
+    }
+
+    pub fn cells(&self) -> impl Iterator<Item = &T> {
+        self.cells.iter()
+    }
+
+    pub fn cells_mut(&mut self) -> impl Iterator<Item = &mut T> {
+        self.cells.iter_mut()
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        (0..self.height).map(|y| self.row(y))
+    }
+
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        assert!(x < self.width, "column {} is outside the grid", x);
+        self.cells.iter().skip(x).step_by(self.width)
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(|x| self.column(x))
+    }
+
+    // the cells reached by `offsets` (ORTHOGONAL or SURROUNDING) that exist under the edge rules
+    pub fn neighbors<'a>(
+        &'a self,
+        (x, y): Position,
+        offsets: &'a [(i8, i8)],
+    ) -> impl Iterator<Item = (Position, &'a T)> {
+        offsets.iter().filter_map(move |&(x_offset, y_offset)| {
+            let position = self.normalize((x + x_offset as isize, y + y_offset as isize));
+            self.get(position).map(|value| (position, value))
+        })
+    }
+
+    pub fn orthogonal_neighbors(&self, position: Position) -> impl Iterator<Item = (Position, &T)> {
+        self.neighbors(position, &ORTHOGONAL)
+    }
+
+    pub fn surrounding_neighbors(
+        &self,
+        position: Position,
+    ) -> impl Iterator<Item = (Position, &T)> {
+        self.neighbors(position, &SURROUNDING)
+    }
+
+    pub fn map<U>(&self, mut f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(&mut f).collect(),
+            edges: match &self.edges {
+                Edges::Bounded => Edges::Bounded,
+                Edges::Wrapping => Edges::Wrapping,
+                Edges::Background(background) => Edges::Background(f(background)),
+            },
+        }
+    }
+
+    // adds `by` cells on every side, taken from the background when there is one.
+    // what used to be at (0, 0) moves to (by, by)
+    pub fn grow(&self, by: usize, fill: T) -> Self
+    where
+        T: Clone,
+    {
+        let fill = match &self.edges {
+            Edges::Background(background) => background.clone(),
+            _ => fill,
+        };
+        let width = self.width + 2 * by;
+        let mut cells = Vec::with_capacity(width * (self.height + 2 * by));
+
+        cells.resize(width * by, fill.clone());
+        for row in self.rows() {
+            cells.resize(cells.len() + by, fill.clone());
+            cells.extend_from_slice(row);
+            cells.resize(cells.len() + by, fill.clone());
+        }
+        cells.resize(cells.len() + width * by, fill);
+
+        Grid {
+            width,
+            height: self.height + 2 * by,
+            cells,
+            edges: self.edges.clone(),
+        }
+    }
+}
+
+impl<T> Index<Position> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, position: Position) -> &T {
+        match self.get(position) {
+            Some(value) => value,
+            None => panic!(
+                "{:?} is outside the {}x{} grid",
+                position, self.width, self.height
+            ),
+        }
+    }
+}
+
+impl<T> IndexMut<Position> for Grid<T> {
+    fn index_mut(&mut self, position: Position) -> &mut T {
+        let (width, height) = (self.width, self.height);
+        match self.get_mut(position) {
+            Some(value) => value,
+            None => panic!("{:?} is outside the {}x{} grid", position, width, height),
+        }
+    }
+}
+
+// prints one line per row, so a `Grid<char>` comes back out the way it was parsed
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (y, row) in self.rows().enumerate() {
+            if y > 0 {
+                writeln!(f)?;
+            }
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod grid_tests {
+    use crate::grid::{Edges, Grid};
+
+    const DIGITS: &str = "123\n456\n";
+
+    fn digits() -> Grid<u32> {
+        Grid::parse(DIGITS, "a digit", |c| c.to_digit(10)).unwrap()
+    }
+
+    #[test]
+    fn parses_rows_of_characters() {
+        let grid = digits();
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid[(0, 0)], 1);
+        assert_eq!(grid[(2, 1)], 6);
+        assert_eq!(grid.to_string(), "123\n456");
+    }
+
+    #[test]
+    fn from_fn_fills_by_position() {
+        let grid = Grid::from_fn(3, 2, |(x, y)| (y * 3 + x + 1) as u32);
+        assert_eq!(grid, digits());
+    }
+
+    #[test]
+    fn parse_errors_point_at_the_cell() {
+        let error = Grid::parse("123\n4x6", "a digit", |c| c.to_digit(10)).unwrap_err();
+        assert_eq!((error.line, error.column), (2, 2));
+        assert_eq!(error.expected, "a digit");
+
+        let error = Grid::parse("123\n45\n", "a digit", |c| c.to_digit(10)).unwrap_err();
+        assert_eq!((error.line, error.column), (2, 3));
+    }
+
+    #[test]
+    fn padded_parse_fills_short_lines() {
+        let grid = Grid::parse_padded("  #\n#", "# or space", ' ', Some).unwrap();
+        assert_eq!(grid.to_string(), "  #\n#  ");
+    }
+
+    #[test]
+    fn bounded_lookups() {
+        let mut grid = digits();
+        assert_eq!(grid.get((-1, 0)), None);
+        assert_eq!(grid.get((3, 0)), None);
+        assert!(grid.get_mut((0, 2)).is_none());
+        grid[(1, 1)] = 0;
+        assert_eq!(grid.row(1), &[4, 0, 6]);
+    }
+
+    #[test]
+    #[should_panic(expected = "(3, 0) is outside the 3x2 grid")]
+    fn indexing_outside_panics() {
+        let _ = digits()[(3, 0)];
+    }
+
+    #[test]
+    fn wrapping_lookups() {
+        let grid = digits().wrapping();
+        assert_eq!(grid[(-1, 0)], 3);
+        assert_eq!(grid[(3, 3)], 4);
+        assert_eq!(grid.normalize((-1, -1)), (2, 1));
+    }
+
+    #[test]
+    fn background_lookups() {
+        let mut grid = digits().with_background(0);
+        assert_eq!(grid[(-100, 100)], 0);
+        assert!(grid.get_mut((-1, 0)).is_none());
+        assert_eq!(grid.edges(), &Edges::Background(0));
+    }
+
+    #[test]
+    fn neighbors_follow_the_edges() {
+        let grid = digits();
+        let mut corner: Vec<u32> = grid.orthogonal_neighbors((0, 0)).map(|(_, v)| *v).collect();
+        corner.sort();
+        assert_eq!(corner, vec![2, 4]);
+        assert_eq!(grid.surrounding_neighbors((1, 0)).count(), 5);
+
+        let wrapping = digits().wrapping();
+        assert_eq!(wrapping.orthogonal_neighbors((0, 0)).count(), 4);
+        assert!(wrapping
+            .orthogonal_neighbors((0, 0))
+            .any(|(position, value)| position == (2, 0) && *value == 3));
+
+        let background = digits().with_background(9);
+        assert_eq!(background.surrounding_neighbors((0, 0)).count(), 8);
+    }
+
+    #[test]
+    fn rows_and_columns() {
+        let grid = digits();
+        let columns: Vec<Vec<u32>> = grid.columns().map(|c| c.copied().collect()).collect();
+        assert_eq!(columns, vec![vec![1, 4], vec![2, 5], vec![3, 6]]);
+        assert_eq!(grid.rows().count(), 2);
+        assert_eq!(Grid::from_rows(vec![vec![1], vec![1, 2]]), None);
+    }
+
+    #[test]
+    fn grow_uses_the_background() {
+        let grid = Grid::parse("#", "#", Some).unwrap();
+        assert_eq!(grid.grow(1, '.').to_string(), "...\n.#.\n...");
+        let grid = grid.with_background('~');
+        assert_eq!(grid.grow(1, '.').to_string(), "~~~\n~#~\n~~~");
+        assert_eq!(grid.map(|c| *c == '#').get((5, 5)), Some(&false));
+    }
+}
@@ -0,0 +1,157 @@
+use std::{
+    fmt, fs, io,
+    path::{Path, PathBuf},
+};
+
+#[derive(Debug)]
+pub enum InputError {
+    Missing {
+        year: u32,
+        day: u32,
+        searched: Vec<PathBuf>,
+    },
+    Unreadable {
+        path: PathBuf,
+        source: io::Error,
+    },
+}
+
+impl fmt::Display for InputError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            InputError::Missing {
+                year,
+                day,
+                searched,
+            } => {
+                writeln!(f, "no input found for {} day {}, looked for:", year, day)?;
+                for path in searched {
+                    writeln!(f, "  {}", path.display())?;
+                }
+                write!(
+                    f,
+                    "download it into one of those files or pass --input <path>"
+                )
+            }
+            InputError::Unreadable { path, source } => {
+                write!(f, "could not read \"{}\": {}", path.display(), source)
+            }
+        }
+    }
+}
+
+impl std::error::Error for InputError {}
+
+// the data directory lives at the root of the repo, next to the year crates
+pub fn data_directory() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .parent()
+        .unwrap()
+        .join("data")
+}
+
+// reads the puzzle input for a day, either from `path` or from `data/<year>/<day>.txt`
+pub fn load(year: u32, day: u32, path: Option<&Path>) -> Result<String, InputError> {
+    match path {
+        Some(path) => read(path),
+        None => load_from(&data_directory(), year, day),
+    }
+}
+
+fn load_from(data_directory: &Path, year: u32, day: u32) -> Result<String, InputError> {
+    let searched = candidate_paths(data_directory, year, day);
+
+    match searched.iter().find(|path| path.is_file()) {
+        Some(path) => read(path),
+        None => Err(InputError::Missing {
+            year,
+            day,
+            searched,
+        }),
+    }
+}
+
+// older inputs were saved as `4.txt` or `day01.txt`, so accept those names as well
+fn candidate_paths(data_directory: &Path, year: u32, day: u32) -> Vec<PathBuf> {
+    let year_directory = data_directory.join(year.to_string());
+    let mut paths: Vec<PathBuf> = vec![];
+
+    for name in [
+        format!("{:02}.txt", day),
+        format!("{}.txt", day),
+        format!("day{:02}.txt", day),
+    ] {
+        let path = year_directory.join(name);
+        if !paths.contains(&path) {
+            paths.push(path);
+        }
+    }
+
+    paths
+}
+
+fn read(path: &Path) -> Result<String, InputError> {
+    fs::read_to_string(path).map_err(|source| InputError::Unreadable {
+        path: path.to_owned(),
+        source,
+    })
+}
+
+#[cfg(test)]
+mod input_tests {
+    use crate::input::{candidate_paths, load, load_from, InputError};
+    use std::{fs, path::Path};
+
+    #[test]
+    fn single_digit_days_try_every_naming() {
+        let paths = candidate_paths(Path::new("data"), 2021, 4);
+        assert_eq!(
+            paths,
+            vec![
+                Path::new("data/2021/04.txt"),
+                Path::new("data/2021/4.txt"),
+                Path::new("data/2021/day04.txt"),
+            ]
+        );
+    }
+
+    #[test]
+    fn double_digit_days_do_not_repeat_paths() {
+        let paths = candidate_paths(Path::new("data"), 2022, 12);
+        assert_eq!(
+            paths,
+            vec![
+                Path::new("data/2022/12.txt"),
+                Path::new("data/2022/day12.txt")
+            ]
+        );
+    }
+
+    #[test]
+    fn missing_input_lists_searched_paths() {
+        let directory = std::env::temp_dir().join("aoc-common-missing-input");
+        match load_from(&directory, 2022, 1) {
+            Err(InputError::Missing { searched, .. }) => {
+                assert_eq!(searched.len(), 3);
+            }
+            _ => panic!("expected a missing input error"),
+        }
+    }
+
+    #[test]
+    fn loads_explicit_path() {
+        let path = std::env::temp_dir().join("aoc-common-explicit-input.txt");
+        fs::write(&path, "1\n2\n3\n").unwrap();
+        assert_eq!(load(2022, 1, Some(&path)).unwrap(), "1\n2\n3\n");
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn unreadable_explicit_path() {
+        let path = std::env::temp_dir().join("aoc-common-does-not-exist.txt");
+        assert!(matches!(
+            load(2022, 1, Some(&path)),
+            Err(InputError::Unreadable { .. })
+        ));
+    }
+}
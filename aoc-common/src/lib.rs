@@ -0,0 +1,13 @@
+pub mod bench;
+pub mod collections;
+pub mod cycle;
+pub mod geometry;
+pub mod grid;
+pub mod input;
+pub mod log;
+pub mod ocr;
+pub mod params;
+pub mod parse;
+pub mod render;
+pub mod search;
+pub mod solution;
@@ -0,0 +1,246 @@
+use crate::solution::Answer;
+
+// the letters puzzles draw on screens and paper, '#' where a pixel is lit. only the letters that
+// have turned up in puzzles so far are known
+const SMALL: [(char, [&str; 6]); 18] = [
+    ('A', [".##.", "#..#", "#..#", "####", "#..#", "#..#"]),
+    ('B', ["###.", "#..#", "###.", "#..#", "#..#", "###."]),
+    ('C', [".##.", "#..#", "#...", "#...", "#..#", ".##."]),
+    ('E', ["####", "#...", "###.", "#...", "#...", "####"]),
+    ('F', ["####", "#...", "###.", "#...", "#...", "#..."]),
+    ('G', [".##.", "#..#", "#...", "#.##", "#..#", ".###"]),
+    ('H', ["#..#", "#..#", "####", "#..#", "#..#", "#..#"]),
+    ('I', ["###", ".#.", ".#.", ".#.", ".#.", "###"]),
+    ('J', ["..##", "...#", "...#", "...#", "#..#", ".##."]),
+    ('K', ["#..#", "#.#.", "##..", "#.#.", "#.#.", "#..#"]),
+    ('L', ["#...", "#...", "#...", "#...", "#...", "####"]),
+    ('O', [".##.", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('P', ["###.", "#..#", "#..#", "###.", "#...", "#..."]),
+    ('R', ["###.", "#..#", "#..#", "###.", "#.#.", "#..#"]),
+    ('S', [".###", "#...", "#...", ".##.", "...#", "###."]),
+    ('U', ["#..#", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('Y', ["#...#", "#...#", ".#.#.", "..#..", "..#..", "..#.."]),
+    ('Z', ["####", "...#", "..#.", ".#..", "#...", "####"]),
+];
+
+const LARGE: [(char, [&str; 10]); 15] = [
+    (
+        'A',
+        [
+            "..##..", ".#..#.", "#....#", "#....#", "#....#", "######", "#....#", "#....#",
+            "#....#", "#....#",
+        ],
+    ),
+    (
+        'B',
+        [
+            "#####.", "#....#", "#....#", "#....#", "#####.", "#....#", "#....#", "#....#",
+            "#....#", "#####.",
+        ],
+    ),
+    (
+        'C',
+        [
+            ".####.", "#....#", "#.....", "#.....", "#.....", "#.....", "#.....", "#.....",
+            "#....#", ".####.",
+        ],
+    ),
+    (
+        'E',
+        [
+            "######", "#.....", "#.....", "#.....", "#####.", "#.....", "#.....", "#.....",
+            "#.....", "######",
+        ],
+    ),
+    (
+        'F',
+        [
+            "######", "#.....", "#.....", "#.....", "#####.", "#.....", "#.....", "#.....",
+            "#.....", "#.....",
+        ],
+    ),
+    (
+        'G',
+        [
+            ".####.", "#....#", "#.....", "#.....", "#.....", "#..###", "#....#", "#....#",
+            "#...##", ".###.#",
+        ],
+    ),
+    (
+        'H',
+        [
+            "#....#", "#....#", "#....#", "#....#", "######", "#....#", "#....#", "#....#",
+            "#....#", "#....#",
+        ],
+    ),
+    (
+        'J',
+        [
+            "...###", "....#.", "....#.", "....#.", "....#.", "....#.", "....#.", "#...#.",
+            "#...#.", ".###..",
+        ],
+    ),
+    (
+        'K',
+        [
+            "#....#", "#...#.", "#..#..", "#.#...", "##....", "##....", "#.#...", "#..#..",
+            "#...#.", "#....#",
+        ],
+    ),
+    (
+        'L',
+        [
+            "#.....", "#.....", "#.....", "#.....", "#.....", "#.....", "#.....", "#.....",
+            "#.....", "######",
+        ],
+    ),
+    (
+        'N',
+        [
+            "#....#", "##...#", "##...#", "#.#..#", "#.#..#", "#..#.#", "#..#.#", "#...##",
+            "#...##", "#....#",
+        ],
+    ),
+    (
+        'P',
+        [
+            "#####.", "#....#", "#....#", "#....#", "#####.", "#.....", "#.....", "#.....",
+            "#.....", "#.....",
+        ],
+    ),
+    (
+        'R',
+        [
+            "#####.", "#....#", "#....#", "#....#", "#####.", "#..#..", "#...#.", "#...#.",
+            "#....#", "#....#",
+        ],
+    ),
+    (
+        'X',
+        [
+            "#....#", "#....#", ".#..#.", ".#..#.", "..##..", "..##..", ".#..#.", ".#..#.",
+            "#....#", "#....#",
+        ],
+    ),
+    (
+        'Z',
+        [
+            "######", ".....#", ".....#", "....#.", "...#..", "..#...", ".#....", "#.....",
+            "#.....", "######",
+        ],
+    ),
+];
+
+// the text drawn in `rows`, any character other than '#' counting as dark. letters are told apart
+// by the blank columns between them, so the rows can have any amount of space around the text.
+// `None` when the rows aren't as tall as one of the fonts or something isn't a letter it knows
+pub fn read<R: AsRef<str>>(rows: &[R]) -> Option<String> {
+    let pixels: Vec<Vec<bool>> = rows
+        .iter()
+        .map(|row| row.as_ref().chars().map(|c| c == '#').collect())
+        .collect();
+    let width = pixels.iter().map(Vec::len).max()?;
+    let lit = |x: usize, y: usize| pixels[y].get(x).copied().unwrap_or(false);
+
+    let mut text = String::new();
+    let mut x = 0;
+    while x < width {
+        if (0..pixels.len()).all(|y| !lit(x, y)) {
+            x += 1;
+            continue;
+        }
+
+        let start = x;
+        while x < width && (0..pixels.len()).any(|y| lit(x, y)) {
+            x += 1;
+        }
+
+        let glyph: Vec<String> = (0..pixels.len())
+            .map(|y| {
+                (start..x)
+                    .map(|x| if lit(x, y) { '#' } else { '.' })
+                    .collect()
+            })
+            .collect();
+        text.push(letter(&glyph)?);
+    }
+
+    (!text.is_empty()).then_some(text)
+}
+
+fn letter(glyph: &[String]) -> Option<char> {
+    let matches = |pattern: &[&str]| pattern.iter().eq(glyph.iter());
+
+    match glyph.len() {
+        6 => SMALL
+            .iter()
+            .find(|(_, pattern)| matches(pattern))
+            .map(|&(letter, _)| letter),
+        10 => LARGE
+            .iter()
+            .find(|(_, pattern)| matches(pattern))
+            .map(|&(letter, _)| letter),
+        _ => None,
+    }
+}
+
+// the letters as the answer when they can be read, otherwise the drawing itself
+pub fn answer(rows: Vec<String>) -> Answer {
+    match read(&rows) {
+        Some(text) => Answer::Text(text),
+        None => Answer::Grid(rows),
+    }
+}
+
+#[cfg(test)]
+mod ocr_tests {
+    use crate::ocr::{answer, read, LARGE, SMALL};
+    use crate::solution::Answer;
+
+    // lays out letters from a font the way puzzles do, with a blank column after each
+    fn draw<const N: usize>(font: &[(char, [&str; N])], text: &str) -> Vec<String> {
+        (0..N)
+            .map(|y| {
+                text.chars()
+                    .map(|c| {
+                        let (_, pattern) = font.iter().find(|(letter, _)| *letter == c).unwrap();
+                        format!("{}.", pattern[y])
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn reads_every_letter_of_both_fonts() {
+        let small: String = SMALL.iter().map(|(letter, _)| letter).collect();
+        assert_eq!(read(&draw(&SMALL, &small)), Some(small));
+
+        let large: String = LARGE.iter().map(|(letter, _)| letter).collect();
+        assert_eq!(read(&draw(&LARGE, &large)), Some(large));
+    }
+
+    #[test]
+    fn ignores_space_around_the_text() {
+        let screen = [
+            "..........#..#.####.",
+            "..........#..#.#....",
+            "..........####.###..",
+            "..........#..#.#....",
+            "..........#..#.#....",
+            "..........#..#.####.",
+        ];
+
+        assert_eq!(read(&screen), Some("HE".to_owned()));
+    }
+
+    #[test]
+    fn anything_else_stays_a_drawing() {
+        let square = ["#####", "#...#", "#...#", "#...#", "#####"].map(str::to_owned);
+        assert_eq!(read(&square), None);
+        assert_eq!(answer(square.to_vec()), Answer::Grid(square.to_vec()));
+
+        assert_eq!(answer(draw(&SMALL, "OK")), Answer::Text("OK".to_owned()));
+        assert_eq!(read::<String>(&[]), None);
+    }
+}
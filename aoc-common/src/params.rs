@@ -0,0 +1,184 @@
+use std::{fmt, str::FromStr};
+
+// values a puzzle states in its text rather than its input, e.g. which row to look at. they often
+// differ between the example and the real puzzle, so a day gives defaults for both
+pub trait Params: Sized {
+    fn example() -> Self;
+
+    fn real() -> Self;
+
+    // changes one value by name, for overrides from the command line
+    fn set(&mut self, name: &str, value: &str) -> Result<(), ParamError>;
+}
+
+// days that don't need anything
+impl Params for () {
+    fn example() -> Self {}
+
+    fn real() -> Self {}
+
+    fn set(&mut self, name: &str, _value: &str) -> Result<(), ParamError> {
+        Err(ParamError::unknown(name))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParamError {
+    pub name: String,
+    pub message: String,
+}
+
+impl ParamError {
+    pub fn unknown(name: &str) -> Self {
+        ParamError {
+            name: name.to_owned(),
+            message: "this day has no such parameter".to_owned(),
+        }
+    }
+}
+
+impl fmt::Display for ParamError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "parameter \"{}\": {}", self.name, self.message)
+    }
+}
+
+impl std::error::Error for ParamError {}
+
+pub fn parse_value<T: FromStr>(name: &str, value: &str) -> Result<T, ParamError> {
+    value.parse().map_err(|_| ParamError {
+        name: name.to_owned(),
+        message: format!("\"{}\" is not a valid value", value),
+    })
+}
+
+// which defaults a run starts from and what to change on top of them
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Settings {
+    pub example: bool,
+    pub overrides: Vec<(String, String)>,
+}
+
+impl Settings {
+    pub fn example() -> Self {
+        Settings {
+            example: true,
+            overrides: vec![],
+        }
+    }
+
+    pub fn build<P: Params>(&self) -> Result<P, ParamError> {
+        let mut params = if self.example {
+            P::example()
+        } else {
+            P::real()
+        };
+
+        for (name, value) in &self.overrides {
+            params.set(name, value)?;
+        }
+
+        Ok(params)
+    }
+}
+
+// declares a day's parameter struct along with its example and real defaults. every field can be
+// overridden by name as long as its type implements `FromStr`
+//
+// params! {
+//     pub struct Day15Params {
+//         row: isize = (example: 10, real: 2_000_000),
+//     }
+// }
+#[macro_export]
+macro_rules! params {
+    (
+        $(#[$meta:meta])*
+        $vis:vis struct $name:ident {
+            $($field:ident: $ty:ty = (example: $example:expr, real: $real:expr)),* $(,)?
+        }
+    ) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, PartialEq, Eq)]
+        $vis struct $name {
+            $(pub $field: $ty,)*
+        }
+
+        impl $crate::params::Params for $name {
+            fn example() -> Self {
+                $name { $($field: $example,)* }
+            }
+
+            fn real() -> Self {
+                $name { $($field: $real,)* }
+            }
+
+            fn set(&mut self, name: &str, value: &str) -> Result<(), $crate::params::ParamError> {
+                match name {
+                    $(stringify!($field) => self.$field = $crate::params::parse_value(name, value)?,)*
+                    _ => return Err($crate::params::ParamError::unknown(name)),
+                }
+
+                Ok(())
+            }
+        }
+    };
+}
+
+#[cfg(test)]
+mod params_tests {
+    use crate::params::{ParamError, Params, Settings};
+
+    params! {
+        struct Search {
+            row: isize = (example: 10, real: 2_000_000),
+            wrap: bool = (example: false, real: true),
+        }
+    }
+
+    #[test]
+    fn defaults_for_example_and_real() {
+        assert_eq!(
+            Search::example(),
+            Search {
+                row: 10,
+                wrap: false
+            }
+        );
+        assert_eq!(Settings::default().build(), Ok(Search::real()));
+        assert_eq!(Settings::example().build(), Ok(Search::example()));
+    }
+
+    #[test]
+    fn overrides_apply_on_top_of_the_defaults() {
+        let settings = Settings {
+            example: true,
+            overrides: vec![("row".to_owned(), "-3".to_owned())],
+        };
+
+        assert_eq!(
+            settings.build(),
+            Ok(Search {
+                row: -3,
+                wrap: false
+            })
+        );
+    }
+
+    #[test]
+    fn bad_overrides() {
+        let mut search = Search::real();
+        assert_eq!(
+            search.set("column", "3"),
+            Err(ParamError::unknown("column"))
+        );
+
+        let error = search.set("row", "ten").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "parameter \"row\": \"ten\" is not a valid value"
+        );
+
+        assert!(().set("row", "10").is_err());
+    }
+}
@@ -0,0 +1,196 @@
+use nom::{
+    character::complete::{char, digit1, satisfy},
+    combinator::{map_res, opt, recognize},
+    error::ErrorKind,
+    sequence::pair,
+    IResult,
+};
+use std::{fmt, str::FromStr};
+
+// where parsing stopped, what was left over and what the parser wanted to see there
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub line: usize,
+    pub column: usize,
+    pub remaining: String,
+    pub expected: String,
+}
+
+impl ParseError {
+    // `remaining` has to be a slice of `input`, which is what nom hands back.
+    // a slice from the middle of the input (one bad line) reports everything after its start
+    pub fn at(input: &str, remaining: &str, expected: impl Into<String>) -> Self {
+        let offset = (remaining.as_ptr() as usize)
+            .checked_sub(input.as_ptr() as usize)
+            .filter(|offset| *offset <= input.len())
+            .unwrap_or(input.len() - remaining.len().min(input.len()));
+        let consumed = &input[..offset];
+        let line_start = consumed.rfind('\n').map(|index| index + 1).unwrap_or(0);
+
+        ParseError {
+            line: consumed.matches('\n').count() + 1,
+            column: consumed[line_start..].chars().count() + 1,
+            remaining: input[offset..].to_owned(),
+            expected: expected.into(),
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "parse error at line {}, column {}: expected {}, ",
+            self.line, self.column, self.expected
+        )?;
+        match self.remaining.lines().next() {
+            None => write!(f, "found end of input"),
+            Some("") => write!(f, "found a line break"),
+            Some(rest_of_line) => write!(f, "found \"{}\"", rest_of_line),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+// runs a nom parser and requires it to consume the whole input. saved puzzle inputs end with a
+// line break, so one is allowed to be left over
+pub fn parse_all<'a, T>(
+    input: &'a str,
+    mut parser: impl FnMut(&'a str) -> nom::IResult<&'a str, T>,
+) -> Result<T, ParseError> {
+    match parser(input) {
+        Ok(("" | "\n" | "\r\n", parsed)) => Ok(parsed),
+        Ok((remaining, _)) => Err(ParseError::at(input, remaining, "end of input")),
+        Err(nom::Err::Error(e)) | Err(nom::Err::Failure(e)) => {
+            Err(ParseError::at(input, e.input, expected(e.code)))
+        }
+        Err(nom::Err::Incomplete(_)) => {
+            Err(ParseError::at(input, &input[input.len()..], "more input"))
+        }
+    }
+}
+
+// an optionally negative integer of any type. unsigned types fail on a leading '-'
+pub fn parse_number<T: FromStr>(input: &str) -> IResult<&str, T> {
+    map_res(recognize(pair(opt(char('-')), digit1)), |s: &str| {
+        s.parse::<T>()
+    })(input)
+}
+
+// a single digit, for grids of numbers written without separators
+pub fn one_digit_parser<T: From<u8>>(input: &str) -> IResult<&str, T> {
+    let (input, digit_char) = satisfy(|c| c.is_ascii_digit())(input)?;
+    Ok((input, T::from(digit_char as u8 - b'0')))
+}
+
+fn expected(kind: ErrorKind) -> String {
+    match kind {
+        ErrorKind::Tag => "a literal tag".to_owned(),
+        ErrorKind::Char => "a specific character".to_owned(),
+        ErrorKind::Digit => "a digit".to_owned(),
+        ErrorKind::OneOf => "one of a set of characters".to_owned(),
+        ErrorKind::Alpha => "a letter".to_owned(),
+        ErrorKind::AlphaNumeric => "a letter or digit".to_owned(),
+        ErrorKind::Space | ErrorKind::MultiSpace => "whitespace".to_owned(),
+        ErrorKind::CrLf => "a line ending".to_owned(),
+        ErrorKind::MapRes => "a value that converts".to_owned(),
+        ErrorKind::Alt => "one of the alternatives".to_owned(),
+        ErrorKind::SeparatedList => "a list item".to_owned(),
+        ErrorKind::Eof => "end of input".to_owned(),
+        other => other.description().to_lowercase(),
+    }
+}
+
+#[cfg(test)]
+mod parse_tests {
+    use crate::parse::{one_digit_parser, parse_all, parse_number, ParseError};
+    use nom::{
+        bytes::complete::tag,
+        character::complete::{digit1, newline},
+        combinator::map_res,
+        multi::{many1, separated_list1},
+        IResult,
+    };
+
+    fn numbers_parser(input: &str) -> IResult<&str, Vec<u32>> {
+        separated_list1(newline, map_res(digit1, |s: &str| s.parse::<u32>()))(input)
+    }
+
+    #[test]
+    fn parses_entire_input() {
+        assert_eq!(parse_all("1\n2\n3", numbers_parser), Ok(vec![1, 2, 3]));
+    }
+
+    #[test]
+    fn leftover_input_reports_where_it_starts() {
+        let error = parse_all("1\n2\n3x4", numbers_parser).unwrap_err();
+        assert_eq!(error.line, 3);
+        assert_eq!(error.column, 2);
+        assert_eq!(error.remaining, "x4");
+        assert_eq!(error.expected, "end of input");
+        assert_eq!(
+            error.to_string(),
+            "parse error at line 3, column 2: expected end of input, found \"x4\""
+        );
+    }
+
+    #[test]
+    fn one_trailing_newline_is_allowed() {
+        assert_eq!(parse_all("1\n2\n", numbers_parser), Ok(vec![1, 2]));
+        assert_eq!(parse_all("1\r\n", numbers_parser), Ok(vec![1]));
+    }
+
+    #[test]
+    fn blank_lines_at_the_end_are_reported_as_leftover() {
+        let error = parse_all("1\n2\n\n", numbers_parser).unwrap_err();
+        assert_eq!((error.line, error.column), (2, 2));
+        assert_eq!(error.remaining, "\n\n");
+        assert!(error.to_string().ends_with("found a line break"));
+    }
+
+    #[test]
+    fn failed_parser_reports_expected_token() {
+        let error = parse_all("start 12", |input| tag("begin")(input)).unwrap_err();
+        assert_eq!((error.line, error.column), (1, 1));
+        assert_eq!(error.expected, "a literal tag");
+
+        let error = parse_all("ab", numbers_parser).unwrap_err();
+        assert_eq!(error.expected, "a digit");
+    }
+
+    #[test]
+    fn error_at_any_slice_of_the_input() {
+        let input = "10\n20\nabc\n";
+        let bad_line = input.lines().nth(2).unwrap();
+        let error = ParseError::at(input, bad_line, "a number");
+        assert_eq!((error.line, error.column), (3, 1));
+        assert_eq!(error.remaining, "abc\n");
+        assert_eq!(
+            error.to_string(),
+            "parse error at line 3, column 1: expected a number, found \"abc\""
+        );
+    }
+
+    #[test]
+    fn parse_number_handles_signs() {
+        assert_eq!(parse_number::<i32>("-42,7"), Ok((",7", -42)));
+        assert_eq!(parse_number::<isize>("42"), Ok(("", 42)));
+        assert_eq!(
+            parse_number::<u64>("18446744073709551615"),
+            Ok(("", u64::MAX))
+        );
+        assert!(parse_number::<usize>("-1").is_err());
+        assert!(parse_number::<i32>("-").is_err());
+    }
+
+    #[test]
+    fn one_digit_parser_reads_a_single_digit() {
+        assert_eq!(one_digit_parser::<u32>("123"), Ok(("23", 1)));
+        assert_eq!(
+            many1(one_digit_parser::<u8>)("909\n"),
+            Ok(("\n", vec![9, 0, 9]))
+        );
+        assert!(one_digit_parser::<u32>("a1").is_err());
+    }
+}
@@ -0,0 +1,386 @@
+use crate::error;
+use crate::grid::{Grid, Position};
+use std::{
+    collections::HashMap,
+    fs,
+    io::{self, Write},
+    path::PathBuf,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Mutex,
+    },
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Colour(pub u8, pub u8, pub u8);
+
+impl Colour {
+    pub const BLACK: Colour = Colour(0, 0, 0);
+    pub const WHITE: Colour = Colour(255, 255, 255);
+    pub const RED: Colour = Colour(220, 50, 47);
+    pub const GREEN: Colour = Colour(80, 200, 80);
+    pub const BLUE: Colour = Colour(60, 120, 230);
+    pub const YELLOW: Colour = Colour(250, 220, 60);
+
+    // 0 is black and 255 white
+    pub fn grey(level: u8) -> Colour {
+        Colour(level, level, level)
+    }
+}
+
+// what one position looks like: a character for the terminal and, optionally, the colour it's
+// drawn in there and in images
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Cell {
+    pub glyph: char,
+    pub colour: Option<Colour>,
+}
+
+impl Cell {
+    pub fn coloured(glyph: char, colour: Colour) -> Self {
+        Cell {
+            glyph,
+            colour: Some(colour),
+        }
+    }
+
+    // images need a colour for everything, blanks are black and anything else white
+    fn pixel(&self) -> Colour {
+        match (self.colour, self.glyph) {
+            (Some(colour), _) => colour,
+            (None, ' ' | '.') => Colour::BLACK,
+            (None, _) => Colour::WHITE,
+        }
+    }
+}
+
+impl From<char> for Cell {
+    fn from(glyph: char) -> Self {
+        Cell {
+            glyph,
+            colour: None,
+        }
+    }
+}
+
+// a picture of some state. dense grids get one with `grid.map(...)`, sparse ones with `points`
+pub type Canvas = Grid<Cell>;
+
+// just big enough to hold every point, with `background` everywhere else. (0, 0) on the canvas is
+// the smallest x and y among the points
+pub fn points(points: impl IntoIterator<Item = (Position, Cell)>, background: Cell) -> Canvas {
+    let points: Vec<(Position, Cell)> = points.into_iter().collect();
+    let Some(min_x) = points.iter().map(|&((x, _), _)| x).min() else {
+        return Grid::new(0, 0, background);
+    };
+    let min_y = points.iter().map(|&((_, y), _)| y).min().unwrap();
+    let max_x = points.iter().map(|&((x, _), _)| x).max().unwrap();
+    let max_y = points.iter().map(|&((_, y), _)| y).max().unwrap();
+
+    let mut canvas = Grid::new(
+        (max_x - min_x + 1) as usize,
+        (max_y - min_y + 1) as usize,
+        background,
+    );
+    for ((x, y), cell) in points {
+        canvas[(x - min_x, y - min_y)] = cell;
+    }
+
+    canvas
+}
+
+pub fn text(canvas: &Canvas) -> String {
+    canvas
+        .rows()
+        .map(|row| row.iter().map(|cell| cell.glyph).collect::<String>())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+// like `text`, with 24-bit ANSI colours for the cells that have one
+pub fn ansi(canvas: &Canvas) -> String {
+    canvas
+        .rows()
+        .map(|row| {
+            row.iter()
+                .map(|cell| match cell.colour {
+                    Some(Colour(r, g, b)) => {
+                        format!("\x1b[38;2;{};{};{}m{}\x1b[0m", r, g, b, cell.glyph)
+                    }
+                    None => cell.glyph.to_string(),
+                })
+                .collect::<String>()
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+// every cell is drawn as a square this many pixels wide
+pub const PIXELS_PER_CELL: usize = 4;
+
+// the image as rows of RGB bytes, with each row `PIXELS_PER_CELL` times as wide as the canvas
+fn pixel_rows(canvas: &Canvas) -> Vec<Vec<u8>> {
+    canvas
+        .rows()
+        .flat_map(|row| {
+            let pixels: Vec<u8> = row
+                .iter()
+                .flat_map(|cell| {
+                    let Colour(r, g, b) = cell.pixel();
+                    [r, g, b].repeat(PIXELS_PER_CELL)
+                })
+                .collect();
+            vec![pixels; PIXELS_PER_CELL]
+        })
+        .collect()
+}
+
+pub fn write_ppm(canvas: &Canvas, out: &mut impl Write) -> io::Result<()> {
+    write!(
+        out,
+        "P6\n{} {}\n255\n",
+        canvas.width() * PIXELS_PER_CELL,
+        canvas.height() * PIXELS_PER_CELL
+    )?;
+    for row in pixel_rows(canvas) {
+        out.write_all(&row)?;
+    }
+
+    Ok(())
+}
+
+// an uncompressed PNG, which any viewer can open without pulling in an image library
+pub fn write_png(canvas: &Canvas, out: &mut impl Write) -> io::Result<()> {
+    let mut header = vec![];
+    header.extend(((canvas.width() * PIXELS_PER_CELL) as u32).to_be_bytes());
+    header.extend(((canvas.height() * PIXELS_PER_CELL) as u32).to_be_bytes());
+    // 8 bits per channel, RGB, the only compression, filter and interlace methods there are
+    header.extend([8, 2, 0, 0, 0]);
+
+    // every row starts with the filter it uses, 0 for none
+    let mut raw = vec![];
+    for row in pixel_rows(canvas) {
+        raw.push(0);
+        raw.extend(row);
+    }
+
+    out.write_all(&[0x89, b'P', b'N', b'G', b'\r', b'\n', 0x1a, b'\n'])?;
+    write_chunk(out, b"IHDR", &header)?;
+    write_chunk(out, b"IDAT", &zlib_stored(&raw))?;
+    write_chunk(out, b"IEND", &[])
+}
+
+fn write_chunk(out: &mut impl Write, kind: &[u8; 4], data: &[u8]) -> io::Result<()> {
+    out.write_all(&(data.len() as u32).to_be_bytes())?;
+    out.write_all(kind)?;
+    out.write_all(data)?;
+
+    let mut checked = kind.to_vec();
+    checked.extend_from_slice(data);
+    out.write_all(&crc32(&checked).to_be_bytes())
+}
+
+// a zlib stream made of deflate blocks that are stored as they are
+fn zlib_stored(data: &[u8]) -> Vec<u8> {
+    let mut stream = vec![0x78, 0x01];
+    let mut blocks = data.chunks(u16::MAX as usize).peekable();
+    if blocks.peek().is_none() {
+        stream.extend([1, 0, 0, 0xff, 0xff]);
+    }
+    while let Some(block) = blocks.next() {
+        let last = blocks.peek().is_none();
+        let length = block.len() as u16;
+        stream.push(last as u8);
+        stream.extend(length.to_le_bytes());
+        stream.extend((!length).to_le_bytes());
+        stream.extend_from_slice(block);
+    }
+    stream.extend(adler32(data).to_be_bytes());
+
+    stream
+}
+
+fn crc32(data: &[u8]) -> u32 {
+    let mut crc = 0xffff_ffff_u32;
+    for &byte in data {
+        crc ^= byte as u32;
+        for _ in 0..8 {
+            crc = if crc & 1 == 1 {
+                (crc >> 1) ^ 0xedb8_8320
+            } else {
+                crc >> 1
+            };
+        }
+    }
+
+    !crc
+}
+
+fn adler32(data: &[u8]) -> u32 {
+    let (mut a, mut b) = (1_u32, 0_u32);
+    for &byte in data {
+        a = (a + byte as u32) % 65521;
+        b = (b + a) % 65521;
+    }
+
+    (b << 16) | a
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FrameFormat {
+    Text,
+    Ppm,
+    Png,
+}
+
+impl FrameFormat {
+    fn extension(&self) -> &'static str {
+        match self {
+            FrameFormat::Text => "txt",
+            FrameFormat::Ppm => "ppm",
+            FrameFormat::Png => "png",
+        }
+    }
+}
+
+// where the frames days draw of their simulations go
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FrameSink {
+    // stderr, one frame after the other
+    Terminal { colour: bool },
+    // numbered files in a directory per day, e.g. `rust_2022-day17/00012.png`
+    Directory(PathBuf, FrameFormat),
+}
+
+static FRAMES_ENABLED: AtomicBool = AtomicBool::new(false);
+static SINK: Mutex<Option<FrameSink>> = Mutex::new(None);
+static FRAME_COUNTS: Mutex<Option<HashMap<String, usize>>> = Mutex::new(None);
+
+pub fn set_frame_sink(sink: Option<FrameSink>) {
+    FRAMES_ENABLED.store(sink.is_some(), Ordering::Relaxed);
+    *SINK.lock().unwrap() = sink;
+}
+
+pub fn frames_enabled() -> bool {
+    FRAMES_ENABLED.load(Ordering::Relaxed)
+}
+
+pub fn save_frame(source: &str, canvas: &Canvas) {
+    let Some(sink) = SINK.lock().unwrap().clone() else {
+        return;
+    };
+
+    let number = {
+        let mut counts = FRAME_COUNTS.lock().unwrap();
+        let count = counts
+            .get_or_insert_with(HashMap::new)
+            .entry(source.to_owned())
+            .or_insert(0);
+        *count += 1;
+        *count
+    };
+
+    let result = match sink {
+        FrameSink::Terminal { colour } => {
+            let drawn = if colour { ansi(canvas) } else { text(canvas) };
+            writeln!(io::stderr(), "{} frame {}\n{}\n", source, number, drawn)
+        }
+        FrameSink::Directory(directory, format) => {
+            let directory = directory.join(source.replace("::", "-"));
+            let path = directory.join(format!("{:05}.{}", number, format.extension()));
+            fs::create_dir_all(&directory)
+                .and_then(|_| fs::File::create(&path))
+                .map(io::BufWriter::new)
+                .and_then(|mut file| match format {
+                    FrameFormat::Text => writeln!(file, "{}", text(canvas)),
+                    FrameFormat::Ppm => write_ppm(canvas, &mut file),
+                    FrameFormat::Png => write_png(canvas, &mut file),
+                })
+        }
+    };
+
+    if let Err(e) = result {
+        error!("couldn't save frame {} of {}: {}", number, source, e);
+    }
+}
+
+// `frame!(render_state(&state))` draws the state as the next frame of the calling day, but only
+// when frames were asked for, so the drawing costs nothing otherwise
+#[macro_export]
+macro_rules! frame {
+    ($canvas:expr) => {
+        if $crate::render::frames_enabled() {
+            $crate::render::save_frame(module_path!(), &$canvas);
+        }
+    };
+}
+
+#[cfg(test)]
+mod render_tests {
+    use crate::render::{
+        adler32, ansi, crc32, points, text, write_png, write_ppm, Cell, Colour, PIXELS_PER_CELL,
+    };
+
+    #[test]
+    fn points_are_framed_by_their_bounds() {
+        let canvas = points(
+            [
+                ((-1, 2), '#'.into()),
+                ((2, 3), Cell::coloured('o', Colour::RED)),
+            ],
+            '.'.into(),
+        );
+
+        assert_eq!((canvas.width(), canvas.height()), (4, 2));
+        assert_eq!(text(&canvas), "#...\n...o");
+        assert_eq!(points([], ' '.into()).width(), 0);
+    }
+
+    #[test]
+    fn ansi_colours_only_coloured_cells() {
+        let canvas = points(
+            [
+                ((0, 0), '#'.into()),
+                ((1, 0), Cell::coloured('o', Colour(1, 2, 3))),
+            ],
+            '.'.into(),
+        );
+
+        assert_eq!(ansi(&canvas), "#\x1b[38;2;1;2;3mo\x1b[0m");
+    }
+
+    #[test]
+    fn ppm_has_a_pixel_square_per_cell() {
+        let canvas = points(
+            [
+                ((0, 0), '#'.into()),
+                ((1, 1), Cell::coloured('o', Colour::RED)),
+            ],
+            '.'.into(),
+        );
+        let mut image = vec![];
+        write_ppm(&canvas, &mut image).unwrap();
+
+        let size = 2 * PIXELS_PER_CELL;
+        let header = format!("P6\n{} {}\n255\n", size, size);
+        assert!(image.starts_with(header.as_bytes()));
+        assert_eq!(image.len(), header.len() + size * size * 3);
+
+        let pixels = &image[header.len()..];
+        assert_eq!(&pixels[..3], &[255, 255, 255]);
+        assert_eq!(&pixels[pixels.len() - 3..], &[220, 50, 47]);
+    }
+
+    #[test]
+    fn png_chunks() {
+        // well known values for both checksums
+        assert_eq!(crc32(b"IEND"), 0xae42_6082);
+        assert_eq!(adler32(b"Wikipedia"), 0x11e6_0398);
+
+        let mut image = vec![];
+        write_png(&points([((0, 0), '#'.into())], '.'.into()), &mut image).unwrap();
+
+        assert!(image.starts_with(b"\x89PNG\r\n\x1a\n\0\0\0\x0dIHDR"));
+        assert!(image.ends_with(b"\0\0\0\0IEND\xae\x42\x60\x82"));
+    }
+}
@@ -0,0 +1,179 @@
+use std::{
+    cmp::Reverse,
+    collections::{hash_map::Entry, BinaryHeap, HashMap},
+    hash::Hash,
+    ops::Add,
+};
+
+// the cheapest way to a goal: what it cost and every state along the way, start and goal included
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SearchResult<S, C> {
+    pub cost: C,
+    pub path: Vec<S>,
+}
+
+// dijkstra from any of `starts` until `is_goal` says yes. `successors` gives the states reachable
+// from a state along with the cost of getting there
+pub fn dijkstra<S, C, I>(
+    starts: impl IntoIterator<Item = S>,
+    successors: impl FnMut(&S) -> I,
+    is_goal: impl FnMut(&S) -> bool,
+) -> Option<SearchResult<S, C>>
+where
+    S: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (S, C)>,
+{
+    a_star(starts, successors, |_| C::default(), is_goal)
+}
+
+// dijkstra that looks at the states `heuristic` thinks are closest first. the heuristic must never
+// guess more than the real remaining cost or the answer may not be the cheapest
+pub fn a_star<S, C, I>(
+    starts: impl IntoIterator<Item = S>,
+    mut successors: impl FnMut(&S) -> I,
+    mut heuristic: impl FnMut(&S) -> C,
+    mut is_goal: impl FnMut(&S) -> bool,
+) -> Option<SearchResult<S, C>>
+where
+    S: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (S, C)>,
+{
+    // states are stored once and referred to by index from then on
+    let mut states: Vec<S> = vec![];
+    let mut indexes: HashMap<S, usize> = HashMap::new();
+    // cheapest known cost to each state and the state it was reached from
+    let mut best: Vec<(C, Option<usize>)> = vec![];
+    let mut to_visit = BinaryHeap::new();
+
+    for start in starts {
+        if let Entry::Vacant(entry) = indexes.entry(start.clone()) {
+            entry.insert(states.len());
+            to_visit.push(Reverse((heuristic(&start), C::default(), states.len())));
+            states.push(start);
+            best.push((C::default(), None));
+        }
+    }
+
+    while let Some(Reverse((_, cost, index))) = to_visit.pop() {
+        if cost > best[index].0 {
+            // already got here more cheaply
+            continue;
+        }
+
+        if is_goal(&states[index]) {
+            let mut path = vec![states[index].clone()];
+            let mut current = index;
+            while let (_, Some(previous)) = best[current] {
+                path.push(states[previous].clone());
+                current = previous;
+            }
+            path.reverse();
+
+            return Some(SearchResult { cost, path });
+        }
+
+        for (next, step_cost) in successors(&states[index]) {
+            let next_cost = cost + step_cost;
+            let next_index = match indexes.entry(next) {
+                Entry::Occupied(entry) => {
+                    let next_index = *entry.get();
+                    if next_cost >= best[next_index].0 {
+                        continue;
+                    }
+                    best[next_index] = (next_cost, Some(index));
+                    next_index
+                }
+                Entry::Vacant(entry) => {
+                    let next_index = states.len();
+                    states.push(entry.key().clone());
+                    entry.insert(next_index);
+                    best.push((next_cost, Some(index)));
+                    next_index
+                }
+            };
+
+            let estimate = next_cost + heuristic(&states[next_index]);
+            to_visit.push(Reverse((estimate, next_cost, next_index)));
+        }
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod search_tests {
+    use crate::geometry::manhattan_distance;
+    use crate::grid::Grid;
+    use crate::search::{a_star, dijkstra};
+
+    // the direct route along the top is blocked, so the way round is longer
+    const MAZE: &str = "S.#.\n..#G\n....";
+
+    fn maze() -> Grid<char> {
+        Grid::parse(MAZE, "a maze cell", Some).unwrap()
+    }
+
+    fn open_neighbors(grid: &Grid<char>, position: (isize, isize)) -> Vec<((isize, isize), u32)> {
+        grid.orthogonal_neighbors(position)
+            .filter(|(_, &c)| c != '#')
+            .map(|(next, _)| (next, 1))
+            .collect()
+    }
+
+    #[test]
+    fn finds_the_cheapest_path() {
+        let grid = maze();
+        let result = dijkstra(
+            [(0, 0)],
+            |&position| open_neighbors(&grid, position),
+            |&position| grid[position] == 'G',
+        )
+        .unwrap();
+
+        assert_eq!(result.cost, 6);
+        assert_eq!(result.path.len(), 7);
+        assert_eq!(result.path.first(), Some(&(0, 0)));
+        assert_eq!(result.path.last(), Some(&(3, 1)));
+        assert!(result.path.iter().all(|&position| grid[position] != '#'));
+    }
+
+    #[test]
+    fn heuristic_gives_the_same_cost() {
+        let grid = maze();
+        let result = a_star(
+            [(0, 0)],
+            |&position| open_neighbors(&grid, position),
+            |&position| manhattan_distance(position, (3, 1)) as u32,
+            |&position| position == (3, 1),
+        )
+        .unwrap();
+
+        assert_eq!(result.cost, 6);
+    }
+
+    #[test]
+    fn picks_the_closest_start_and_weights() {
+        // going straight costs 10, the detour through b costs 2
+        let result = dijkstra(
+            ['a', 'z'],
+            |&state| match state {
+                'a' => vec![('c', 10), ('b', 1)],
+                'b' => vec![('c', 1)],
+                _ => vec![],
+            },
+            |&state| state == 'c',
+        )
+        .unwrap();
+
+        assert_eq!(result.cost, 2);
+        assert_eq!(result.path, vec!['a', 'b', 'c']);
+    }
+
+    #[test]
+    fn unreachable_goal() {
+        let result = dijkstra([0u8], |_| Vec::<(u8, u32)>::new(), |&state| state == 1);
+        assert_eq!(result, None);
+    }
+}
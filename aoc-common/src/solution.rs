@@ -0,0 +1,218 @@
+use crate::bench::{bench, BenchFn};
+use crate::params::{ParamError, Params, Settings};
+use crate::parse::ParseError;
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Answer {
+    Integer(i128),
+    Text(String),
+    // one string per row, e.g. the letters drawn on a screen
+    Grid(Vec<String>),
+    // the part has no puzzle (day 25 part 2) or hasn't been solved yet
+    Unsolved,
+}
+
+impl Answer {
+    pub fn grid<R: IntoIterator<Item = String>>(rows: R) -> Self {
+        Answer::Grid(rows.into_iter().collect())
+    }
+}
+
+macro_rules! answer_from_integer {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(value: $t) -> Self {
+                    Answer::Integer(value as i128)
+                }
+            }
+        )*
+    };
+}
+
+answer_from_integer!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize);
+
+impl From<String> for Answer {
+    fn from(value: String) -> Self {
+        Answer::Text(value)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(value: &str) -> Self {
+        Answer::Text(value.to_owned())
+    }
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Answer::Integer(value) => write!(f, "{}", value),
+            Answer::Text(value) => write!(f, "{}", value),
+            Answer::Grid(rows) => write!(f, "{}", rows.join("\n")),
+            Answer::Unsolved => write!(f, "unsolved"),
+        }
+    }
+}
+
+// every day parses its input once and then answers both parts from the parsed value.
+// `Input` can borrow from the puzzle text for days that keep slices of it around.
+// `Params` holds anything the puzzle text sets apart from the input, `()` for most days.
+pub trait Solution {
+    type Input<'a>;
+
+    type Params: Params;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError>;
+
+    fn part1(input: &Self::Input<'_>, params: &Self::Params) -> Answer;
+
+    fn part2(input: &Self::Input<'_>, params: &Self::Params) -> Answer;
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SolveError {
+    Parse(ParseError),
+    Param(ParamError),
+}
+
+impl fmt::Display for SolveError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SolveError::Parse(e) => write!(f, "{}", e),
+            SolveError::Param(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl std::error::Error for SolveError {}
+
+impl From<ParseError> for SolveError {
+    fn from(e: ParseError) -> Self {
+        SolveError::Parse(e)
+    }
+}
+
+impl From<ParamError> for SolveError {
+    fn from(e: ParamError) -> Self {
+        SolveError::Param(e)
+    }
+}
+
+// solves a day: takes the input, the parts to run and the settings its parameters are built from
+pub type DayFn = fn(&str, &[u32], &Settings) -> Result<Vec<Answer>, SolveError>;
+
+// what a year crate registers for each day, `Day::of::<Day01>()`
+#[derive(Clone, Copy)]
+pub struct Day {
+    pub run: DayFn,
+    pub bench: BenchFn,
+}
+
+impl Day {
+    pub const fn of<S: Solution>() -> Self {
+        Day {
+            run: run::<S>,
+            bench: bench::<S>,
+        }
+    }
+}
+
+// parses the input and runs the requested parts in order
+pub fn solve<S: Solution>(
+    input: &str,
+    parts: &[u32],
+    params: &S::Params,
+) -> Result<Vec<Answer>, ParseError> {
+    let parsed = S::parse(input)?;
+
+    Ok(parts
+        .iter()
+        .map(|part| match part {
+            1 => S::part1(&parsed, params),
+            _ => S::part2(&parsed, params),
+        })
+        .collect())
+}
+
+// `solve` with the parameters built from settings, which is how the runner calls a day
+pub fn run<S: Solution>(
+    input: &str,
+    parts: &[u32],
+    settings: &Settings,
+) -> Result<Vec<Answer>, SolveError> {
+    let params = settings.build::<S::Params>()?;
+
+    Ok(solve::<S>(input, parts, &params)?)
+}
+
+#[cfg(test)]
+mod solution_tests {
+    use crate::params::{ParamError, Settings};
+    use crate::parse::{parse_all, ParseError};
+    use crate::solution::{run, solve, Answer, Solution, SolveError};
+    use nom::{character::complete::digit1, combinator::map_res, IResult};
+
+    fn number_parser(input: &str) -> IResult<&str, u32> {
+        map_res(digit1, |s: &str| s.parse::<u32>())(input)
+    }
+
+    struct Doubler;
+
+    impl Solution for Doubler {
+        type Input<'a> = u32;
+
+        type Params = ();
+
+        fn parse(input: &str) -> Result<u32, ParseError> {
+            parse_all(input, number_parser)
+        }
+
+        fn part1(input: &u32, _params: &()) -> Answer {
+            (input * 2).into()
+        }
+
+        fn part2(input: &u32, _params: &()) -> Answer {
+            format!("{}{}", input, input).into()
+        }
+    }
+
+    #[test]
+    fn solve_runs_requested_parts() {
+        assert_eq!(
+            solve::<Doubler>("21", &[1, 2], &()),
+            Ok(vec![Answer::Integer(42), Answer::Text("2121".to_owned())])
+        );
+        assert_eq!(solve::<Doubler>("21", &[2], &()).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn solve_stops_on_parse_errors() {
+        let error = solve::<Doubler>("21x", &[1, 2], &()).unwrap_err();
+        assert_eq!((error.line, error.column), (1, 3));
+    }
+
+    #[test]
+    fn run_rejects_unknown_parameters() {
+        let settings = Settings {
+            example: false,
+            overrides: vec![("rounds".to_owned(), "3".to_owned())],
+        };
+
+        assert_eq!(
+            run::<Doubler>("21", &[1], &settings),
+            Err(SolveError::Param(ParamError::unknown("rounds")))
+        );
+        assert_eq!(
+            run::<Doubler>("21", &[1], &Settings::example()),
+            Ok(vec![Answer::Integer(42)])
+        );
+    }
+
+    #[test]
+    fn grid_answers_display_one_row_per_line() {
+        let answer = Answer::grid(vec!["#.".to_owned(), ".#".to_owned()]);
+        assert_eq!(answer.to_string(), "#.\n.#");
+    }
+}
@@ -0,0 +1,46 @@
+use aoc_common::bench::{table, Stats, Step};
+use aoc_common::input::load;
+use aoc_common::params::Settings;
+use aoc_common::solution::Day;
+use std::env;
+
+const YEARS: [(u32, &[(u32, Day)]); 2] = [
+    (rust_2021::YEAR, &rust_2021::DAYS),
+    (rust_2022::YEAR, &rust_2022::DAYS),
+];
+
+const RUNS: usize = 10;
+
+fn main() {
+    // cargo passes `--bench` along, anything else is `YEAR` or `YEAR/DAY`
+    let filter = env::args().skip(1).find(|arg| !arg.starts_with('-'));
+    let selected = |year: u32, day: u32| match &filter {
+        Some(filter) => filter == &year.to_string() || filter == &format!("{}/{}", year, day),
+        None => true,
+    };
+
+    let mut rows: Vec<(String, Step, Stats)> = vec![];
+    for (year, days) in YEARS {
+        for (day, entry) in days.iter().filter(|(day, _)| selected(year, *day)) {
+            let Ok(input) = load(year, *day, None) else {
+                continue;
+            };
+
+            match (entry.bench)(&input, &[1, 2], &Settings::default(), RUNS) {
+                Ok(steps) => rows.extend(
+                    steps
+                        .into_iter()
+                        .map(|(step, stats)| (format!("{}/{}", year, day), step, stats)),
+                ),
+                Err(e) => eprintln!("{} Day{:02}: {}", year, day, e),
+            }
+        }
+    }
+
+    if rows.is_empty() {
+        eprintln!("no inputs to time, they are read from ./data/[year]/[day].txt");
+        return;
+    }
+
+    println!("{}", table(&rows));
+}
@@ -0,0 +1,387 @@
+use aoc_common::bench::{table, Stats, Step};
+use aoc_common::log::{self, Level};
+use aoc_common::params::Settings;
+use aoc_common::render::{self, FrameFormat, FrameSink};
+use aoc_common::solution::{Answer, Day};
+use std::{env, path::PathBuf, process};
+
+const YEARS: [(u32, &[(u32, Day)]); 2] = [
+    (rust_2021::YEAR, &rust_2021::DAYS),
+    (rust_2022::YEAR, &rust_2022::DAYS),
+];
+
+#[derive(Debug, PartialEq, Eq)]
+struct Args {
+    year: Option<u32>,
+    day: Option<u32>,
+    part: Option<u32>,
+    all: bool,
+    input: Option<PathBuf>,
+    settings: Settings,
+    // timed runs of each step, `None` to just solve
+    bench: Option<usize>,
+    log_level: Level,
+    frames: Option<FrameSink>,
+}
+
+const DEFAULT_BENCH_RUNS: usize = 10;
+
+fn main() {
+    let args = match parse_args(env::args().skip(1)) {
+        Ok(args) => args,
+        Err(message) => {
+            eprintln!("{}", message);
+            eprintln!("{}", usage());
+            process::exit(2);
+        }
+    };
+
+    log::set_max_level(args.log_level);
+    render::set_frame_sink(args.frames.clone());
+
+    let parts: Vec<u32> = match args.part {
+        Some(part) => vec![part],
+        None => vec![1, 2],
+    };
+
+    let days: Vec<(u32, u32, Day)> = YEARS
+        .iter()
+        .filter(|(year, _)| args.year.is_none_or(|wanted| wanted == *year))
+        .flat_map(|(year, days)| days.iter().map(move |(day, solve)| (*year, *day, *solve)))
+        .filter(|(_, day, _)| args.day.is_none_or(|wanted| wanted == *day))
+        .collect();
+
+    let mut failed = false;
+    let mut timings: Vec<(String, Step, Stats)> = vec![];
+    for (year, day, entry) in days {
+        let input = match aoc_common::input::load(year, day, args.input.as_deref()) {
+            Ok(input) => input,
+            Err(e) => {
+                eprintln!("{}", e);
+                failed = true;
+                continue;
+            }
+        };
+
+        if let Some(runs) = args.bench {
+            match (entry.bench)(&input, &parts, &args.settings, runs) {
+                Ok(steps) => timings.extend(
+                    steps
+                        .into_iter()
+                        .map(|(step, stats)| (format!("{}/{}", year, day), step, stats)),
+                ),
+                Err(e) => {
+                    eprintln!("{} Day{:02}: {}", year, day, e);
+                    failed = true;
+                }
+            }
+
+            continue;
+        }
+
+        match (entry.run)(&input, &parts, &args.settings) {
+            Ok(answers) => {
+                for (part, answer) in parts.iter().zip(answers) {
+                    print_answer(year, day, *part, &answer);
+                }
+            }
+            Err(e) => {
+                eprintln!("{} Day{:02}: {}", year, day, e);
+                failed = true;
+            }
+        }
+    }
+
+    if args.bench.is_some() && !timings.is_empty() {
+        println!("{}", table(&timings));
+    }
+
+    if failed {
+        process::exit(1);
+    }
+}
+
+fn print_answer(year: u32, day: u32, part: u32, answer: &Answer) {
+    match answer {
+        Answer::Grid(_) => println!("{} Day{:02}: part {}\n{}", year, day, part, answer),
+        _ => println!("{} Day{:02}: part {} \"{}\"", year, day, part, answer),
+    }
+}
+
+fn parse_year(value: &str) -> Result<u32, String> {
+    match value.parse::<u32>() {
+        Ok(number) if YEARS.iter().any(|(year, _)| *year == number) => Ok(number),
+        _ => Err(format!("invalid year \"{}\"", value)),
+    }
+}
+
+fn parse_day(value: &str) -> Result<u32, String> {
+    match value.parse::<u32>() {
+        Ok(number) if (1..=25).contains(&number) => Ok(number),
+        _ => Err(format!("invalid day \"{}\"", value)),
+    }
+}
+
+fn parse_part(value: &str) -> Result<u32, String> {
+    match value.parse::<u32>() {
+        Ok(number) if number == 1 || number == 2 => Ok(number),
+        _ => Err(format!("invalid part \"{}\"", value)),
+    }
+}
+
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<Args, String> {
+    let mut year = None;
+    let mut day = None;
+    let mut part = None;
+    let mut all = false;
+    let mut input = None;
+    let mut settings = Settings::default();
+    let mut bench = None;
+    let mut runs = None;
+    let mut verbosity = 0;
+    let mut frames = None;
+    let mut frame_format = None;
+    let mut colour = false;
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--year" | "-y" => {
+                let value = args.next().ok_or("--year needs a value")?;
+                year = Some(parse_year(&value)?);
+            }
+            "--day" | "-d" => {
+                let value = args.next().ok_or("--day needs a value")?;
+                day = Some(parse_day(&value)?);
+            }
+            "--part" | "-p" => {
+                let value = args.next().ok_or("--part needs a value")?;
+                part = Some(parse_part(&value)?);
+            }
+            "--input" | "-i" => {
+                let value = args.next().ok_or("--input needs a path")?;
+                input = Some(PathBuf::from(value));
+            }
+            "--example" | "-e" => settings.example = true,
+            // `--param row=10`
+            "--param" => {
+                let value = args.next().ok_or("--param needs a NAME=VALUE")?;
+                match value.split_once('=') {
+                    Some((name, value)) => {
+                        settings.overrides.push((name.to_owned(), value.to_owned()))
+                    }
+                    None => return Err(format!("invalid parameter \"{}\"", value)),
+                }
+            }
+            "--bench" | "-b" => bench = Some(DEFAULT_BENCH_RUNS),
+            "--runs" => {
+                let value = args.next().ok_or("--runs needs a number")?;
+                match value.parse::<usize>() {
+                    Ok(number) if number > 0 => runs = Some(number),
+                    _ => return Err(format!("invalid number of runs \"{}\"", value)),
+                }
+            }
+            "--verbose" | "-v" => verbosity += 1,
+            "-vv" => verbosity += 2,
+            "-vvv" => verbosity += 3,
+            // `--frames -` draws them on the terminal
+            "--frames" => frames = Some(args.next().ok_or("--frames needs a directory or -")?),
+            "--frame-format" => {
+                let value = args.next().ok_or("--frame-format needs a format")?;
+                frame_format = Some(match value.as_str() {
+                    "png" => FrameFormat::Png,
+                    "ppm" => FrameFormat::Ppm,
+                    "text" => FrameFormat::Text,
+                    _ => return Err(format!("invalid frame format \"{}\"", value)),
+                });
+            }
+            "--colour" => colour = true,
+            "--all" | "-a" => all = true,
+            "--help" | "-h" => {
+                println!("{}", usage());
+                process::exit(0);
+            }
+            // `2022`, `2022/5` or `2022/5/1`
+            target if !target.starts_with('-') => {
+                let mut pieces = target.split('/');
+                year = pieces.next().map(parse_year).transpose()?;
+                day = pieces.next().map(parse_day).transpose()?;
+                part = pieces.next().map(parse_part).transpose()?;
+                if pieces.next().is_some() {
+                    return Err(format!("invalid target \"{}\"", target));
+                }
+            }
+            _ => return Err(format!("unknown argument \"{}\"", arg)),
+        }
+    }
+
+    if all && (year.is_some() || day.is_some()) {
+        return Err("--all runs every year and day, leave out the year and day".to_owned());
+    }
+
+    if !all && year.is_none() {
+        return Err("either a year or --all is required".to_owned());
+    }
+
+    if input.is_some() && day.is_none() {
+        return Err("--input can only be used with a single day".to_owned());
+    }
+
+    if !settings.overrides.is_empty() && day.is_none() {
+        return Err("--param can only be used with a single day".to_owned());
+    }
+
+    let frames = match (frames.as_deref(), frame_format, colour) {
+        (None, None, false) => None,
+        (Some("-"), None, colour) => Some(FrameSink::Terminal { colour }),
+        (Some("-"), Some(_), _) => {
+            return Err("--frame-format is for frames saved to a directory".to_owned())
+        }
+        (Some(directory), format, false) => Some(FrameSink::Directory(
+            PathBuf::from(directory),
+            format.unwrap_or(FrameFormat::Png),
+        )),
+        (Some(_), _, true) => return Err("--colour is for frames drawn on the terminal".to_owned()),
+        (None, _, _) => return Err("--frame-format and --colour need --frames".to_owned()),
+    };
+
+    if runs.is_some() && bench.is_none() {
+        return Err("--runs only applies to --bench".to_owned());
+    }
+
+    Ok(Args {
+        year,
+        day,
+        part,
+        all,
+        input,
+        settings,
+        bench: runs.or(bench),
+        log_level: Level::from_verbosity(verbosity),
+        frames,
+    })
+}
+
+fn usage() -> String {
+    format!(
+        "usage: {} (YEAR[/DAY[/PART]] | --all) [--year YEAR] [--day N] [--part P] [--input PATH] \
+         [--example] [--param NAME=VALUE]... [--bench [--runs N]] [-v...] \
+         [--frames (DIR [--frame-format png|ppm|text] | - [--colour])]",
+        env!("CARGO_BIN_NAME")
+    )
+}
+
+#[cfg(test)]
+mod main_tests {
+    use crate::{parse_args, Args};
+    use aoc_common::log::Level;
+    use aoc_common::params::Settings;
+    use aoc_common::render::{FrameFormat, FrameSink};
+    use std::path::PathBuf;
+
+    fn args(line: &str) -> Result<Args, String> {
+        parse_args(line.split_whitespace().map(str::to_owned))
+    }
+
+    #[test]
+    fn target_selects_year_day_and_part() {
+        assert_eq!(
+            args("2022/5/1"),
+            Ok(Args {
+                year: Some(2022),
+                day: Some(5),
+                part: Some(1),
+                all: false,
+                input: None,
+                settings: Settings::default(),
+                bench: None,
+                log_level: Level::Warn,
+                frames: None,
+            })
+        );
+        assert_eq!(args("2021/17").unwrap().part, None);
+        assert_eq!(args("2021").unwrap().day, None);
+    }
+
+    #[test]
+    fn flags_match_the_target() {
+        assert_eq!(args("--year 2022 --day 5 --part 1"), args("2022/5/1"));
+        assert_eq!(
+            args("2021/3 -i example.txt").unwrap().input,
+            Some(PathBuf::from("example.txt"))
+        );
+    }
+
+    #[test]
+    fn example_defaults_and_overrides() {
+        let settings = args("2022/15 -e --param row=11 --param search_size=30")
+            .unwrap()
+            .settings;
+        assert!(settings.example);
+        assert_eq!(
+            settings.overrides,
+            vec![
+                ("row".to_owned(), "11".to_owned()),
+                ("search_size".to_owned(), "30".to_owned())
+            ]
+        );
+        assert_eq!(args("2022/15").unwrap().settings, Settings::default());
+    }
+
+    #[test]
+    fn bench_runs() {
+        assert_eq!(args("2022/15 --bench").unwrap().bench, Some(10));
+        assert_eq!(args("--all -b --runs 3").unwrap().bench, Some(3));
+        assert_eq!(args("2022").unwrap().bench, None);
+    }
+
+    #[test]
+    fn verbosity() {
+        assert_eq!(args("2022/19").unwrap().log_level, Level::Warn);
+        assert_eq!(args("2022/19 -v").unwrap().log_level, Level::Info);
+        assert_eq!(
+            args("2022/19 --verbose -v").unwrap().log_level,
+            Level::Debug
+        );
+        assert_eq!(args("2022/19 -vvv").unwrap().log_level, Level::Trace);
+    }
+
+    #[test]
+    fn frames() {
+        assert_eq!(
+            args("2022/17 --frames out").unwrap().frames,
+            Some(FrameSink::Directory(PathBuf::from("out"), FrameFormat::Png))
+        );
+        assert_eq!(
+            args("2022/17 --frames out --frame-format ppm")
+                .unwrap()
+                .frames,
+            Some(FrameSink::Directory(PathBuf::from("out"), FrameFormat::Ppm))
+        );
+        assert_eq!(
+            args("2021/11 --frames - --colour").unwrap().frames,
+            Some(FrameSink::Terminal { colour: true })
+        );
+        assert_eq!(args("2021/11").unwrap().frames, None);
+    }
+
+    #[test]
+    fn rejects_bad_selections() {
+        assert!(args("").is_err());
+        assert!(args("2019/1").is_err());
+        assert!(args("2022/26").is_err());
+        assert!(args("2022/1/3").is_err());
+        assert!(args("2022/1/1/1").is_err());
+        assert!(args("--all 2022").is_err());
+        assert!(args("2022 --input example.txt").is_err());
+        assert!(args("--day 4").is_err());
+        assert!(args("2022 --param row=10").is_err());
+        assert!(args("2022/15 --param row").is_err());
+        assert!(args("2022 --runs 5").is_err());
+        assert!(args("2022 --bench --runs 0").is_err());
+        assert!(args("2022 -vx").is_err());
+        assert!(args("2022/17 --frames out --frame-format gif").is_err());
+        assert!(args("2022/17 --frames out --colour").is_err());
+        assert!(args("2022/17 --frames - --frame-format png").is_err());
+        assert!(args("2022/17 --colour").is_err());
+    }
+}
@@ -0,0 +1,85 @@
+use aoc_common::input::{data_directory, load};
+use aoc_common::params::Settings;
+use aoc_common::solution::Day;
+use std::fs;
+
+const YEARS: [(u32, &[(u32, Day)]); 2] = [
+    (rust_2021::YEAR, &rust_2021::DAYS),
+    (rust_2022::YEAR, &rust_2022::DAYS),
+];
+
+// `2022/1/2 45000`. answers that span several lines are written with `\n` between the rows
+fn parse_line(line: &str) -> Option<(u32, u32, u32, &str)> {
+    let (target, answer) = line.split_once(' ')?;
+    let mut pieces = target.split('/').map(|piece| piece.parse::<u32>().ok());
+
+    match (
+        pieces.next()?,
+        pieces.next()?,
+        pieces.next()?,
+        pieces.next(),
+    ) {
+        (Some(year), Some(day), Some(part), None) => Some((year, day, part, answer.trim())),
+        _ => None,
+    }
+}
+
+// checks the answers recorded in `data/answers.txt` against the real inputs next to it. neither
+// is checked in, so days without an input or an answer are skipped
+#[test]
+fn recorded_answers() {
+    let path = data_directory().join("answers.txt");
+    let Ok(recorded) = fs::read_to_string(&path) else {
+        eprintln!("no answers recorded in {}", path.display());
+        return;
+    };
+
+    let mut failures = vec![];
+    for line in recorded
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+    {
+        let (year, day, part, expected) =
+            parse_line(line).unwrap_or_else(|| panic!("can't read \"{}\" in answers.txt", line));
+
+        let solve_day = YEARS
+            .iter()
+            .filter(|(known_year, _)| *known_year == year)
+            .flat_map(|(_, days)| days.iter())
+            .find(|(known_day, _)| *known_day == day)
+            .map(|(_, entry)| entry.run)
+            .unwrap_or_else(|| panic!("there is no {} day {}", year, day));
+
+        let Ok(input) = load(year, day, None) else {
+            continue;
+        };
+
+        match solve_day(&input, &[part], &Settings::default()) {
+            Ok(answers) => {
+                let actual = answers[0].to_string().replace('\n', "\\n");
+                if actual != expected {
+                    failures.push(format!(
+                        "{}/{}/{}: expected {}, got {}",
+                        year, day, part, expected, actual
+                    ));
+                }
+            }
+            Err(e) => failures.push(format!("{}/{}/{}: {}", year, day, part, e)),
+        }
+    }
+
+    assert!(failures.is_empty(), "\n{}", failures.join("\n"));
+}
+
+#[test]
+fn reads_answer_lines() {
+    assert_eq!(parse_line("2022/1/2 45000"), Some((2022, 1, 2, "45000")));
+    assert_eq!(
+        parse_line("2021/13/2 #..#\\n#..#"),
+        Some((2021, 13, 2, "#..#\\n#..#"))
+    );
+    assert_eq!(parse_line("2022/1 45000"), None);
+    assert_eq!(parse_line("2022/1/2/1 45000"), None);
+    assert_eq!(parse_line("2022/1/2"), None);
+}
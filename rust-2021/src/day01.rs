@@ -1,63 +1,97 @@
-use itertools::Itertools;
-
-pub fn part1() -> usize {
-    let input = include_str!("../../data/2021/day01.txt");
-    let (_, count_increases) = input.lines().map(|n| n.parse::<usize>().unwrap()).fold(
-        (None, 0),
-        |(previous, count), current| match previous {
-            Some(previous_value) => {
-                if current > previous_value {
-                    (Some(current), count + 1)
-                } else {
-                    (Some(current), count)
-                }
-            }
-            None => (Some(current), count),
-        },
-    );
-    count_increases
-}
+use aoc_common::parse::ParseError;
+use aoc_common::solution::{Answer, Solution};
+
+pub struct Day01;
+
+impl Solution for Day01 {
+    type Input<'a> = Vec<usize>;
 
-pub fn part2() -> usize {
-    let input = include_str!("../../data/2021/day01.txt");
-    let input_numbers = input
-        .lines()
-        .into_iter()
-        .map(|n| n.parse::<usize>().unwrap())
-        .collect_vec();
-    let grouped = groups_of_with_step(3, 1, input_numbers);
-    let (_, count_increases) = grouped.iter().map(|v| v.iter().sum()).fold(
-        (None, 0),
-        |(previous, count): (Option<usize>, usize), current| match previous {
-            Some(previous_value) => {
-                if current > previous_value {
-                    (Some(current), count + 1)
-                } else {
-                    (Some(current), count)
+    type Params = ();
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        input
+            .lines()
+            .map(|n| {
+                n.parse::<usize>()
+                    .map_err(|_| ParseError::at(input, n, "a number"))
+            })
+            .collect()
+    }
+
+    fn part1(input_numbers: &Self::Input<'_>, _params: &Self::Params) -> Answer {
+        let (_, count_increases) =
+            input_numbers
+                .iter()
+                .fold((None, 0), |(previous, count), current| match previous {
+                    Some(previous_value) => {
+                        if current > previous_value {
+                            (Some(current), count + 1)
+                        } else {
+                            (Some(current), count)
+                        }
+                    }
+                    None => (Some(current), count),
+                });
+        count_increases.into()
+    }
+
+    fn part2(input_numbers: &Self::Input<'_>, _params: &Self::Params) -> Answer {
+        let grouped = groups_of_with_step(3, 1, input_numbers.clone());
+        let (_, count_increases) = grouped.iter().map(|v| v.iter().sum()).fold(
+            (None, 0),
+            |(previous, count): (Option<usize>, usize), current| match previous {
+                Some(previous_value) => {
+                    if current > previous_value {
+                        (Some(current), count + 1)
+                    } else {
+                        (Some(current), count)
+                    }
                 }
-            }
-            None => (Some(current), count),
-        },
-    );
+                None => (Some(current), count),
+            },
+        );
 
-    count_increases
+        count_increases.into()
+    }
 }
 
 fn groups_of_with_step(size: usize, step: usize, it: Vec<usize>) -> Vec<Vec<usize>> {
-    if size <= 0 || step <= 0 || it.len() < size {
+    if size == 0 || step == 0 || it.len() < size {
         return vec![];
     }
 
-    let this_group: Vec<usize> = it[0..size].iter().cloned().collect();
+    let this_group: Vec<usize> = it[0..size].to_vec();
 
     let mut with_group = Vec::new();
     with_group.push(this_group);
 
-    with_group.extend(groups_of_with_step(
-        size,
-        step,
-        it[step..].iter().cloned().collect(),
-    ));
+    with_group.extend(groups_of_with_step(size, step, it[step..].to_vec()));
 
     with_group
 }
+
+#[cfg(test)]
+mod day01_tests {
+    use crate::day01::Day01;
+    use aoc_common::solution::{solve, Answer};
+
+    const EXAMPLE: &str = "199
+200
+208
+210
+200
+207
+240
+269
+260
+263
+";
+
+    #[test]
+    fn example() {
+        assert_eq!(
+            solve::<Day01>(EXAMPLE, &[1, 2], &()).unwrap(),
+            [Answer::Integer(7), Answer::Integer(5)]
+        );
+    }
+}
@@ -1,37 +1,48 @@
+use aoc_common::parse::ParseError;
+use aoc_common::solution::{Answer, Solution};
 use itertools::Itertools;
 
 #[derive(Debug)]
-enum Instruction {
+pub enum Instruction {
     Up(i32),
     Down(i32),
     Forward(i32),
 }
 
-pub fn part1() -> i32 {
-    let input = include_str!("../../data/2021/day02.txt");
-    let instructions = parse_input(input);
-    let (final_x, final_y) = instructions
-        .iter()
-        .fold((0, 0), |(x, y), current| match current {
-            Instruction::Forward(distance) => (x + distance, y),
-            Instruction::Up(distance) => (x, y - distance),
-            Instruction::Down(distance) => (x, y + distance),
-        });
-    final_x * final_y
-}
+pub struct Day02;
+
+impl Solution for Day02 {
+    type Input<'a> = Vec<Instruction>;
+
+    type Params = ();
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        Ok(parse_input(input))
+    }
+
+    fn part1(instructions: &Self::Input<'_>, _params: &Self::Params) -> Answer {
+        let (final_x, final_y) =
+            instructions
+                .iter()
+                .fold((0, 0), |(x, y), current| match current {
+                    Instruction::Forward(distance) => (x + distance, y),
+                    Instruction::Up(distance) => (x, y - distance),
+                    Instruction::Down(distance) => (x, y + distance),
+                });
+        (final_x * final_y).into()
+    }
 
-pub fn part2() -> i32 {
-    let input = include_str!("../../data/2021/day02.txt");
-    let instructions = parse_input(input);
-    let (final_x, final_y, _) =
-        instructions
-            .iter()
-            .fold((0, 0, 0), |(x, y, aim), current| match current {
-                Instruction::Forward(distance) => (x + distance, y + (aim * distance), aim),
-                Instruction::Up(distance) => (x, y, aim - distance),
-                Instruction::Down(distance) => (x, y, aim + distance),
-            });
-    final_x * final_y
+    fn part2(instructions: &Self::Input<'_>, _params: &Self::Params) -> Answer {
+        let (final_x, final_y, _) =
+            instructions
+                .iter()
+                .fold((0, 0, 0), |(x, y, aim), current| match current {
+                    Instruction::Forward(distance) => (x + distance, y + (aim * distance), aim),
+                    Instruction::Up(distance) => (x, y, aim - distance),
+                    Instruction::Down(distance) => (x, y, aim + distance),
+                });
+        (final_x * final_y).into()
+    }
 }
 
 fn parse_input(input: &str) -> Vec<Instruction> {
@@ -46,3 +57,25 @@ fn parse_input(input: &str) -> Vec<Instruction> {
         })
         .collect_vec()
 }
+
+#[cfg(test)]
+mod day02_tests {
+    use crate::day02::Day02;
+    use aoc_common::solution::{solve, Answer};
+
+    const EXAMPLE: &str = "forward 5
+down 5
+forward 8
+up 3
+down 8
+forward 2
+";
+
+    #[test]
+    fn example() {
+        assert_eq!(
+            solve::<Day02>(EXAMPLE, &[1, 2], &()).unwrap(),
+            [Answer::Integer(150), Answer::Integer(900)]
+        );
+    }
+}
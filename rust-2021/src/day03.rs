@@ -1,3 +1,5 @@
+use aoc_common::parse::ParseError;
+use aoc_common::solution::{Answer, Solution};
 use std::collections::HashSet;
 
 #[derive(Clone)]
@@ -6,88 +8,98 @@ struct Counts {
     ones: i32,
 }
 
-pub fn part1() -> i32 {
-    let input = include_str!("../../data/2021/day03.txt");
-    let length = input.lines().next().unwrap().len();
+pub struct Day03;
 
-    let mut frequency: Vec<Counts> = vec![Counts { zeros: 0, ones: 0 }; length];
+impl Solution for Day03 {
+    type Input<'a> = &'a str;
 
-    input.lines().for_each(|line| {
-        for (index, char) in line.char_indices() {
-            if char == '1' {
-                frequency[index].ones += 1;
-            } else {
-                frequency[index].zeros += 1;
-            }
-        }
-    });
+    type Params = ();
 
-    let gamma_rate = frequency.iter().fold(0, |val_so_far, counts: &Counts| {
-        (val_so_far << 1) + (if counts.ones > counts.zeros { 1 } else { 0 })
-    });
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        Ok(input)
+    }
 
-    let epsilon_rate = frequency.iter().fold(0, |val_so_far, counts: &Counts| {
-        (val_so_far << 1) + (if counts.ones < counts.zeros { 1 } else { 0 })
-    });
+    fn part1(input: &Self::Input<'_>, _params: &Self::Params) -> Answer {
+        let length = input.lines().next().unwrap().len();
 
-    gamma_rate * epsilon_rate
-}
+        let mut frequency: Vec<Counts> = vec![Counts { zeros: 0, ones: 0 }; length];
 
-pub fn part2() -> i32 {
-    let input = include_str!("../../data/2021/day03.txt");
-    let length = input.lines().next().unwrap().len();
-
-    let mut remaining_lines_for_oxygen: HashSet<String> = input
-        .lines()
-        .map(|s| s.to_string())
-        .collect::<HashSet<String>>();
-
-    let mut remaining_lines_for_co2: HashSet<String> = remaining_lines_for_oxygen.clone();
-
-    let mut current_bit: usize = 0;
-    while remaining_lines_for_oxygen.len() > 1 && current_bit < length {
-        let counts = frequency_at_bit(&remaining_lines_for_oxygen, current_bit);
-        remaining_lines_for_oxygen.retain(|val| {
-            if counts.ones >= counts.zeros {
-                val.chars().nth(current_bit) == Some('1')
-            } else {
-                val.chars().nth(current_bit) == Some('0')
+        input.lines().for_each(|line| {
+            for (index, char) in line.char_indices() {
+                if char == '1' {
+                    frequency[index].ones += 1;
+                } else {
+                    frequency[index].zeros += 1;
+                }
             }
         });
 
-        current_bit += 1;
-    }
-
-    assert!(remaining_lines_for_oxygen.len() == 1);
+        let gamma_rate = frequency.iter().fold(0, |val_so_far, counts: &Counts| {
+            (val_so_far << 1) + (if counts.ones > counts.zeros { 1 } else { 0 })
+        });
 
-    current_bit = 0;
-    while remaining_lines_for_co2.len() > 1 && current_bit < length {
-        let counts = frequency_at_bit(&remaining_lines_for_co2, current_bit);
-        remaining_lines_for_co2.retain(|val| {
-            if counts.ones >= counts.zeros {
-                val.chars().nth(current_bit) == Some('0')
-            } else {
-                val.chars().nth(current_bit) == Some('1')
-            }
+        let epsilon_rate = frequency.iter().fold(0, |val_so_far, counts: &Counts| {
+            (val_so_far << 1) + (if counts.ones < counts.zeros { 1 } else { 0 })
         });
 
-        current_bit += 1;
+        (gamma_rate * epsilon_rate).into()
     }
 
-    assert!(remaining_lines_for_co2.len() == 1);
+    fn part2(input: &Self::Input<'_>, _params: &Self::Params) -> Answer {
+        let length = input.lines().next().unwrap().len();
+
+        let mut remaining_lines_for_oxygen: HashSet<String> = input
+            .lines()
+            .map(|s| s.to_string())
+            .collect::<HashSet<String>>();
+
+        let mut remaining_lines_for_co2: HashSet<String> = remaining_lines_for_oxygen.clone();
+
+        let mut current_bit: usize = 0;
+        while remaining_lines_for_oxygen.len() > 1 && current_bit < length {
+            let counts = frequency_at_bit(&remaining_lines_for_oxygen, current_bit);
+            remaining_lines_for_oxygen.retain(|val| {
+                if counts.ones >= counts.zeros {
+                    val.chars().nth(current_bit) == Some('1')
+                } else {
+                    val.chars().nth(current_bit) == Some('0')
+                }
+            });
+
+            current_bit += 1;
+        }
+
+        assert!(remaining_lines_for_oxygen.len() == 1);
+
+        current_bit = 0;
+        while remaining_lines_for_co2.len() > 1 && current_bit < length {
+            let counts = frequency_at_bit(&remaining_lines_for_co2, current_bit);
+            remaining_lines_for_co2.retain(|val| {
+                if counts.ones >= counts.zeros {
+                    val.chars().nth(current_bit) == Some('0')
+                } else {
+                    val.chars().nth(current_bit) == Some('1')
+                }
+            });
+
+            current_bit += 1;
+        }
+
+        assert!(remaining_lines_for_co2.len() == 1);
 
-    let oxygen_line = remaining_lines_for_oxygen.iter().next().unwrap();
-    let co2_line = remaining_lines_for_co2.iter().next().unwrap();
+        let oxygen_line = remaining_lines_for_oxygen.iter().next().unwrap();
+        let co2_line = remaining_lines_for_co2.iter().next().unwrap();
 
-    let oxygen_rate = oxygen_line.chars().fold(0, |val_so_far, bit| {
-        (val_so_far << 1) + (if bit == '1' { 1 } else { 0 })
-    });
+        let oxygen_rate = oxygen_line.chars().fold(0, |val_so_far, bit| {
+            (val_so_far << 1) + (if bit == '1' { 1 } else { 0 })
+        });
 
-    let co2_scrub_rate = co2_line.chars().fold(0, |val_so_far, bit| {
-        (val_so_far << 1) + (if bit == '1' { 1 } else { 0 })
-    });
+        let co2_scrub_rate = co2_line.chars().fold(0, |val_so_far, bit| {
+            (val_so_far << 1) + (if bit == '1' { 1 } else { 0 })
+        });
 
-    oxygen_rate * co2_scrub_rate
+        (oxygen_rate * co2_scrub_rate).into()
+    }
 }
 
 fn frequency_at_bit(remaining: &HashSet<String>, bit: usize) -> Counts {
@@ -107,3 +119,31 @@ fn frequency_at_bit(remaining: &HashSet<String>, bit: usize) -> Counts {
             }
         })
 }
+
+#[cfg(test)]
+mod day03_tests {
+    use crate::day03::Day03;
+    use aoc_common::solution::{solve, Answer};
+
+    const EXAMPLE: &str = "00100
+11110
+10110
+10111
+10101
+01111
+00111
+11100
+10000
+11001
+00010
+01010
+";
+
+    #[test]
+    fn example() {
+        assert_eq!(
+            solve::<Day03>(EXAMPLE, &[1, 2], &()).unwrap(),
+            [Answer::Integer(198), Answer::Integer(230)]
+        );
+    }
+}
@@ -1,3 +1,6 @@
+use aoc_common::parse::{parse_all, ParseError};
+use aoc_common::solution::{Answer, Solution};
+use aoc_common::warn;
 use itertools::Itertools;
 use nom::{
     bytes::complete::tag,
@@ -7,69 +10,55 @@ use nom::{
 };
 use std::collections::HashMap;
 
-pub fn part1() -> u32 {
-    let input = include_str!("../../data/2021/4.txt");
-
-    match input_parser(input) {
-        Ok((remaining_input, (values, starting_boards))) if remaining_input.len() == 0 => {
-            println!("parsed entire input");
-            let mut boards = starting_boards;
-            for value in values {
-                boards = play_value(value, &boards);
-                let winning_board = boards.iter().find_map(|board| score_board(board));
-                if let Some(ref score) = winning_board {
-                    return (*score) * value;
-                }
-            }
+type Board = HashMap<(u32, u32), (u32, bool)>;
 
-            0
-        }
-        Ok((remaining, _)) => {
-            println!("remaining unparsed \"{}\"", remaining);
-            0
-        }
-        Err(e) => {
-            println!("error parsing \"{}\"", e);
-            0
+pub struct Day04;
+
+impl Solution for Day04 {
+    type Input<'a> = (Vec<u32>, Vec<Board>);
+
+    type Params = ();
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse_all(input, input_parser)
+    }
+
+    fn part1((values, starting_boards): &Self::Input<'_>, _params: &Self::Params) -> Answer {
+        let mut boards = starting_boards.clone();
+        for &value in values {
+            boards = play_value(value, &boards);
+            let winning_board = boards.iter().find_map(score_board);
+            if let Some(ref score) = winning_board {
+                return ((*score) * value).into();
+            }
         }
+
+        warn!("no board ever wins");
+        Answer::Unsolved
     }
-}
 
-pub fn part2() -> u32 {
-    let input = include_str!("../../data/2021/4.txt");
-
-    match input_parser(input) {
-        Ok((remaining_input, (values, starting_boards))) if remaining_input.len() == 0 => {
-            println!("parsed entire input");
-            let mut boards = starting_boards;
-            for value in values {
-                boards = play_value(value, &boards);
-                if boards.len() > 1 {
-                    boards = boards.iter().filter_map(|board|
-                        //remove winning boards
-                        if let Some(ref score) = score_board(board) {
-                            None
-                        } else {
-                            Some(board.clone())
-                        }
-                    ).collect_vec();
-                } else {
-                    if let Some(ref score) = score_board(boards.first().unwrap()) {
-                        return (*score) * value;
+    fn part2((values, starting_boards): &Self::Input<'_>, _params: &Self::Params) -> Answer {
+        let mut boards = starting_boards.clone();
+        for &value in values {
+            boards = play_value(value, &boards);
+            if boards.len() > 1 {
+                boards = boards.iter().filter_map(|board|
+                    //remove winning boards
+                    if let Some(_score) = score_board(board) {
+                        None
+                    } else {
+                        Some(board.clone())
                     }
+                ).collect_vec();
+            } else {
+                if let Some(ref score) = score_board(boards.first().unwrap()) {
+                    return ((*score) * value).into();
                 }
             }
-
-            0
-        }
-        Ok((remaining, _)) => {
-            println!("remaining unparsed \"{}\"", remaining);
-            0
-        }
-        Err(e) => {
-            println!("error parsing \"{}\"", e);
-            0
         }
+
+        warn!("the last board never wins");
+        Answer::Unsolved
     }
 }
 
@@ -86,21 +75,20 @@ fn board_line_parser(input: &str) -> IResult<&str, Vec<(u32, bool)>> {
     Ok((input, line))
 }
 
-fn board_parser(input: &str) -> IResult<&str, HashMap<(u32, u32), (u32, bool)>> {
+fn board_parser(input: &str) -> IResult<&str, Board> {
     let (input, board) = nom::multi::count(board_line_parser, 5)(input)?;
 
-    let mut card: HashMap<(u32, u32), (u32, bool)> = HashMap::new();
-    for x in 0..board.len() {
-        let line = &board[x];
-        for y in 0..line.len() {
-            card.insert((x.try_into().unwrap(), y.try_into().unwrap()), line[y]);
+    let mut card: Board = HashMap::new();
+    for (x, line) in board.iter().enumerate() {
+        for (y, spot) in line.iter().enumerate() {
+            card.insert((x.try_into().unwrap(), y.try_into().unwrap()), *spot);
         }
     }
 
     Ok((input, card))
 }
 
-fn input_parser(input: &str) -> IResult<&str, (Vec<u32>, Vec<HashMap<(u32, u32), (u32, bool)>>)> {
+fn input_parser(input: &str) -> IResult<&str, (Vec<u32>, Vec<Board>)> {
     let (input, values) = separated_list0(
         tag(","),
         map_res(nom::character::complete::digit0, |s: &str| s.parse::<u32>()),
@@ -116,34 +104,28 @@ fn input_parser(input: &str) -> IResult<&str, (Vec<u32>, Vec<HashMap<(u32, u32),
     Ok((input, parse_result))
 }
 
-fn play_value(
-    new_value: u32,
-    boards: &Vec<HashMap<(u32, u32), (u32, bool)>>,
-) -> Vec<HashMap<(u32, u32), (u32, bool)>> {
+fn play_value(new_value: u32, boards: &[Board]) -> Vec<Board> {
     boards
         .iter()
         .map(|board| play_value_on_board(new_value, board))
         .collect_vec()
 }
 
-fn play_value_on_board(
-    new_value: u32,
-    board: &HashMap<(u32, u32), (u32, bool)>,
-) -> HashMap<(u32, u32), (u32, bool)> {
+fn play_value_on_board(new_value: u32, board: &Board) -> Board {
     board
         .iter()
         .map(|((x, y), (value, set))| ((*x, *y), (*value, *set || *value == new_value)))
         .collect()
 }
 
-fn sum_of_unset(board: &HashMap<(u32, u32), (u32, bool)>) -> u32 {
+fn sum_of_unset(board: &Board) -> u32 {
     board.iter().fold(
         0,
         |sum, ((_, _), (value, set))| if *set { sum } else { sum + *value },
     )
 }
 
-fn score_board(board: &HashMap<(u32, u32), (u32, bool)>) -> Option<u32> {
+fn score_board(board: &Board) -> Option<u32> {
     for x in 0u32..5u32 {
         let is_winning = is_winning_column(x, board);
         if is_winning {
@@ -161,7 +143,7 @@ fn score_board(board: &HashMap<(u32, u32), (u32, bool)>) -> Option<u32> {
     None
 }
 
-fn is_winning_column(x: u32, board: &HashMap<(u32, u32), (u32, bool)>) -> bool {
+fn is_winning_column(x: u32, board: &Board) -> bool {
     let mut all_are_set = true;
     for y in 0u32..5u32 {
         let cell = (x, y);
@@ -173,7 +155,7 @@ fn is_winning_column(x: u32, board: &HashMap<(u32, u32), (u32, bool)>) -> bool {
     all_are_set
 }
 
-fn is_winning_row(y: u32, board: &HashMap<(u32, u32), (u32, bool)>) -> bool {
+fn is_winning_row(y: u32, board: &Board) -> bool {
     let mut all_are_set = true;
     for x in 0u32..5u32 {
         let cell = (x, y);
@@ -184,3 +166,38 @@ fn is_winning_row(y: u32, board: &HashMap<(u32, u32), (u32, bool)>) -> bool {
 
     all_are_set
 }
+
+#[cfg(test)]
+mod day04_tests {
+    use crate::day04::Day04;
+    use aoc_common::solution::{solve, Answer};
+
+    const EXAMPLE: &str = "7,4,9,5,11,17,23,2,0,14,21,24,10,16,13,6,15,25,12,22,18,20,8,19,3,26,1
+
+22 13 17 11  0
+ 8  2 23  4 24
+21  9 14 16  7
+ 6 10  3 18  5
+ 1 12 20 15 19
+
+ 3 15  0  2 22
+ 9 18 13 17  5
+19  8  7 25 23
+20 11 10 24  4
+14 21 16 12  6
+
+14 21 17 24  4
+10 16 15  9 19
+18  8 23 26 20
+22 11 13  6  5
+ 2  0 12  3  7
+";
+
+    #[test]
+    fn example() {
+        assert_eq!(
+            solve::<Day04>(EXAMPLE, &[1, 2], &()).unwrap(),
+            [Answer::Integer(4512), Answer::Integer(1924)]
+        );
+    }
+}
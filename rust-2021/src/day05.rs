@@ -1,93 +1,37 @@
-use itertools::Itertools;
-use nom::{
-    bytes::complete::tag,
-    combinator::{map_res, opt},
-    multi::separated_list0,
-    IResult,
-};
+use aoc_common::parse::{parse_all, ParseError};
+use aoc_common::solution::{Answer, Solution};
+use nom::{bytes::complete::tag, combinator::map_res, multi::separated_list0, IResult};
 use std::collections::HashMap;
 
-enum LineSegment {
+pub enum LineSegment {
     Horizontal(((u32, u32), u32)),
     Vertical((u32, (u32, u32))),
     Diagonal(((u32, u32), (u32, u32))),
 }
 
-pub fn part1() -> u32 {
-    let input = include_str!("../../data/2021/5.txt");
-
-    match input_parser(input) {
-        Ok((remaining_input, line_segments)) if remaining_input.len() == 0 => {
-            println!("parsed entire input");
-            let occupied_spaces = line_segments
-                .iter()
-                .filter(|line_segment| match line_segment {
-                    LineSegment::Horizontal(_) => true,
-                    LineSegment::Vertical(_) => true,
-                    LineSegment::Diagonal(_) => false,
-                })
-                .fold(
-                    HashMap::new(),
-                    |mut acc: HashMap<(u32, u32), u32>, line_segment| match line_segment {
-                        &LineSegment::Horizontal(((starting_x, ending_x), y)) => {
-                            for x in starting_x..(ending_x + 1) {
-                                let cell = (x, y);
-                                match acc.get(&cell) {
-                                    Some(current_count) => {
-                                        acc.insert(cell, current_count + 1);
-                                    }
-                                    None => {
-                                        acc.insert(cell, 1);
-                                    }
-                                }
-                            }
-                            acc
-                        }
-                        &LineSegment::Vertical((x, (starting_y, ending_y))) => {
-                            for y in starting_y..(ending_y + 1) {
-                                let cell = (x, y);
-                                match acc.get(&cell) {
-                                    Some(current_count) => {
-                                        acc.insert(cell, current_count + 1);
-                                    }
-                                    None => {
-                                        acc.insert(cell, 1);
-                                    }
-                                }
-                            }
-                            acc
-                        }
-                        &LineSegment::Diagonal(_) => acc,
-                    },
-                );
-            occupied_spaces
-                .iter()
-                .filter(|(_key, count)| **count > 1)
-                .count()
-                .try_into()
-                .unwrap()
-        }
-        Ok((remaining, _)) => {
-            println!("remaining unparsed \"{}\"", remaining);
-            0
-        }
-        Err(e) => {
-            println!("error parsing \"{}\"", e);
-            0
-        }
-    }
-}
+pub struct Day05;
+
+impl Solution for Day05 {
+    type Input<'a> = Vec<LineSegment>;
+
+    type Params = ();
 
-pub fn part2() -> u32 {
-    let input = include_str!("../../data/2021/5.txt");
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse_all(input, input_parser)
+    }
 
-    match input_parser(input) {
-        Ok((remaining_input, line_segments)) if remaining_input.len() == 0 => {
-            println!("parsed entire input");
-            let occupied_spaces = line_segments.iter().fold(
+    fn part1(line_segments: &Self::Input<'_>, _params: &Self::Params) -> Answer {
+        let occupied_spaces = line_segments
+            .iter()
+            .filter(|line_segment| match line_segment {
+                LineSegment::Horizontal(_) => true,
+                LineSegment::Vertical(_) => true,
+                LineSegment::Diagonal(_) => false,
+            })
+            .fold(
                 HashMap::new(),
-                |mut acc: HashMap<(u32, u32), u32>, line_segment| match line_segment {
-                    &LineSegment::Horizontal(((starting_x, ending_x), y)) => {
+                |mut acc: HashMap<(u32, u32), u32>, line_segment| match *line_segment {
+                    LineSegment::Horizontal(((starting_x, ending_x), y)) => {
                         for x in starting_x..(ending_x + 1) {
                             let cell = (x, y);
                             match acc.get(&cell) {
@@ -101,7 +45,7 @@ pub fn part2() -> u32 {
                         }
                         acc
                     }
-                    &LineSegment::Vertical((x, (starting_y, ending_y))) => {
+                    LineSegment::Vertical((x, (starting_y, ending_y))) => {
                         for y in starting_y..(ending_y + 1) {
                             let cell = (x, y);
                             match acc.get(&cell) {
@@ -115,32 +59,51 @@ pub fn part2() -> u32 {
                         }
                         acc
                     }
-                    &LineSegment::Diagonal(((starting_x, starting_y), (ending_x, ending_y))) => {
-                        let mut cell = (starting_x, starting_y);
-                        while cell != (ending_x, ending_y) {
-                            match acc.get(&cell) {
-                                Some(current_count) => {
-                                    acc.insert(cell, current_count + 1);
-                                }
-                                None => {
-                                    acc.insert(cell, 1);
-                                }
-                            }
+                    LineSegment::Diagonal(_) => acc,
+                },
+            );
+        occupied_spaces
+            .iter()
+            .filter(|(_key, count)| **count > 1)
+            .count()
+            .into()
+    }
 
-                            cell = (
-                                if cell.0 > ending_x {
-                                    cell.0 - 1
-                                } else {
-                                    cell.0 + 1
-                                },
-                                if cell.1 > ending_y {
-                                    cell.1 - 1
-                                } else {
-                                    cell.1 + 1
-                                },
-                            );
+    fn part2(line_segments: &Self::Input<'_>, _params: &Self::Params) -> Answer {
+        let occupied_spaces = line_segments.iter().fold(
+            HashMap::new(),
+            |mut acc: HashMap<(u32, u32), u32>, line_segment| match *line_segment {
+                LineSegment::Horizontal(((starting_x, ending_x), y)) => {
+                    for x in starting_x..(ending_x + 1) {
+                        let cell = (x, y);
+                        match acc.get(&cell) {
+                            Some(current_count) => {
+                                acc.insert(cell, current_count + 1);
+                            }
+                            None => {
+                                acc.insert(cell, 1);
+                            }
                         }
-                        //This is not good... Off by one since we need to calculate once the cell is on the ending spot
+                    }
+                    acc
+                }
+                LineSegment::Vertical((x, (starting_y, ending_y))) => {
+                    for y in starting_y..(ending_y + 1) {
+                        let cell = (x, y);
+                        match acc.get(&cell) {
+                            Some(current_count) => {
+                                acc.insert(cell, current_count + 1);
+                            }
+                            None => {
+                                acc.insert(cell, 1);
+                            }
+                        }
+                    }
+                    acc
+                }
+                LineSegment::Diagonal(((starting_x, starting_y), (ending_x, ending_y))) => {
+                    let mut cell = (starting_x, starting_y);
+                    while cell != (ending_x, ending_y) {
                         match acc.get(&cell) {
                             Some(current_count) => {
                                 acc.insert(cell, current_count + 1);
@@ -150,25 +113,38 @@ pub fn part2() -> u32 {
                             }
                         }
 
-                        acc
+                        cell = (
+                            if cell.0 > ending_x {
+                                cell.0 - 1
+                            } else {
+                                cell.0 + 1
+                            },
+                            if cell.1 > ending_y {
+                                cell.1 - 1
+                            } else {
+                                cell.1 + 1
+                            },
+                        );
                     }
-                },
-            );
-            occupied_spaces
-                .iter()
-                .filter(|(_key, count)| **count > 1)
-                .count()
-                .try_into()
-                .unwrap()
-        }
-        Ok((remaining, _)) => {
-            println!("remaining unparsed \"{}\"", remaining);
-            0
-        }
-        Err(e) => {
-            println!("error parsing \"{}\"", e);
-            0
-        }
+                    //This is not good... Off by one since we need to calculate once the cell is on the ending spot
+                    match acc.get(&cell) {
+                        Some(current_count) => {
+                            acc.insert(cell, current_count + 1);
+                        }
+                        None => {
+                            acc.insert(cell, 1);
+                        }
+                    }
+
+                    acc
+                }
+            },
+        );
+        occupied_spaces
+            .iter()
+            .filter(|(_key, count)| **count > 1)
+            .count()
+            .into()
     }
 }
 
@@ -213,3 +189,29 @@ fn input_parser(input: &str) -> IResult<&str, Vec<LineSegment>> {
 
     Ok((input, lines))
 }
+
+#[cfg(test)]
+mod day05_tests {
+    use crate::day05::Day05;
+    use aoc_common::solution::{solve, Answer};
+
+    const EXAMPLE: &str = "0,9 -> 5,9
+8,0 -> 0,8
+9,4 -> 3,4
+2,2 -> 2,1
+7,0 -> 7,4
+6,4 -> 2,0
+0,9 -> 2,9
+3,4 -> 1,4
+0,0 -> 8,8
+5,5 -> 8,2
+";
+
+    #[test]
+    fn example() {
+        assert_eq!(
+            solve::<Day05>(EXAMPLE, &[1, 2], &()).unwrap(),
+            [Answer::Integer(5), Answer::Integer(12)]
+        );
+    }
+}
@@ -1,94 +1,65 @@
+use aoc_common::collections::upsert_with;
+use aoc_common::parse::{parse_all, ParseError};
+use aoc_common::solution::{Answer, Solution};
 use itertools::Itertools;
 use nom::{bytes::complete::tag, combinator::map_res, multi::separated_list0, IResult};
 use std::collections::HashMap;
 
-pub fn part1() -> usize {
-    let input = include_str!("../../data/2021/6.txt");
+pub struct Day06;
 
-    match input_parser(input) {
-        Ok((remaining_input, initial_state)) if remaining_input.is_empty() => {
-            println!("parsed entire input");
+impl Solution for Day06 {
+    type Input<'a> = Vec<usize>;
 
-            let mut state: Vec<usize> = initial_state;
-            for _ in 0..80 {
-                state =
-                    state
-                        .iter()
-                        .fold(Vec::with_capacity(state.capacity()), |mut acc, timer| {
-                            if *timer == 0 {
-                                acc.push(6);
-                                acc.push(8);
-                            } else {
-                                acc.push(*timer - 1);
-                            }
-                            acc
-                        });
-            }
+    type Params = ();
 
-            state.len()
-        }
-        Ok((remaining, _)) => {
-            println!("remaining unparsed \"{}\"", remaining);
-            0
-        }
-        Err(e) => {
-            println!("error parsing \"{}\"", e);
-            0
-        }
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse_all(input, input_parser)
     }
-}
-
-pub fn part2() -> usize {
-    let input = include_str!("../../data/2021/6.txt");
 
-    match input_parser(input) {
-        Ok((remaining_input, initial_state)) if remaining_input.is_empty() => {
-            println!("parsed entire input");
-
-            let mut state: HashMap<usize, usize> = initial_state
+    fn part1(initial_state: &Self::Input<'_>, _params: &Self::Params) -> Answer {
+        let mut state: Vec<usize> = initial_state.clone();
+        for _ in 0..80 {
+            state = state
                 .iter()
-                .sorted()
-                .group_by(|x| **x)
-                .into_iter()
-                .map(|(value, group)| (value, group.into_iter().count()))
-                .collect();
-            for _ in 0..256 {
-                state = state
-                    .iter()
-                    .fold(HashMap::new(), |mut acc, (&value, &count)| {
-                        if value == 0 {
-                            upsert(&mut acc, 6, count, |current_val| current_val + count);
-                            upsert(&mut acc, 8, count, |current_val| current_val + count);
-                        } else {
-                            upsert(&mut acc, value - 1, count, |current_val| {
-                                current_val + count
-                            });
-                        }
-                        acc
-                    });
-            }
-
-            state.iter().fold(0, |acc, (_, &count)| acc + count)
-        }
-        Ok((remaining, _)) => {
-            println!("remaining unparsed \"{}\"", remaining);
-            0
-        }
-        Err(e) => {
-            println!("error parsing \"{}\"", e);
-            0
+                .fold(Vec::with_capacity(state.capacity()), |mut acc, timer| {
+                    if *timer == 0 {
+                        acc.push(6);
+                        acc.push(8);
+                    } else {
+                        acc.push(*timer - 1);
+                    }
+                    acc
+                });
         }
+
+        state.len().into()
     }
-}
 
-fn upsert(map: &mut HashMap<usize, usize>, key: usize, val: usize, f: impl Fn(usize) -> usize) {
-    match map.get(&key) {
-        Some(&current_val) => {
-            map.insert(key, f(current_val));
-        }
-        None => {
-            map.insert(key, val);
+    fn part2(initial_state: &Self::Input<'_>, _params: &Self::Params) -> Answer {
+        let mut state: HashMap<usize, usize> = initial_state
+            .iter()
+            .sorted()
+            .group_by(|x| **x)
+            .into_iter()
+            .map(|(value, group)| (value, group.into_iter().count()))
+            .collect();
+        for _ in 0..256 {
+            state = state
+                .iter()
+                .fold(HashMap::new(), |mut acc, (&value, &count)| {
+                    if value == 0 {
+                        upsert_with(&mut acc, 6, count, |current_val| current_val + count);
+                        upsert_with(&mut acc, 8, count, |current_val| current_val + count);
+                    } else {
+                        upsert_with(&mut acc, value - 1, count, |current_val| {
+                            current_val + count
+                        });
+                    }
+                    acc
+                });
         }
+
+        state.iter().fold(0, |acc, (_, &count)| acc + count).into()
     }
 }
 
@@ -104,3 +75,20 @@ fn input_parser(input: &str) -> IResult<&str, Vec<usize>> {
 
     Ok((input, timers))
 }
+
+#[cfg(test)]
+mod day06_tests {
+    use crate::day06::Day06;
+    use aoc_common::solution::{solve, Answer};
+
+    const EXAMPLE: &str = "3,4,3,1,2
+";
+
+    #[test]
+    fn example() {
+        assert_eq!(
+            solve::<Day06>(EXAMPLE, &[1, 2], &()).unwrap(),
+            [Answer::Integer(5934), Answer::Integer(26984457539)]
+        );
+    }
+}
@@ -1,68 +1,48 @@
-use itertools::Itertools;
+use aoc_common::parse::{parse_all, ParseError};
+use aoc_common::solution::{Answer, Solution};
 use nom::{bytes::complete::tag, combinator::map_res, multi::separated_list0, IResult};
-use std::collections::HashMap;
-
-pub fn part1() -> isize {
-    let input = include_str!("../../data/2021/7.txt");
-
-    match input_parser(input) {
-        Ok((remaining_input, crab_positions)) if remaining_input.is_empty() => {
-            println!("parsed entire input");
-
-            let max = crab_positions.iter().max().unwrap();
-            let first_check = 0;
-            let mut min_fuel = part1_cost_to_move_to_position(&first_check, &crab_positions);
-            for x in 1..(*max + 1) {
-                let fuel_cost = part1_cost_to_move_to_position(&x, &crab_positions);
-                min_fuel = if fuel_cost < min_fuel {
-                    fuel_cost
-                } else {
-                    min_fuel
-                };
-            }
-
-            min_fuel
-        }
-        Ok((remaining, _)) => {
-            println!("remaining unparsed \"{}\"", remaining);
-            0
-        }
-        Err(e) => {
-            println!("error parsing \"{}\"", e);
-            0
-        }
+
+pub struct Day07;
+
+impl Solution for Day07 {
+    type Input<'a> = Vec<isize>;
+
+    type Params = ();
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse_all(input, input_parser)
     }
-}
 
-pub fn part2() -> isize {
-    let input = include_str!("../../data/2021/7.txt");
-
-    match input_parser(input) {
-        Ok((remaining_input, crab_positions)) if remaining_input.is_empty() => {
-            println!("parsed entire input");
-
-            let max = crab_positions.iter().max().unwrap();
-            let first_check = 0;
-            let mut min_fuel = part2_cost_to_move_to_position(&first_check, &crab_positions);
-            for x in 1..(*max + 1) {
-                let fuel_cost = part2_cost_to_move_to_position(&x, &crab_positions);
-                min_fuel = if fuel_cost < min_fuel {
-                    fuel_cost
-                } else {
-                    min_fuel
-                };
-            }
-
-            min_fuel
-        }
-        Ok((remaining, _)) => {
-            println!("remaining unparsed \"{}\"", remaining);
-            0
+    fn part1(crab_positions: &Self::Input<'_>, _params: &Self::Params) -> Answer {
+        let max = crab_positions.iter().max().unwrap();
+        let first_check = 0;
+        let mut min_fuel = part1_cost_to_move_to_position(&first_check, crab_positions);
+        for x in 1..(*max + 1) {
+            let fuel_cost = part1_cost_to_move_to_position(&x, crab_positions);
+            min_fuel = if fuel_cost < min_fuel {
+                fuel_cost
+            } else {
+                min_fuel
+            };
         }
-        Err(e) => {
-            println!("error parsing \"{}\"", e);
-            0
+
+        min_fuel.into()
+    }
+
+    fn part2(crab_positions: &Self::Input<'_>, _params: &Self::Params) -> Answer {
+        let max = crab_positions.iter().max().unwrap();
+        let first_check = 0;
+        let mut min_fuel = part2_cost_to_move_to_position(&first_check, crab_positions);
+        for x in 1..(*max + 1) {
+            let fuel_cost = part2_cost_to_move_to_position(&x, crab_positions);
+            min_fuel = if fuel_cost < min_fuel {
+                fuel_cost
+            } else {
+                min_fuel
+            };
         }
+
+        min_fuel.into()
     }
 }
 
@@ -92,3 +72,20 @@ fn input_parser(input: &str) -> IResult<&str, Vec<isize>> {
 
     Ok((input, timers))
 }
+
+#[cfg(test)]
+mod day07_tests {
+    use crate::day07::Day07;
+    use aoc_common::solution::{solve, Answer};
+
+    const EXAMPLE: &str = "16,1,2,0,4,2,7,1,2,14
+";
+
+    #[test]
+    fn example() {
+        assert_eq!(
+            solve::<Day07>(EXAMPLE, &[1, 2], &()).unwrap(),
+            [Answer::Integer(37), Answer::Integer(168)]
+        );
+    }
+}
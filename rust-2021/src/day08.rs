@@ -1,58 +1,46 @@
+use aoc_common::parse::{parse_all, ParseError};
+use aoc_common::solution::{Answer, Solution};
 use nom::{bytes::complete::tag, multi::separated_list0, sequence::separated_pair, IResult};
 use std::collections::HashSet;
 
 type InputLine<'a, 'b> = (Vec<&'a str>, Vec<&'b str>);
 
-pub fn part1() -> usize {
-    let input = include_str!("../../data/2021/8.txt");
+pub struct Day08;
 
-    match input_parser(input) {
-        Ok((remaining_input, display_logs)) if remaining_input.is_empty() => {
-            println!("parsed entire input");
+impl Solution for Day08 {
+    type Input<'a> = Vec<InputLine<'a, 'a>>;
 
-            display_logs.iter().fold(0, |acc, (_inputs, outputs)| {
+    type Params = ();
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse_all(input, input_parser)
+    }
+
+    fn part1(display_logs: &Self::Input<'_>, _params: &Self::Params) -> Answer {
+        display_logs
+            .iter()
+            .fold(0, |acc, (_inputs, outputs)| {
                 acc + count_recognizable(outputs)
             })
-        }
-        Ok((remaining, _)) => {
-            println!("remaining unparsed \"{}\"", remaining);
-            0
-        }
-        Err(e) => {
-            println!("error parsing \"{}\"", e);
-            0
-        }
+            .into()
     }
-}
-
-pub fn part2() -> usize {
-    let input = include_str!("../../data/2021/8.txt");
 
-    match input_parser(input) {
-        Ok((remaining_input, display_logs)) if remaining_input.is_empty() => {
-            println!("parsed entire input");
-
-            display_logs.iter().fold(0, |acc, (inputs, outputs)| {
+    fn part2(display_logs: &Self::Input<'_>, _params: &Self::Params) -> Answer {
+        display_logs
+            .iter()
+            .fold(0, |acc, (inputs, outputs)| {
                 let connections = infer_connections(inputs);
 
                 let decoded = decode(outputs, &connections);
                 acc + decoded
             })
-        }
-        Ok((remaining, _)) => {
-            println!("remaining unparsed \"{}\"", remaining);
-            0
-        }
-        Err(e) => {
-            println!("error parsing \"{}\"", e);
-            0
-        }
+            .into()
     }
 }
 
 fn decode(values: &[&str], connections: &[(&str, usize)]) -> usize {
     values.iter().fold(0, |acc, value| {
-        (acc * 10) + find_value(connections, *value).unwrap()
+        (acc * 10) + find_value(connections, value).unwrap()
     })
 }
 
@@ -137,10 +125,7 @@ fn find_length<'a>(input: &[&'a str], length: usize) -> &'a str {
 }
 
 fn count_recognizable(values: &[&str]) -> usize {
-    values
-        .iter()
-        .filter(|value| is_recognizable(*value))
-        .count()
+    values.iter().filter(|value| is_recognizable(value)).count()
 }
 
 fn is_recognizable(value: &&str) -> bool {
@@ -158,14 +143,41 @@ fn space_separated_strings(input: &str) -> IResult<&str, Vec<&str>> {
     separated_list0(tag(" "), chars_parser)(input)
 }
 
-fn line_parser(input: &str) -> IResult<&str, InputLine> {
+fn line_parser(input: &str) -> IResult<&str, InputLine<'_, '_>> {
     separated_pair(space_separated_strings, tag(" | "), space_separated_strings)(input)
 }
 
-fn input_parser(input: &str) -> IResult<&str, Vec<InputLine>> {
+fn input_parser(input: &str) -> IResult<&str, Vec<InputLine<'_, '_>>> {
     let (input, lines) = separated_list0(nom::character::complete::newline, line_parser)(input)?;
 
     let (input, _) = nom::character::complete::newline(input)?;
 
     Ok((input, lines))
 }
+
+#[cfg(test)]
+mod day08_tests {
+    use crate::day08::Day08;
+    use aoc_common::solution::{solve, Answer};
+
+    const EXAMPLE: &str =
+        "be cfbegad cbdgef fgaecd cgeb fdcge agebfd fecdb fabcd edb | fdgacbe cefdb cefbgd gcbe
+edbfga begcd cbg gc gcadebf fbgde acbgfd abcde gfcbed gfec | fcgedb cgb dgebacf gc
+fgaebd cg bdaec gdafb agbcfd gdcbef bgcad gfac gcb cdgabef | cg cg fdcagb cbg
+fbegcd cbd adcefb dageb afcb bc aefdc ecdab fgdeca fcdbega | efabcd cedba gadfec cb
+aecbfdg fbg gf bafeg dbefa fcge gcbea fcaegb dgceab fcbdga | gecf egdcabf bgf bfgea
+fgeab ca afcebg bdacfeg cfaedg gcfdb baec bfadeg bafgc acf | gebdcfa ecba ca fadegcb
+dbcfg fgd bdegcaf fgec aegbdf ecdfab fbedc dacgb gdcebf gf | cefg dcbef fcge gbcadfe
+bdfegc cbegaf gecbf dfcage bdacg ed bedf ced adcbefg gebcd | ed bcgafe cdgba cbgef
+egadfb cdbfeg cegd fecab cgb gbdefca cg fgcdab egfdb bfceg | gbdfcae bgc cg cgb
+gcafb gcf dcaebfg ecagb gf abcdeg gaef cafbge fdbac fegbdc | fgae cfgab fg bagce
+";
+
+    #[test]
+    fn example() {
+        assert_eq!(
+            solve::<Day08>(EXAMPLE, &[1, 2], &()).unwrap(),
+            [Answer::Integer(26), Answer::Integer(61229)]
+        );
+    }
+}
@@ -1,51 +1,31 @@
-use core::num;
+use aoc_common::parse::{parse_all, ParseError};
+use aoc_common::solution::{Answer, Solution};
 use itertools::Itertools;
-use nom::{bytes::complete::tag, multi::separated_list0, sequence::separated_pair, IResult};
-use std::collections::{HashMap, HashSet};
+use nom::{multi::separated_list0, IResult};
 
-pub fn part1() -> usize {
-    let input = include_str!("../../data/2021/10.txt");
+pub struct Day10;
 
-    match input_parser(input) {
-        Ok((remaining_input, input)) if remaining_input.is_empty() => {
-            println!("parsed entire input");
+impl Solution for Day10 {
+    type Input<'a> = Vec<Vec<char>>;
 
-            input.iter().map(|line| part_1_score_line(line)).sum()
-        }
-        Ok((remaining, _)) => {
-            println!("remaining unparsed \"{}\"", remaining);
-            0
-        }
-        Err(e) => {
-            println!("error parsing \"{}\"", e);
-            0
-        }
-    }
-}
+    type Params = ();
 
-pub fn part2() -> usize {
-    let input = include_str!("../../data/2021/10.txt");
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse_all(input, input_parser)
+    }
 
-    match input_parser(input) {
-        Ok((remaining_input, input)) if remaining_input.is_empty() => {
-            println!("parsed entire input");
+    fn part1(input: &Self::Input<'_>, _params: &Self::Params) -> Answer {
+        input.iter().map(part_1_score_line).sum::<usize>().into()
+    }
 
-            let scores = input
-                .iter()
-                .filter_map(|line| score_line_complete(line))
-                .sorted()
-                .collect_vec();
-            let middle_pos = scores.len() / 2;
-            scores[middle_pos]
-        }
-        Ok((remaining, _)) => {
-            println!("remaining unparsed \"{}\"", remaining);
-            0
-        }
-        Err(e) => {
-            println!("error parsing \"{}\"", e);
-            0
-        }
+    fn part2(input: &Self::Input<'_>, _params: &Self::Params) -> Answer {
+        let scores = input
+            .iter()
+            .filter_map(score_line_complete)
+            .sorted()
+            .collect_vec();
+        let middle_pos = scores.len() / 2;
+        scores[middle_pos].into()
     }
 }
 
@@ -74,7 +54,7 @@ fn part_1_score_line(chars: &Vec<char>) -> usize {
                     Some('[') => {
                         //ok
                     }
-                    Some(x) => return 57,
+                    Some(_x) => return 57,
                     None => return 0,
                 }
             }
@@ -83,7 +63,7 @@ fn part_1_score_line(chars: &Vec<char>) -> usize {
                     Some('(') => {
                         //ok
                     }
-                    Some(x) => return 3,
+                    Some(_x) => return 3,
                     None => return 0,
                 }
             }
@@ -92,7 +72,7 @@ fn part_1_score_line(chars: &Vec<char>) -> usize {
                     Some('{') => {
                         //ok
                     }
-                    Some(x) => return 1197,
+                    Some(_x) => return 1197,
                     None => return 0,
                 }
             }
@@ -101,7 +81,7 @@ fn part_1_score_line(chars: &Vec<char>) -> usize {
                     Some('<') => {
                         //ok
                     }
-                    Some(x) => return 25137,
+                    Some(_x) => return 25137,
                     None => return 0,
                 }
             }
@@ -142,7 +122,7 @@ fn score_line_complete(chars: &Vec<char>) -> Option<usize> {
                     Some('[') => {
                         //ok
                     }
-                    Some(x) => return None,
+                    Some(_x) => return None,
                     None => break,
                 }
             }
@@ -151,7 +131,7 @@ fn score_line_complete(chars: &Vec<char>) -> Option<usize> {
                     Some('(') => {
                         //ok
                     }
-                    Some(x) => return None,
+                    Some(_x) => return None,
                     None => break,
                 }
             }
@@ -160,7 +140,7 @@ fn score_line_complete(chars: &Vec<char>) -> Option<usize> {
                     Some('{') => {
                         //ok
                     }
-                    Some(x) => return None,
+                    Some(_x) => return None,
                     None => break,
                 }
             }
@@ -169,7 +149,7 @@ fn score_line_complete(chars: &Vec<char>) -> Option<usize> {
                     Some('<') => {
                         //ok
                     }
-                    Some(x) => return None,
+                    Some(_x) => return None,
                     None => break,
                 }
             }
@@ -222,3 +202,29 @@ fn input_parser(input: &str) -> IResult<&str, Vec<Vec<char>>> {
 
     Ok((input, lines))
 }
+
+#[cfg(test)]
+mod day10_tests {
+    use crate::day10::Day10;
+    use aoc_common::solution::{solve, Answer};
+
+    const EXAMPLE: &str = "[({(<(())[]>[[{[]{<()<>>
+[(()[<>])]({[<{<<[]>>(
+{([(<{}[<>[]}>{[]{[(<()>
+(((({<>}<{<{<>}{[]{[]{}
+[[<[([]))<([[{}[[()]]]
+[{[{({}]{}}([{[{{{}}([]
+{<[[]]>}<{[{[{[]{()[[[]
+[<(<(<(<{}))><([]([]()
+<{([([[(<>()){}]>(<<{{
+<{([{{}}[<[[[<>{}]]]>[]]
+";
+
+    #[test]
+    fn example() {
+        assert_eq!(
+            solve::<Day10>(EXAMPLE, &[1, 2], &()).unwrap(),
+            [Answer::Integer(26397), Answer::Integer(288957)]
+        );
+    }
+}
@@ -1,67 +1,48 @@
-use itertools::Itertools;
+use aoc_common::parse::{parse_all, ParseError};
+use aoc_common::solution::{Answer, Solution};
+use aoc_common::trace;
 use nom::{bytes::complete::tag, multi::separated_list0, sequence::separated_pair, IResult};
-use std::collections::{HashMap, HashSet};
+use std::collections::HashSet;
 
 type Input<'a, 'b> = Vec<(&'a str, &'b str)>;
 
-pub fn part1() -> usize {
-    let input = include_str!("../../data/2021/12.txt");
+pub struct Day12;
 
-    match input_parser(input) {
-        Ok((remaining_input, connections)) if remaining_input.is_empty() => {
-            println!("parsed entire input");
+impl Solution for Day12 {
+    type Input<'a> = Input<'a, 'a>;
 
-            let starting_place = "start";
-            let current_path: Vec<String> = vec![starting_place.to_owned()];
+    type Params = ();
 
-            let paths = expand_path(&current_path, &connections);
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse_all(input, input_parser)
+    }
 
-            // print_paths(&paths);
+    fn part1(connections: &Self::Input<'_>, _params: &Self::Params) -> Answer {
+        let starting_place = "start";
+        let current_path: Vec<String> = vec![starting_place.to_owned()];
 
-            paths.len()
-        }
-        Ok((remaining, _)) => {
-            println!("remaining unparsed \"{}\"", remaining);
-            0
-        }
-        Err(e) => {
-            println!("error parsing \"{}\"", e);
-            0
-        }
-    }
-}
+        let paths = expand_path(&current_path, connections);
 
-pub fn part2() -> usize {
-    let input = include_str!("../../data/2021/12.txt");
+        log_paths(&paths);
 
-    match input_parser(input) {
-        Ok((remaining_input, connections)) if remaining_input.is_empty() => {
-            println!("parsed entire input");
+        paths.len().into()
+    }
 
-            let starting_place = "start";
-            let current_path: Vec<String> = vec![starting_place.to_owned()];
+    fn part2(connections: &Self::Input<'_>, _params: &Self::Params) -> Answer {
+        let starting_place = "start";
+        let current_path: Vec<String> = vec![starting_place.to_owned()];
 
-            let paths = part_2_expand_path(&current_path, &connections);
+        let paths = part_2_expand_path(&current_path, connections);
 
-            // print_paths(&paths);
+        log_paths(&paths);
 
-            paths.len()
-        }
-        Ok((remaining, _)) => {
-            println!("remaining unparsed \"{}\"", remaining);
-            0
-        }
-        Err(e) => {
-            println!("error parsing \"{}\"", e);
-            0
-        }
+        paths.len().into()
     }
 }
 
-fn print_paths(paths: &[Vec<String>]) {
+fn log_paths(paths: &[Vec<String>]) {
     for path in paths {
-        println!("{:?}", path.join("->"));
-        println!();
+        trace!("{}", path.join("->"));
     }
 }
 
@@ -149,7 +130,7 @@ fn contains_small_duplicates(path: &[String]) -> bool {
     let mut seen: HashSet<String> = HashSet::new();
     for cave in path {
         if cave != "star" && cave.chars().next().unwrap().is_lowercase() {
-            if seen.get(cave).is_some() {
+            if seen.contains(cave) {
                 return true;
             }
 
@@ -168,10 +149,33 @@ fn line_parser(input: &str) -> IResult<&str, (&str, &str)> {
     )(input)
 }
 
-fn input_parser(input: &str) -> IResult<&str, Input> {
+fn input_parser(input: &str) -> IResult<&str, Input<'_, '_>> {
     let (input, paths) = separated_list0(nom::character::complete::newline, line_parser)(input)?;
 
     let (input, _) = nom::character::complete::newline(input)?;
 
     Ok((input, paths))
 }
+
+#[cfg(test)]
+mod day12_tests {
+    use crate::day12::Day12;
+    use aoc_common::solution::{solve, Answer};
+
+    const EXAMPLE: &str = "start-A
+start-b
+A-c
+A-b
+b-d
+A-end
+b-end
+";
+
+    #[test]
+    fn example() {
+        assert_eq!(
+            solve::<Day12>(EXAMPLE, &[1, 2], &()).unwrap(),
+            [Answer::Integer(10), Answer::Integer(36)]
+        );
+    }
+}
@@ -1,84 +1,75 @@
+use aoc_common::frame;
+use aoc_common::ocr;
+use aoc_common::parse::{parse_all, ParseError};
+use aoc_common::render::{self, Canvas};
+use aoc_common::solution::{Answer, Solution};
 use itertools::Itertools;
 use nom::{
     branch::alt, bytes::complete::tag, combinator::map_res, multi::separated_list0,
     sequence::separated_pair, IResult,
 };
-use std::collections::{HashMap, HashSet};
+use std::collections::HashSet;
 
 type Input = (Vec<(i32, i32)>, Vec<FoldInstruction>);
 
-enum FoldInstruction {
+pub enum FoldInstruction {
     X(i32),
     Y(i32),
 }
 
-pub fn part1() -> usize {
-    let input = include_str!("../../data/2021/13.txt");
+pub struct Day13;
 
-    match input_parser(input) {
-        Ok((remaining_input, (dots, fold_instructions))) if remaining_input.is_empty() => {
-            println!("parsed entire input");
+impl Solution for Day13 {
+    type Input<'a> = Input;
 
-            let mut plotted_dots: HashSet<(i32, i32)> = HashSet::new();
-            plotted_dots.extend(dots);
-            let fold_instruction = fold_instructions.get(0).unwrap();
+    type Params = ();
 
-            fold(fold_instruction, &mut plotted_dots);
-
-            plotted_dots.len()
-        }
-        Ok((remaining, _)) => {
-            println!("remaining unparsed \"{}\"", remaining);
-            0
-        }
-        Err(e) => {
-            println!("error parsing \"{}\"", e);
-            0
-        }
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse_all(input, input_parser)
     }
-}
 
-pub fn part2() -> usize {
-    let input = include_str!("../../data/2021/13.txt");
+    fn part1((dots, fold_instructions): &Self::Input<'_>, _params: &Self::Params) -> Answer {
+        let mut plotted_dots: HashSet<(i32, i32)> = HashSet::new();
+        plotted_dots.extend(dots);
+        let fold_instruction = fold_instructions.first().unwrap();
 
-    match input_parser(input) {
-        Ok((remaining_input, (dots, fold_instructions))) if remaining_input.is_empty() => {
-            println!("parsed entire input");
+        fold(fold_instruction, &mut plotted_dots);
 
-            println!("parsed entire input");
-
-            let mut plotted_dots: HashSet<(i32, i32)> = HashSet::new();
-            plotted_dots.extend(dots);
-            for fold_instruction in fold_instructions.iter() {
-                fold(fold_instruction, &mut plotted_dots);
-            }
-
-            print_dots(&plotted_dots);
+        plotted_dots.len().into()
+    }
 
-            plotted_dots.len()
-        }
-        Ok((remaining, _)) => {
-            println!("remaining unparsed \"{}\"", remaining);
-            0
-        }
-        Err(e) => {
-            println!("error parsing \"{}\"", e);
-            0
+    fn part2((dots, fold_instructions): &Self::Input<'_>, _params: &Self::Params) -> Answer {
+        let mut plotted_dots: HashSet<(i32, i32)> = HashSet::new();
+        plotted_dots.extend(dots);
+        frame!(draw_dots(&plotted_dots));
+        for fold_instruction in fold_instructions.iter() {
+            fold(fold_instruction, &mut plotted_dots);
+            frame!(draw_dots(&plotted_dots));
         }
+
+        ocr::answer(render_dots(&plotted_dots))
     }
 }
 
-fn print_dots(dots: &HashSet<(i32, i32)>) {
-    let max_y = 100;
-    let max_x = 100;
+fn render_dots(dots: &HashSet<(i32, i32)>) -> Vec<String> {
+    let max_y = dots.iter().map(|&(_, y)| y).max().unwrap_or(0);
+    let max_x = dots.iter().map(|&(x, _)| x).max().unwrap_or(0);
 
-    for y in 0..max_y {
-        for x in 0..max_x {
-            print!("{}", if dots.contains(&(x, y)) { "#" } else { "." })
-        }
+    (0..=max_y)
+        .map(|y| {
+            (0..=max_x)
+                .map(|x| if dots.contains(&(x, y)) { '#' } else { '.' })
+                .collect()
+        })
+        .collect()
+}
 
-        println!();
-    }
+fn draw_dots(dots: &HashSet<(i32, i32)>) -> Canvas {
+    render::points(
+        dots.iter()
+            .map(|&(x, y)| ((x as isize, y as isize), '#'.into())),
+        '.'.into(),
+    )
 }
 
 fn fold(fold_instruction: &FoldInstruction, dots: &mut HashSet<(i32, i32)>) {
@@ -169,3 +160,80 @@ fn input_parser(input: &str) -> IResult<&str, Input> {
 
     Ok((input, (dots, fold_instructions)))
 }
+
+#[cfg(test)]
+mod day13_tests {
+    use crate::day13::Day13;
+    use aoc_common::solution::{solve, Answer};
+
+    const EXAMPLE: &str = "6,10
+0,14
+9,10
+0,3
+10,4
+4,11
+6,0
+6,12
+4,1
+0,13
+10,12
+3,4
+3,0
+8,4
+1,10
+2,14
+8,10
+9,0
+
+fold along y=7
+fold along x=5
+";
+
+    #[test]
+    fn example() {
+        assert_eq!(
+            solve::<Day13>(EXAMPLE, &[1, 2], &()).unwrap(),
+            [
+                Answer::Integer(17),
+                Answer::grid(["#####", "#...#", "#...#", "#...#", "#####"].map(str::to_owned))
+            ]
+        );
+    }
+
+    // the folded paper spells out "OK"
+    const LETTERS: &str = "0,2
+0,4
+0,9
+0,11
+1,5
+1,12
+2,0
+2,7
+3,1
+3,3
+3,8
+3,10
+5,1
+5,3
+5,5
+5,8
+5,10
+5,12
+6,2
+7,1
+7,3
+7,8
+8,0
+8,7
+
+fold along y=6
+";
+
+    #[test]
+    fn reads_the_folded_letters() {
+        assert_eq!(
+            solve::<Day13>(LETTERS, &[2], &()).unwrap(),
+            [Answer::Text("OK".to_owned())]
+        );
+    }
+}
@@ -1,151 +1,108 @@
+use aoc_common::collections::upsert;
+use aoc_common::debug;
+use aoc_common::parse::{parse_all, ParseError};
+use aoc_common::solution::{Answer, Solution};
 use itertools::Itertools;
 use nom::{bytes::complete::tag, multi::separated_list0, sequence::separated_pair, IResult};
-use std::{
-    collections::{HashMap, HashSet},
-    hash::Hash,
-};
+use std::collections::HashMap;
 
 type Input<'a> = (&'a str, Vec<(&'a str, char)>);
 
-pub fn part1() -> usize {
-    let input = include_str!("../../data/2021/14.txt");
-
-    match input_parser(input) {
-        Ok((remaining_input, (starting_polymer_template, substitutions)))
-            if remaining_input.is_empty() =>
-        {
-            println!("parsed entire input");
-
-            let mut polymer_template = starting_polymer_template.to_owned();
-
-            for i in 0..10 {
-                let mut next_polymer = String::with_capacity(polymer_template.len() * 2);
-                next_polymer.push(polymer_template.chars().next().unwrap());
-                for (prev, next) in polymer_template.chars().into_iter().tuple_windows() {
-                    if let Some(&(_, new)) = substitutions.iter().find(|&(to_replace, _)| {
-                        // to_replace.starts_with(&String::from_iter(vec![prev, next]))
-                        to_replace.starts_with(prev) && to_replace.chars().nth(1) == Some(next)
-                    }) {
-                        next_polymer.push(new);
-                    }
-
-                    next_polymer.push(next);
-                }
-                polymer_template = next_polymer.to_owned();
-            }
+pub struct Day14;
 
-            score(&polymer_template)
-        }
-        Ok((remaining, _)) => {
-            println!("remaining unparsed \"{}\"", remaining);
-            0
-        }
-        Err(e) => {
-            println!("error parsing \"{}\"", e);
-            0
-        }
-    }
-}
+impl Solution for Day14 {
+    type Input<'a> = Input<'a>;
 
-pub fn part2() -> usize {
-    let input = include_str!("../../data/2021/14.txt");
-
-    match input_parser(input) {
-        Ok((remaining_input, (starting_polymer_template, substitutions)))
-            if remaining_input.is_empty() =>
-        {
-            println!("parsed entire input");
-
-            let mut polymer_template: HashMap<(char, char), usize> = HashMap::new();
-            for pair in starting_polymer_template
-                .chars()
-                .into_iter()
-                .tuple_windows()
-            {
-                upsert(&mut polymer_template, pair, 1);
-            }
+    type Params = ();
 
-            for _ in 0..40 {
-                let mut next_polymer: HashMap<(char, char), usize> =
-                    HashMap::with_capacity(polymer_template.len() * 2);
-                for (&(prev, next), count) in polymer_template.iter() {
-                    if let Some(&(_, new)) = substitutions.iter().find(|&(to_replace, _)| {
-                        // to_replace.starts_with(&String::from_iter(vec![prev, next]))
-                        to_replace.starts_with(prev) && to_replace.chars().nth(1) == Some(next)
-                    }) {
-                        upsert(&mut next_polymer, (prev, new), *count);
-                        upsert(&mut next_polymer, (new, next), *count);
-                    } else {
-                        upsert(&mut next_polymer, (prev, next), *count);
-                    }
-                }
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse_all(input, input_parser)
+    }
 
-                polymer_template = next_polymer.to_owned();
-            }
+    fn part1(
+        (starting_polymer_template, substitutions): &Self::Input<'_>,
+        _params: &Self::Params,
+    ) -> Answer {
+        let mut polymer_template = starting_polymer_template.to_string();
+
+        for _i in 0..10 {
+            let mut next_polymer = String::with_capacity(polymer_template.len() * 2);
+            next_polymer.push(polymer_template.chars().next().unwrap());
+            for (prev, next) in polymer_template.chars().tuple_windows() {
+                if let Some(&(_, new)) = substitutions.iter().find(|&(to_replace, _)| {
+                    // to_replace.starts_with(&String::from_iter(vec![prev, next]))
+                    to_replace.starts_with(prev) && to_replace.chars().nth(1) == Some(next)
+                }) {
+                    next_polymer.push(new);
+                }
 
-            let mut frequencies: HashMap<char, usize> = HashMap::new();
-            for ((first, second), count) in polymer_template.iter() {
-                upsert_c(&mut frequencies, *first, *count);
-                upsert_c(&mut frequencies, *second, *count);
+                next_polymer.push(next);
             }
-            upsert_c(
-                &mut frequencies,
-                starting_polymer_template.chars().next().unwrap(),
-                1,
-            );
-            upsert_c(
-                &mut frequencies,
-                starting_polymer_template.chars().last().unwrap(),
-                1,
-            );
-
-            let mut sorted_frequencies = frequencies
-                .iter()
-                .sorted_by(|&(_, val1), &(_, val2)| Ord::cmp(&val1, &val2));
-            let fewest = sorted_frequencies.next().unwrap();
-            let most = sorted_frequencies.last().unwrap();
-
-            println!(
-                "most {} {} fewest {} {}",
-                most.0, most.1, fewest.0, fewest.1
-            );
-
-            most.1 / 2 - fewest.1 / 2
-        }
-        Ok((remaining, _)) => {
-            println!("remaining unparsed \"{}\"", remaining);
-            0
-        }
-        Err(e) => {
-            println!("error parsing \"{}\"", e);
-            0
+            polymer_template = next_polymer.to_owned();
         }
+
+        score(&polymer_template).into()
     }
-}
 
-fn upsert(map: &mut HashMap<(char, char), usize>, key: (char, char), val: usize) {
-    match map.get(&key) {
-        Some(&current_val) => {
-            map.insert(key, current_val + val);
-        }
-        None => {
-            map.insert(key, val);
+    fn part2(
+        (starting_polymer_template, substitutions): &Self::Input<'_>,
+        _params: &Self::Params,
+    ) -> Answer {
+        let mut polymer_template: HashMap<(char, char), usize> = HashMap::new();
+        for pair in starting_polymer_template.chars().tuple_windows() {
+            upsert(&mut polymer_template, pair, 1);
         }
-    }
-}
 
-fn upsert_c(map: &mut HashMap<char, usize>, key: char, val: usize) {
-    match map.get(&key) {
-        Some(&current_val) => {
-            map.insert(key, current_val + val);
+        for _ in 0..40 {
+            let mut next_polymer: HashMap<(char, char), usize> =
+                HashMap::with_capacity(polymer_template.len() * 2);
+            for (&(prev, next), count) in polymer_template.iter() {
+                if let Some(&(_, new)) = substitutions.iter().find(|&(to_replace, _)| {
+                    // to_replace.starts_with(&String::from_iter(vec![prev, next]))
+                    to_replace.starts_with(prev) && to_replace.chars().nth(1) == Some(next)
+                }) {
+                    upsert(&mut next_polymer, (prev, new), *count);
+                    upsert(&mut next_polymer, (new, next), *count);
+                } else {
+                    upsert(&mut next_polymer, (prev, next), *count);
+                }
+            }
+
+            polymer_template = next_polymer.to_owned();
         }
-        None => {
-            map.insert(key, val);
+
+        let mut frequencies: HashMap<char, usize> = HashMap::new();
+        for ((first, second), count) in polymer_template.iter() {
+            upsert(&mut frequencies, *first, *count);
+            upsert(&mut frequencies, *second, *count);
         }
+        upsert(
+            &mut frequencies,
+            starting_polymer_template.chars().next().unwrap(),
+            1,
+        );
+        upsert(
+            &mut frequencies,
+            starting_polymer_template.chars().last().unwrap(),
+            1,
+        );
+
+        let mut sorted_frequencies = frequencies
+            .iter()
+            .sorted_by(|&(_, val1), &(_, val2)| Ord::cmp(&val1, &val2));
+        let fewest = sorted_frequencies.next().unwrap();
+        let most = sorted_frequencies.last().unwrap();
+
+        debug!(
+            "most {} {} fewest {} {}",
+            most.0, most.1, fewest.0, fewest.1
+        );
+
+        (most.1 / 2 - fewest.1 / 2).into()
     }
 }
 
-fn score(result: &String) -> usize {
+fn score(result: &str) -> usize {
     let mut frequencies: HashMap<char, usize> = HashMap::new();
     for c in result.chars() {
         match frequencies.get(&c) {
@@ -175,7 +132,7 @@ fn substitution_parser(input: &str) -> IResult<&str, (&str, char)> {
     )(input)
 }
 
-fn input_parser(input: &str) -> IResult<&str, Input> {
+fn input_parser(input: &str) -> IResult<&str, Input<'_>> {
     let (input, polymer_template) = nom::character::complete::alpha1(input)?;
     let (input, _) = nom::character::complete::newline(input)?;
     let (input, _) = nom::character::complete::newline(input)?;
@@ -187,3 +144,37 @@ fn input_parser(input: &str) -> IResult<&str, Input> {
 
     Ok((input, (polymer_template, substitutions)))
 }
+
+#[cfg(test)]
+mod day14_tests {
+    use crate::day14::Day14;
+    use aoc_common::solution::{solve, Answer};
+
+    const EXAMPLE: &str = "NNCB
+
+CH -> B
+HH -> N
+CB -> H
+NH -> C
+HB -> C
+HC -> B
+HN -> C
+NN -> C
+BH -> H
+NC -> B
+NB -> B
+BN -> B
+BB -> N
+BC -> B
+CC -> N
+CN -> C
+";
+
+    #[test]
+    fn example() {
+        assert_eq!(
+            solve::<Day14>(EXAMPLE, &[1, 2], &()).unwrap(),
+            [Answer::Integer(1588), Answer::Integer(2188189693529)]
+        );
+    }
+}
@@ -1,175 +1,81 @@
-use itertools::Itertools;
-use nom::{bytes::complete::tag, multi::separated_list1, sequence::separated_pair, IResult};
-use std::{
-    collections::{HashMap, HashSet},
-    hash::Hash,
-};
+use aoc_common::grid::Grid;
+use aoc_common::parse::ParseError;
+use aoc_common::search::dijkstra;
+use aoc_common::solution::{Answer, Solution};
 
-type Input = ((i32, i32), HashMap<(i32, i32), u32>);
+pub struct Day15;
 
-pub fn part1() -> u32 {
-    let input = include_str!("../../data/2021/15.txt");
+impl Solution for Day15 {
+    type Input<'a> = Grid<u32>;
 
-    match input_parser(input) {
-        Ok((remaining_input, ((width, height), grid))) if remaining_input.is_empty() => {
-            println!("parsed entire input");
+    type Params = ();
 
-            let mut cost_to_location: HashMap<(i32, i32), u32> = HashMap::with_capacity(grid.len());
-
-            cost_to_location.insert((0, 0), 0);
-
-            let mut to_visit: HashSet<(i32, i32)> = HashSet::new();
-
-            push_neighbors(&(0, 0), &mut to_visit);
-
-            build_cost(grid, &mut cost_to_location, &to_visit);
-
-            let cost_of_ending_position = cost_to_location.get(&(width - 1, height - 1)).unwrap();
-            *cost_of_ending_position
-        }
-        Ok((remaining, _)) => {
-            println!("remaining unparsed \"{}\"", remaining);
-            0
-        }
-        Err(e) => {
-            println!("error parsing \"{}\"", e);
-            0
-        }
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        Grid::parse(input, "a digit", |c| c.to_digit(10))
     }
-}
-
-pub fn part2() -> u32 {
-    let input = include_str!("../../data/2021/15.txt");
-
-    match input_parser(input) {
-        Ok((remaining_input, ((width, height), grid))) if remaining_input.is_empty() => {
-            println!("parsed entire input");
 
-            let mut expanded_grid: HashMap<(i32, i32), u32> =
-                HashMap::with_capacity(grid.len() * 25);
-            for expanded_x in 0..5 {
-                for expanded_y in 0..5 {
-                    for (&position, &value) in grid.iter() {
-                        let new_value = (value + expanded_x + expanded_y - 1) % 9 + 1;
-
-                        let x_offset: i32 = expanded_x.try_into().unwrap();
-                        let y_offset: i32 = expanded_y.try_into().unwrap();
-
-                        let new_x: i32 = position.0 + (width * x_offset);
-                        let new_y: i32 = position.1 + (height * y_offset);
-                        expanded_grid.insert((new_x, new_y), new_value);
-                    }
-                }
-            }
-
-            let mut cost_to_location: HashMap<(i32, i32), u32> =
-                HashMap::with_capacity(expanded_grid.len());
-
-            cost_to_location.insert((0, 0), 0);
-
-            let mut to_visit: HashSet<(i32, i32)> = HashSet::new();
-
-            push_neighbors(&(0, 0), &mut to_visit);
-
-            build_cost(expanded_grid, &mut cost_to_location, &to_visit);
-
-            let cost_of_ending_position = cost_to_location
-                .get(&(width * 5 - 1, height * 5 - 1))
-                .unwrap();
-            *cost_of_ending_position
-        }
-        Ok((remaining, _)) => {
-            println!("remaining unparsed \"{}\"", remaining);
-            0
-        }
-        Err(e) => {
-            println!("error parsing \"{}\"", e);
-            0
-        }
+    fn part1(grid: &Self::Input<'_>, _params: &Self::Params) -> Answer {
+        lowest_total_risk(grid).into()
     }
-}
 
-fn build_cost(
-    map: HashMap<(i32, i32), u32>,
-    cost: &mut HashMap<(i32, i32), u32>,
-    to_visit: &HashSet<(i32, i32)>,
-) {
-    let mut next_visit = HashSet::new();
-    for position in to_visit.iter() {
-        if let Some(value) = map.get(position) {
-            if let Some(cost_to_get_here) = lesser_path_to(position, cost) {
-                let prev_value = cost.get(position);
-                let calculated_cost = *cost_to_get_here + *value;
-
-                match prev_value {
-                    Some(&previous_cost) => {
-                        if calculated_cost < previous_cost {
-                            push_neighbors(position, &mut next_visit);
-                        }
-                    }
-                    None => {
-                        push_neighbors(position, &mut next_visit);
-                    }
+    fn part2(grid: &Self::Input<'_>, _params: &Self::Params) -> Answer {
+        let (width, height) = (grid.width(), grid.height());
+        let mut expanded_grid = Grid::new(width * 5, height * 5, 0);
+        for expanded_x in 0..5 {
+            for expanded_y in 0..5 {
+                for ((x, y), &value) in grid.iter() {
+                    let new_value = (value + expanded_x + expanded_y - 1) % 9 + 1;
+
+                    let new_x = x + (width * expanded_x as usize) as isize;
+                    let new_y = y + (height * expanded_y as usize) as isize;
+                    expanded_grid[(new_x, new_y)] = new_value;
                 }
-
-                cost.insert(*position, calculated_cost);
             }
         }
-    }
 
-    if next_visit.is_empty() {
-        return;
+        lowest_total_risk(&expanded_grid).into()
     }
-
-    build_cost(map, cost, &next_visit);
 }
 
-fn lesser_path_to<'a>(
-    position: &(i32, i32),
-    cost: &'a HashMap<(i32, i32), u32>,
-) -> Option<&'a u32> {
-    let neighbors = [(-1, 0), (1, 0), (0, 1), (0, -1)];
-
-    neighbors
-        .iter()
-        .filter_map(|&(x_offset, y_offset)| {
-            cost.get(&(position.0 + x_offset, position.1 + y_offset))
-        })
-        .min()
+fn lowest_total_risk(grid: &Grid<u32>) -> u32 {
+    let ending_position = (grid.width() as isize - 1, grid.height() as isize - 1);
+
+    // the risk of a cell is paid on the way in, so the start costs nothing
+    dijkstra(
+        [(0, 0)],
+        |&position| {
+            grid.orthogonal_neighbors(position)
+                .map(|(next, &risk)| (next, risk))
+                .collect::<Vec<_>>()
+        },
+        |&position| position == ending_position,
+    )
+    .unwrap()
+    .cost
 }
 
-fn push_neighbors(position: &(i32, i32), to_visit: &mut HashSet<(i32, i32)>) {
-    for (x_offset, y_offset) in [(-1, 0), (1, 0), (0, 1), (0, -1)] {
-        to_visit.insert((position.0 + x_offset, position.1 + y_offset));
+#[cfg(test)]
+mod day15_tests {
+    use crate::day15::Day15;
+    use aoc_common::solution::{solve, Answer};
+
+    const EXAMPLE: &str = "1163751742
+1381373672
+2136511328
+3694931569
+7463417111
+1319128137
+1359912421
+3125421639
+1293138521
+2311944581
+";
+
+    #[test]
+    fn example() {
+        assert_eq!(
+            solve::<Day15>(EXAMPLE, &[1, 2], &()).unwrap(),
+            [Answer::Integer(40), Answer::Integer(315)]
+        );
     }
 }
-
-fn one_digit_parser(input: &str) -> IResult<&str, u32> {
-    let (input, digit_char) = nom::character::complete::satisfy(|c| c.is_ascii_digit())(input)?;
-    let digit = digit_char.to_digit(10).unwrap();
-    Ok((input, digit))
-}
-
-fn line_parser(input: &str) -> IResult<&str, Vec<u32>> {
-    nom::multi::many0(one_digit_parser)(input)
-}
-
-fn input_parser(input: &str) -> IResult<&str, Input> {
-    let (input, lines) = separated_list1(nom::character::complete::newline, line_parser)(input)?;
-
-    // let (input, _) = nom::character::complete::newline(input)?;
-
-    let mut cave_map: HashMap<(i32, i32), u32> = HashMap::new();
-
-    for (y, line) in lines.iter().enumerate() {
-        for (x, value) in line.iter().enumerate() {
-            cave_map.insert((x.try_into().unwrap(), y.try_into().unwrap()), *value);
-        }
-    }
-
-    let width: i32 = lines[0].len().try_into().unwrap();
-    let height: i32 = lines.len().try_into().unwrap();
-
-    //there's an extra, empty line at the bottom
-    Ok((input, ((width, height - 1), cave_map)))
-}
@@ -1,17 +1,20 @@
+use aoc_common::parse::{parse_all, parse_number, ParseError};
+use aoc_common::solution::{Answer, Solution};
 use bitreader::BitReader;
 use hex;
-use itertools::Itertools;
 use nom::{
-    bytes::complete::tag, combinator::map_res, multi::separated_list1, sequence::separated_pair,
+    branch::alt,
+    character::complete::{alpha1, char, space0},
+    combinator::{map, map_res},
+    error::{Error, ErrorKind},
+    multi::separated_list1,
+    sequence::{delimited, pair},
     IResult,
 };
-use std::{
-    collections::{HashMap, HashSet},
-    hash::Hash,
-};
+use std::fmt;
 
 #[derive(Debug, PartialEq)]
-struct Packet {
+pub struct Packet {
     version: u64,
     type_id: u64,
     contents: PacketContents,
@@ -29,42 +32,72 @@ enum PacketContents {
     Equal(Vec<Packet>),
 }
 
-pub fn part1() -> u64 {
-    let input = include_str!("../../data/2021/16.txt");
-
-    match input_parser(input) {
-        Ok((remaining_input, packet)) if remaining_input.is_empty() => {
-            println!("parsed entire input");
-            sum_packet_version(&packet)
+impl PacketContents {
+    fn type_id(&self) -> u64 {
+        match self {
+            PacketContents::Sum(_) => 0,
+            PacketContents::Product(_) => 1,
+            PacketContents::Minimum(_) => 2,
+            PacketContents::Maximum(_) => 3,
+            PacketContents::Literal(_) => 4,
+            PacketContents::GreaterThan(_) => 5,
+            PacketContents::LessThan(_) => 6,
+            PacketContents::Equal(_) => 7,
         }
-        Ok((remaining, _)) => {
-            println!("remaining unparsed \"{}\"", remaining);
-            0
+    }
+
+    // the operator's name in expressions and its operands, `None` for literals
+    fn operator(&self) -> Option<(&str, &[Packet])> {
+        match self {
+            PacketContents::Sum(operands) => Some(("sum", operands)),
+            PacketContents::Product(operands) => Some(("product", operands)),
+            PacketContents::Minimum(operands) => Some(("min", operands)),
+            PacketContents::Maximum(operands) => Some(("max", operands)),
+            PacketContents::Literal(_) => None,
+            PacketContents::GreaterThan(operands) => Some(("gt", operands)),
+            PacketContents::LessThan(operands) => Some(("lt", operands)),
+            PacketContents::Equal(operands) => Some(("eq", operands)),
         }
-        Err(e) => {
-            println!("error parsing \"{}\"", e);
-            0
+    }
+}
+
+// `eq(sum(1, 3), product(2, 2))`. versions aren't shown
+impl fmt::Display for Packet {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match (&self.contents, self.contents.operator()) {
+            (PacketContents::Literal(value), _) => write!(f, "{}", value),
+            (_, Some((name, operands))) => {
+                write!(f, "{}(", name)?;
+                for (index, operand) in operands.iter().enumerate() {
+                    if index > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{}", operand)?;
+                }
+                write!(f, ")")
+            }
+            (_, None) => unreachable!("only literals have no operator"),
         }
     }
 }
 
-pub fn part2() -> u64 {
-    let input = include_str!("../../data/2021/16.txt");
+pub struct Day16;
 
-    match input_parser(input) {
-        Ok((remaining_input, packet)) if remaining_input.is_empty() => {
-            println!("parsed entire input");
+impl Solution for Day16 {
+    type Input<'a> = Packet;
 
-            evaluate_packet(&packet)
-        }
-        Ok((remaining, _)) => {
-            println!("remaining unparsed \"{}\"", remaining);
-            0
-        }
-        Err(e) => {
-            println!("error parsing \"{}\"", e);
-            0
-        }
+    type Params = ();
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse_all(input, input_parser)
+    }
+
+    fn part1(packet: &Self::Input<'_>, _params: &Self::Params) -> Answer {
+        sum_packet_version(packet).into()
+    }
+
+    fn part2(packet: &Self::Input<'_>, _params: &Self::Params) -> Answer {
+        evaluate_packet(packet).into()
     }
 }
 
@@ -77,16 +110,8 @@ fn evaluate_packet(packet: &Packet) -> u64 {
         PacketContents::Product(operands) => operands
             .iter()
             .fold(1, |acc, packet| acc * evaluate_packet(packet)),
-        PacketContents::Minimum(operands) => operands
-            .iter()
-            .map(|packet| evaluate_packet(packet))
-            .min()
-            .unwrap(),
-        PacketContents::Maximum(operands) => operands
-            .iter()
-            .map(|packet| evaluate_packet(packet))
-            .max()
-            .unwrap(),
+        PacketContents::Minimum(operands) => operands.iter().map(evaluate_packet).min().unwrap(),
+        PacketContents::Maximum(operands) => operands.iter().map(evaluate_packet).max().unwrap(),
         PacketContents::GreaterThan(operands) => {
             let operand_1_value = evaluate_packet(&operands[0]);
             let operand_2_value = evaluate_packet(&operands[1]);
@@ -136,12 +161,119 @@ fn sum_packet_version_help(acc: u64, packet: &Packet) -> u64 {
     }
 }
 
-fn sum_operands_version(packets: &Vec<Packet>) -> u64 {
+fn sum_operands_version(packets: &[Packet]) -> u64 {
     packets.iter().fold(0, |packets_acc, operand| {
         packets_acc + sum_packet_version(operand)
     })
 }
 
+// bits are appended most significant first, the order the transmission is read in
+#[derive(Default)]
+struct BitWriter {
+    bytes: Vec<u8>,
+    len: usize,
+}
+
+impl BitWriter {
+    fn write(&mut self, value: u64, width: usize) {
+        for bit in (0..width).rev() {
+            if self.len.is_multiple_of(8) {
+                self.bytes.push(0);
+            }
+            if (value >> bit) & 1 == 1 {
+                *self.bytes.last_mut().unwrap() |= 0x80 >> (self.len % 8);
+            }
+            self.len += 1;
+        }
+    }
+
+    fn append(&mut self, other: &BitWriter) {
+        for index in 0..other.len {
+            self.write((other.bytes[index / 8] >> (7 - index % 8)) as u64, 1);
+        }
+    }
+}
+
+fn packet_writer(writer: &mut BitWriter, packet: &Packet) -> Result<(), String> {
+    match (&packet.contents, packet.contents.operator()) {
+        (PacketContents::Literal(value), _) => {
+            writer.write(packet.version, 3);
+            writer.write(packet.contents.type_id(), 3);
+
+            let groups = (64 - value.leading_zeros() as usize).div_ceil(4).max(1);
+            for group in (0..groups).rev() {
+                writer.write((group > 0) as u64, 1);
+                writer.write(value >> (4 * group), 4);
+            }
+
+            Ok(())
+        }
+        (_, Some((_, operands))) => {
+            operator_writer(writer, packet.version, packet.contents.type_id(), operands)
+        }
+        (_, None) => unreachable!("only literals have no operator"),
+    }
+}
+
+fn operator_writer(
+    writer: &mut BitWriter,
+    version: u64,
+    type_id: u64,
+    operands: &[Packet],
+) -> Result<(), String> {
+    writer.write(version, 3);
+    writer.write(type_id, 3);
+
+    if operands.len() < 1 << 11 {
+        writer.write(1, 1);
+        writer.write(operands.len() as u64, 11);
+        for operand in operands {
+            packet_writer(writer, operand)?;
+        }
+        return Ok(());
+    }
+
+    // too many operands to count, so they're given by their length in bits
+    let mut sub_packets = BitWriter::default();
+    for operand in operands {
+        packet_writer(&mut sub_packets, operand)?;
+    }
+    if sub_packets.len < 1 << 15 {
+        writer.write(0, 1);
+        writer.write(sub_packets.len as u64, 15);
+        writer.append(&sub_packets);
+        return Ok(());
+    }
+
+    // too many to count or measure. sums, products, minimums and maximums come out the same with
+    // the operands after the first few moved into another packet for the same operator, which has
+    // version 0 so the versions still add up to the same
+    if type_id >= 4 {
+        return Err(format!(
+            "a comparison of {} operands is too big to encode",
+            operands.len()
+        ));
+    }
+    let (first, rest) = operands.split_at((1 << 11) - 2);
+    writer.write(1, 1);
+    writer.write(first.len() as u64 + 1, 11);
+    for operand in first {
+        packet_writer(writer, operand)?;
+    }
+    operator_writer(writer, 0, type_id, rest)
+}
+
+// the transmission for a packet, padded with zeros to whole bytes. operators always count their
+// sub-packets when there are few enough, so decoding and encoding again can change the hex but
+// not the packet, unless it has more operands than the format can hold in one. comparisons with
+// that many can't be encoded at all
+pub fn encode_packet(packet: &Packet) -> Result<String, String> {
+    let mut writer = BitWriter::default();
+    packet_writer(&mut writer, packet)?;
+
+    Ok(hex::encode_upper(writer.bytes))
+}
+
 fn literal_group_parser(reader: &mut BitReader) -> (bool, u64) {
     let has_more_groups = reader.read_bool().unwrap();
     let group_value = reader.read_u64(4).unwrap();
@@ -208,7 +340,7 @@ fn packet_parser(reader: &mut BitReader) -> Packet {
     };
 
     Packet {
-        version: version,
+        version,
         type_id: id,
         contents: packet_contents,
     }
@@ -232,12 +364,63 @@ fn input_parser(input: &str) -> IResult<&str, Packet> {
     Ok((input, packet))
 }
 
+fn literal_expression_parser(input: &str) -> IResult<&str, Packet> {
+    map(parse_number, |value| Packet {
+        version: 0,
+        type_id: 4,
+        contents: PacketContents::Literal(value),
+    })(input)
+}
+
+fn operator_expression_parser(start: &str) -> IResult<&str, Packet> {
+    let (input, name) = alpha1(start)?;
+    let (input, operands) = delimited(
+        char('('),
+        separated_list1(pair(char(','), space0), expression_parser),
+        char(')'),
+    )(input)?;
+
+    let contents = match (name, operands.len()) {
+        ("sum", _) => PacketContents::Sum(operands),
+        ("product", _) => PacketContents::Product(operands),
+        ("min", _) => PacketContents::Minimum(operands),
+        ("max", _) => PacketContents::Maximum(operands),
+        ("gt", 2) => PacketContents::GreaterThan(operands),
+        ("lt", 2) => PacketContents::LessThan(operands),
+        ("eq", 2) => PacketContents::Equal(operands),
+        _ => return Err(nom::Err::Error(Error::new(start, ErrorKind::Tag))),
+    };
+
+    Ok((
+        input,
+        Packet {
+            version: 0,
+            type_id: contents.type_id(),
+            contents,
+        },
+    ))
+}
+
+fn expression_parser(input: &str) -> IResult<&str, Packet> {
+    alt((literal_expression_parser, operator_expression_parser))(input)
+}
+
+// the packet for an expression in the form `Packet` displays as, with every version 0
+pub fn parse_expression(expression: &str) -> Result<Packet, ParseError> {
+    parse_all(expression, expression_parser)
+}
+
 #[cfg(test)]
 mod day16_tests {
+    use crate::day16::encode_packet;
     use crate::day16::evaluate_packet;
     use crate::day16::input_parser;
+    use crate::day16::parse_expression;
+    use crate::day16::sum_packet_version;
+    use crate::day16::Day16;
     use crate::day16::Packet;
     use crate::day16::PacketContents;
+    use aoc_common::solution::{solve, Answer};
 
     #[test]
     fn can_parse_literal_value() {
@@ -312,7 +495,7 @@ mod day16_tests {
     }
 
     #[test]
-    fn evaluating_C200B40A82() {
+    fn evaluating_c200b40a82() {
         let test_string = "C200B40A82\n";
         let result = input_parser(test_string);
         assert!(result.is_ok());
@@ -321,7 +504,7 @@ mod day16_tests {
     }
 
     #[test]
-    fn evaluating_04005AC33890() {
+    fn evaluating_04005ac33890() {
         let test_string = "04005AC33890\n";
         let result = input_parser(test_string);
         assert!(result.is_ok());
@@ -330,7 +513,7 @@ mod day16_tests {
     }
 
     #[test]
-    fn evaluating_880086C3E88112() {
+    fn evaluating_880086c3e88112() {
         let test_string = "880086C3E88112\n";
         let result = input_parser(test_string);
         assert!(result.is_ok());
@@ -339,7 +522,7 @@ mod day16_tests {
     }
 
     #[test]
-    fn evaluating_CE00C43D881120() {
+    fn evaluating_ce00c43d881120() {
         let test_string = "CE00C43D881120\n";
         let result = input_parser(test_string);
         assert!(result.is_ok());
@@ -348,7 +531,7 @@ mod day16_tests {
     }
 
     #[test]
-    fn evaluating_D8005AC2A8F0() {
+    fn evaluating_d8005ac2a8f0() {
         let test_string = "D8005AC2A8F0\n";
         let result = input_parser(test_string);
         assert!(result.is_ok());
@@ -357,7 +540,7 @@ mod day16_tests {
     }
 
     #[test]
-    fn evaluating_F600BC2D8F() {
+    fn evaluating_f600bc2d8f() {
         let test_string = "F600BC2D8F\n";
         let result = input_parser(test_string);
         assert!(result.is_ok());
@@ -366,7 +549,7 @@ mod day16_tests {
     }
 
     #[test]
-    fn evaluating_9C005AC2F8F0() {
+    fn evaluating_9c005ac2f8f0() {
         let test_string = "9C005AC2F8F0\n";
         let result = input_parser(test_string);
         assert!(result.is_ok());
@@ -375,11 +558,142 @@ mod day16_tests {
     }
 
     #[test]
-    fn evaluating_9C0141080250320F1802104A08() {
+    fn evaluating_9c0141080250320f1802104a08() {
         let test_string = "9C0141080250320F1802104A08\n";
         let result = input_parser(test_string);
         assert!(result.is_ok());
         let evaluation = evaluate_packet(&result.unwrap().1);
         assert_eq!(evaluation, 1);
     }
+
+    #[test]
+    fn encodes_what_it_decodes() {
+        let literal = input_parser("D2FE28\n").unwrap().1;
+        assert_eq!(encode_packet(&literal).unwrap(), "D2FE28");
+
+        for test_string in [
+            "38006F45291200",
+            "EE00D40C823060",
+            "8A004A801A8002F478",
+            "9C0141080250320F1802104A08",
+        ] {
+            let packet = input_parser(&format!("{}\n", test_string)).unwrap().1;
+            let encoded = encode_packet(&packet).unwrap();
+            assert_eq!(input_parser(&format!("{}\n", encoded)).unwrap().1, packet);
+        }
+    }
+
+    #[test]
+    fn prints_packets_as_expressions() {
+        let packet = input_parser("9C0141080250320F1802104A08\n").unwrap().1;
+        assert_eq!(packet.to_string(), "eq(sum(1, 3), product(2, 2))");
+    }
+
+    #[test]
+    fn crafts_transmissions_from_expressions() {
+        let expression = "sum(3, max(5, 9), lt(70000000000, 2))";
+        let packet = parse_expression(expression).unwrap();
+        assert_eq!(packet.to_string(), expression);
+        assert_eq!(evaluate_packet(&packet), 12);
+
+        let transmission = format!("{}\n", encode_packet(&packet).unwrap());
+        assert_eq!(input_parser(&transmission).unwrap().1, packet);
+
+        let many_ones = format!("sum({})", vec!["1"; 2048].join(", "));
+        let packet = parse_expression(&many_ones).unwrap();
+        let transmission = format!("{}\n", encode_packet(&packet).unwrap());
+        assert_eq!(
+            evaluate_packet(&input_parser(&transmission).unwrap().1),
+            2048
+        );
+
+        assert!(parse_expression("gt(1, 2, 3)").is_err());
+        assert!(parse_expression("average(1, 2)").is_err());
+    }
+
+    #[test]
+    fn encodes_more_operands_than_fit() {
+        // 3000 operands of 31 bits each are too many to count and too long to measure
+        let big_sum = format!("sum({})", vec!["1000000"; 3000].join(", "));
+        let packet = parse_expression(&big_sum).unwrap();
+        let encoded = encode_packet(&packet).unwrap();
+        let decoded = input_parser(&format!("{}\n", encoded)).unwrap().1;
+
+        assert_eq!(evaluate_packet(&decoded), 3_000_000_000);
+        assert_eq!(encode_packet(&decoded).unwrap(), encoded);
+        let nested = format!(
+            "sum({}, sum({}))",
+            vec!["1000000"; 2046].join(", "),
+            vec!["1000000"; 954].join(", ")
+        );
+        assert_eq!(decoded.to_string(), nested);
+
+        let big_product = format!("product({})", vec!["1"; 5000].join(", "));
+        let packet = parse_expression(&big_product).unwrap();
+        let decoded = input_parser(&format!("{}\n", encode_packet(&packet).unwrap()))
+            .unwrap()
+            .1;
+        assert_eq!(evaluate_packet(&decoded), 1);
+        assert_eq!(sum_packet_version(&decoded), 0);
+
+        let literal = |version| Packet {
+            version,
+            type_id: 4,
+            contents: PacketContents::Literal(1000000),
+        };
+        let big_sum = Packet {
+            version: 5,
+            type_id: 0,
+            contents: PacketContents::Sum((0..5000).map(|index| literal(index % 8)).collect()),
+        };
+        let decoded = input_parser(&format!("{}\n", encode_packet(&big_sum).unwrap()))
+            .unwrap()
+            .1;
+        assert_eq!(evaluate_packet(&decoded), 5_000_000_000);
+        assert_eq!(sum_packet_version(&decoded), sum_packet_version(&big_sum));
+    }
+
+    #[test]
+    fn refuses_comparisons_too_big_to_encode() {
+        let literal = || Packet {
+            version: 0,
+            type_id: 4,
+            contents: PacketContents::Literal(1000000),
+        };
+        let comparison = Packet {
+            version: 0,
+            type_id: 7,
+            contents: PacketContents::Equal((0..3000).map(|_| literal()).collect()),
+        };
+        assert!(encode_packet(&comparison).is_err());
+
+        let nested = Packet {
+            version: 0,
+            type_id: 0,
+            contents: PacketContents::Sum(vec![literal(), comparison]),
+        };
+        assert!(encode_packet(&nested).is_err());
+    }
+
+    const EXAMPLE_PART1: &str = "8A004A801A8002F478
+";
+
+    #[test]
+    fn example_part1() {
+        assert_eq!(
+            solve::<Day16>(EXAMPLE_PART1, &[1], &()).unwrap(),
+            [Answer::Integer(16)]
+        );
+    }
+
+    const EXAMPLE_PART2: &str = "9C0141080250320F1802104A08
+";
+
+    #[test]
+    fn example_part2() {
+        assert_eq!(
+            solve::<Day16>(EXAMPLE_PART2, &[2], &()).unwrap(),
+            [Answer::Integer(1)]
+        );
+    }
 }
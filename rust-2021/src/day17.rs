@@ -1,13 +1,7 @@
-use itertools::Itertools;
-use nom::{
-    bytes::complete::tag, combinator::map_res, multi::separated_list1, sequence::separated_pair,
-    IResult,
-};
+use aoc_common::parse::{parse_all, parse_number, ParseError};
+use aoc_common::solution::{Answer, Solution};
+use nom::{bytes::complete::tag, IResult};
 use std::cmp::Ordering;
-use std::{
-    collections::{HashMap, HashSet},
-    hash::Hash,
-};
 
 #[derive(Debug, Clone, Copy)]
 struct State {
@@ -20,7 +14,7 @@ impl State {
     fn with_velocity(velocity: (i32, i32)) -> State {
         State {
             probe_position: (0, 0),
-            velocity: velocity,
+            velocity,
             peak_y: 0,
         }
     }
@@ -33,81 +27,61 @@ enum ProbeResult {
     Miss,
 }
 
-pub fn part1() -> i32 {
-    let input = include_str!("../../data/2021/17.txt");
+pub struct Day17;
 
-    match input_parser(input) {
-        Ok((remaining_input, target_area)) if remaining_input.is_empty() => {
-            println!("parsed entire input");
+impl Solution for Day17 {
+    type Input<'a> = TargetArea;
 
-            let mut potential_velocities: Vec<(i32, i32)> = Vec::new();
+    type Params = ();
 
-            for x in 1..1000 {
-                for y in 0..1000 {
-                    potential_velocities.push((x, y))
-                }
-            }
-
-            let mut velocities_that_hit: Vec<((i32, i32), i32)> = Vec::new();
-            for velocity in potential_velocities.iter() {
-                if let ProbeResult::Hit(peak) =
-                    step_until_result(State::with_velocity(*velocity), target_area)
-                {
-                    velocities_that_hit.push((*velocity, peak))
-                }
-            }
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse_all(input, input_parser)
+    }
 
-            let velocity_with_max = velocities_that_hit.iter().max_by(|&a, &b| a.1.cmp(&b.1));
+    fn part1(target_area: &Self::Input<'_>, _params: &Self::Params) -> Answer {
+        let mut potential_velocities: Vec<(i32, i32)> = Vec::new();
 
-            velocity_with_max.unwrap().1
-        }
-        Ok((remaining, _)) => {
-            println!("remaining unparsed \"{}\"", remaining);
-            0
+        for x in 1..1000 {
+            for y in 0..1000 {
+                potential_velocities.push((x, y))
+            }
         }
-        Err(e) => {
-            println!("error parsing \"{}\"", e);
-            0
+
+        let mut velocities_that_hit: Vec<((i32, i32), i32)> = Vec::new();
+        for velocity in potential_velocities.iter() {
+            if let ProbeResult::Hit(peak) =
+                step_until_result(State::with_velocity(*velocity), *target_area)
+            {
+                velocities_that_hit.push((*velocity, peak))
+            }
         }
-    }
-}
 
-pub fn part2() -> usize {
-    let input = include_str!("../../data/2021/17.txt");
+        let velocity_with_max = velocities_that_hit.iter().max_by(|&a, &b| a.1.cmp(&b.1));
 
-    match input_parser(input) {
-        Ok((remaining_input, target_area)) if remaining_input.is_empty() => {
-            println!("parsed entire input");
+        velocity_with_max.unwrap().1.into()
+    }
 
-            let mut potential_velocities: Vec<(i32, i32)> = Vec::new();
+    fn part2(target_area: &Self::Input<'_>, _params: &Self::Params) -> Answer {
+        let mut potential_velocities: Vec<(i32, i32)> = Vec::new();
 
-            for x in 1..1000 {
-                for y in -5000..5000 {
-                    potential_velocities.push((x, y))
-                }
-            }
-            // let mut velocities_that_hit = Vec::new();
-            let mut velocities_that_hit: usize = 0;
-            for velocity in potential_velocities.iter() {
-                if let ProbeResult::Hit(peak) =
-                    step_until_result(State::with_velocity(*velocity), target_area)
-                {
-                    // velocities_that_hit.push(*velocity)
-                    velocities_that_hit += 1;
-                }
+        for x in 1..1000 {
+            for y in -5000..5000 {
+                potential_velocities.push((x, y))
             }
-
-            // velocities_that_hit.len()
-            velocities_that_hit
-        }
-        Ok((remaining, _)) => {
-            println!("remaining unparsed \"{}\"", remaining);
-            0
         }
-        Err(e) => {
-            println!("error parsing \"{}\"", e);
-            0
+        // let mut velocities_that_hit = Vec::new();
+        let mut velocities_that_hit: usize = 0;
+        for velocity in potential_velocities.iter() {
+            if let ProbeResult::Hit(_peak) =
+                step_until_result(State::with_velocity(*velocity), *target_area)
+            {
+                // velocities_that_hit.push(*velocity)
+                velocities_that_hit += 1;
+            }
         }
+
+        // velocities_that_hit.len()
+        velocities_that_hit.into()
     }
 }
 
@@ -156,15 +130,7 @@ fn step_probe(state: State) -> State {
     }
 }
 
-fn parse_number(input: &str) -> IResult<&str, i32> {
-    let (input, maybe_negative) = nom::combinator::opt(tag("-"))(input)?;
-    let (input, val) =
-        map_res(nom::character::complete::digit1, |s: &str| s.parse::<i32>())(input)?;
-
-    Ok((input, if maybe_negative.is_some() { -val } else { val }))
-}
-
-fn input_parser(input: &str) -> IResult<&str, ((i32, i32), (i32, i32))> {
+fn input_parser(input: &str) -> IResult<&str, TargetArea> {
     let (input, _) = tag("target area: x=")(input)?;
     let (input, x_start) = parse_number(input)?;
     let (input, _) = tag("..")(input)?;
@@ -178,3 +144,20 @@ fn input_parser(input: &str) -> IResult<&str, ((i32, i32), (i32, i32))> {
 
     Ok((input, ((x_start, x_end), (y_start, y_end))))
 }
+
+#[cfg(test)]
+mod day17_tests {
+    use crate::day17::Day17;
+    use aoc_common::solution::{solve, Answer};
+
+    const EXAMPLE: &str = "target area: x=20..30, y=-10..-5
+";
+
+    #[test]
+    fn example() {
+        assert_eq!(
+            solve::<Day17>(EXAMPLE, &[1, 2], &()).unwrap(),
+            [Answer::Integer(45), Answer::Integer(112)]
+        );
+    }
+}
@@ -1,86 +1,108 @@
+use aoc_common::parse::{parse_all, parse_number, ParseError};
+use aoc_common::solution::{Answer, Solution};
+use aoc_common::trace;
 use itertools::Itertools;
-use nom::{
-    bytes::complete::tag, combinator::map_res, multi::separated_list1, sequence::separated_pair,
-    IResult,
-};
-use std::ops;
-use std::{
-    collections::{HashMap, HashSet},
-    hash::Hash,
-};
+use nom::{bytes::complete::tag, multi::separated_list1, sequence::separated_pair, IResult};
+use std::{fmt, iter::Sum, ops::Add, str::FromStr};
 
 #[derive(Clone, Debug, PartialEq)]
-enum SnailfishNumber {
+pub enum SnailfishNumber {
     Literal(i32),
     Pair(Box<(SnailfishNumber, SnailfishNumber)>),
 }
 
-pub fn part1() -> i32 {
-    let input = include_str!("../../data/2021/18.txt");
+impl SnailfishNumber {
+    pub fn magnitude(&self) -> i32 {
+        match self {
+            SnailfishNumber::Literal(x) => *x,
+            SnailfishNumber::Pair(pair) => 3 * pair.0.magnitude() + 2 * pair.1.magnitude(),
+        }
+    }
+}
 
-    match input_parser(input) {
-        Ok((remaining_input, input_numbers)) if remaining_input.is_empty() => {
-            println!("parsed entire input");
+// the bracket notation the puzzle writes numbers in, e.g. `[[1,2],3]`
+impl FromStr for SnailfishNumber {
+    type Err = ParseError;
 
-            let result = sum(input_numbers);
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_all(s, snailfish_value_parser)
+    }
+}
 
-            magnitude(result)
-        }
-        Ok((remaining, _)) => {
-            println!("remaining unparsed \"{}\"", remaining);
-            0
-        }
-        Err(e) => {
-            println!("error parsing \"{}\"", e);
-            0
+impl fmt::Display for SnailfishNumber {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SnailfishNumber::Literal(x) => write!(f, "{}", x),
+            SnailfishNumber::Pair(pair) => write!(f, "[{},{}]", pair.0, pair.1),
         }
     }
 }
 
-pub fn part2() -> i32 {
-    let input = include_str!("../../data/2021/18.txt");
+impl Add for SnailfishNumber {
+    type Output = SnailfishNumber;
 
-    match input_parser(input) {
-        Ok((remaining_input, input_numbers)) if remaining_input.is_empty() => {
-            println!("parsed entire input");
+    fn add(self, rhs: Self) -> Self::Output {
+        add(self, rhs)
+    }
+}
 
-            let mut max_magnitude = 0;
-            for mut pair in input_numbers.into_iter().combinations(2) {
-                let in_order = magnitude(sum(pair.clone()));
-                if in_order > max_magnitude {
-                    max_magnitude = in_order;
-                }
+// there's no snailfish zero, so adding up nothing panics
+impl Sum for SnailfishNumber {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.reduce(add)
+            .expect("can't add up an empty list of snailfish numbers")
+    }
+}
 
-                pair.reverse();
-                let reversed = magnitude(sum(pair));
-                if reversed > max_magnitude {
-                    max_magnitude = reversed;
-                }
-            }
+// a single step of reducing a number
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Reduction {
+    Exploded(i32, i32),
+    Split(i32),
+}
 
-            max_magnitude
-        }
-        Ok((remaining, _)) => {
-            println!("remaining unparsed \"{}\"", remaining);
-            0
-        }
-        Err(e) => {
-            println!("error parsing \"{}\"", e);
-            0
+impl fmt::Display for Reduction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Reduction::Exploded(left, right) => write!(f, "explode [{},{}]", left, right),
+            Reduction::Split(value) => write!(f, "split {}", value),
         }
     }
 }
 
-fn sum(numbers: Vec<SnailfishNumber>) -> SnailfishNumber {
-    let result = numbers.into_iter().reduce(|a, b| add(a, b));
+pub struct Day18;
 
-    result.unwrap()
-}
+impl Solution for Day18 {
+    type Input<'a> = Vec<SnailfishNumber>;
 
-fn magnitude(number: SnailfishNumber) -> i32 {
-    match number {
-        SnailfishNumber::Literal(x) => x,
-        SnailfishNumber::Pair(pair) => 3 * magnitude(pair.0) + 2 * magnitude(pair.1),
+    type Params = ();
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse_all(input, input_parser)
+    }
+
+    fn part1(input_numbers: &Self::Input<'_>, _params: &Self::Params) -> Answer {
+        let result: SnailfishNumber = input_numbers.iter().cloned().sum();
+
+        result.magnitude().into()
+    }
+
+    fn part2(input_numbers: &Self::Input<'_>, _params: &Self::Params) -> Answer {
+        let mut max_magnitude = 0;
+        for mut pair in input_numbers.iter().cloned().combinations(2) {
+            let in_order = pair.iter().cloned().sum::<SnailfishNumber>().magnitude();
+            if in_order > max_magnitude {
+                max_magnitude = in_order;
+            }
+
+            pair.reverse();
+            let reversed = pair.into_iter().sum::<SnailfishNumber>().magnitude();
+            if reversed > max_magnitude {
+                max_magnitude = reversed;
+            }
+        }
+
+        max_magnitude.into()
     }
 }
 
@@ -90,13 +112,59 @@ fn add(left: SnailfishNumber, right: SnailfishNumber) -> SnailfishNumber {
 
 fn reduce(number: SnailfishNumber) -> SnailfishNumber {
     let mut result = number;
-    loop {
-        if let Some(explode_result) = try_explode(result.clone()) {
-            result = explode_result;
-        } else if let Some(split_result) = try_split(result.clone()) {
-            result = split_result;
-        } else {
-            return result;
+    while let Some((reduction, next)) = reduce_step(&result) {
+        trace!("after {}: {}", reduction, next);
+        result = next;
+    }
+
+    result
+}
+
+// every step reducing the number takes, with the number after it
+pub fn reduction_steps(number: SnailfishNumber) -> Vec<(Reduction, SnailfishNumber)> {
+    let mut steps: Vec<(Reduction, SnailfishNumber)> = vec![];
+    let mut result = number;
+    while let Some((reduction, next)) = reduce_step(&result) {
+        steps.push((reduction, next.clone()));
+        result = next;
+    }
+
+    steps
+}
+
+// explosions come before splits, and the leftmost one of each goes first
+fn reduce_step(number: &SnailfishNumber) -> Option<(Reduction, SnailfishNumber)> {
+    if let Some((left, right)) = exploding_pair(number, 0) {
+        let result = try_explode(number.clone())?;
+        Some((Reduction::Exploded(left, right), result))
+    } else if let Some(value) = splitting_value(number) {
+        let result = try_split(number.clone())?;
+        Some((Reduction::Split(value), result))
+    } else {
+        None
+    }
+}
+
+fn exploding_pair(number: &SnailfishNumber, depth: usize) -> Option<(i32, i32)> {
+    match number {
+        SnailfishNumber::Literal(_) => None,
+        SnailfishNumber::Pair(pair) => match &**pair {
+            (SnailfishNumber::Literal(left), SnailfishNumber::Literal(right)) if depth >= 4 => {
+                Some((*left, *right))
+            }
+            (left, right) => {
+                exploding_pair(left, depth + 1).or_else(|| exploding_pair(right, depth + 1))
+            }
+        },
+    }
+}
+
+fn splitting_value(number: &SnailfishNumber) -> Option<i32> {
+    match number {
+        SnailfishNumber::Literal(value) if *value > 9 => Some(*value),
+        SnailfishNumber::Literal(_) => None,
+        SnailfishNumber::Pair(pair) => {
+            splitting_value(&pair.0).or_else(|| splitting_value(&pair.1))
         }
     }
 }
@@ -122,21 +190,15 @@ fn try_explode(number: SnailfishNumber) -> Option<SnailfishNumber> {
 
 fn try_explode_help(number: SnailfishNumber, depth: usize) -> (SnailfishNumber, ExplodeResult) {
     match number {
-        SnailfishNumber::Literal(value) => (number, ExplodeResult::NoExplosion),
-        SnailfishNumber::Pair(pair) => {
-            if depth == 4 {
-                //explode
-                //panic if the left or right is a pair
-                match *pair {
-                    (SnailfishNumber::Literal(left), SnailfishNumber::Literal(right)) => (
-                        SnailfishNumber::Literal(0),
-                        ExplodeResult::Exploded(left, right),
-                    ),
-                    _ => {
-                        panic!("At depth 4, the left or right value was not a literal")
-                    }
-                }
-            } else {
+        SnailfishNumber::Literal(_value) => (number, ExplodeResult::NoExplosion),
+        SnailfishNumber::Pair(pair) => match &*pair {
+            // only numbers that were never reduced have pairs deeper than this, and then the
+            // leftmost pair of plain numbers goes first
+            (SnailfishNumber::Literal(left), SnailfishNumber::Literal(right)) if depth >= 4 => (
+                SnailfishNumber::Literal(0),
+                ExplodeResult::Exploded(*left, *right),
+            ),
+            _ => {
                 let left_result = try_explode_help(pair.0.clone(), depth + 1);
                 match left_result.1 {
                     ExplodeResult::Exploded(exploded_left, exploded_right) => (
@@ -196,7 +258,7 @@ fn try_explode_help(number: SnailfishNumber, depth: usize) -> (SnailfishNumber,
                     }
                 }
             }
-        }
+        },
     }
 }
 
@@ -220,7 +282,7 @@ fn try_split(number: SnailfishNumber) -> Option<SnailfishNumber> {
             SnailfishNumber::Literal((value as f32 / 2_f32).floor() as i32),
             SnailfishNumber::Literal((value as f32 / 2_f32).ceil() as i32),
         )))),
-        SnailfishNumber::Literal(value) => None,
+        SnailfishNumber::Literal(_value) => None,
         SnailfishNumber::Pair(pair) => match (try_split(pair.0.clone()), try_split(pair.1.clone()))
         {
             (Some(left), _) => Some(SnailfishNumber::Pair(Box::new((left, pair.1)))),
@@ -230,19 +292,8 @@ fn try_split(number: SnailfishNumber) -> Option<SnailfishNumber> {
     }
 }
 
-fn parse_number(input: &str) -> IResult<&str, SnailfishNumber> {
-    let (input, maybe_negative) = nom::combinator::opt(tag("-"))(input)?;
-    let (input, val) =
-        map_res(nom::character::complete::digit1, |s: &str| s.parse::<i32>())(input)?;
-
-    Ok((
-        input,
-        if maybe_negative.is_some() {
-            SnailfishNumber::Literal(-val)
-        } else {
-            SnailfishNumber::Literal(val)
-        },
-    ))
+fn literal_parser(input: &str) -> IResult<&str, SnailfishNumber> {
+    nom::combinator::map(parse_number, SnailfishNumber::Literal)(input)
 }
 
 fn nested_pair_parser(input: &str) -> IResult<&str, SnailfishNumber> {
@@ -255,7 +306,7 @@ fn nested_pair_parser(input: &str) -> IResult<&str, SnailfishNumber> {
 }
 
 fn snailfish_value_parser(input: &str) -> IResult<&str, SnailfishNumber> {
-    nom::branch::alt((parse_number, nested_pair_parser))(input)
+    nom::branch::alt((literal_parser, nested_pair_parser))(input)
 }
 
 fn input_parser(input: &str) -> IResult<&str, Vec<SnailfishNumber>> {
@@ -269,8 +320,12 @@ fn input_parser(input: &str) -> IResult<&str, Vec<SnailfishNumber>> {
 
 #[cfg(test)]
 mod day18_tests {
+    use crate::day18::reduction_steps;
     use crate::day18::snailfish_value_parser;
+    use crate::day18::Day18;
+    use crate::day18::Reduction;
     use crate::day18::SnailfishNumber;
+    use aoc_common::solution::{solve, Answer};
 
     #[test]
     fn can_parse_literal_value() {
@@ -314,4 +369,102 @@ mod day18_tests {
             )))
         );
     }
+
+    fn number(text: &str) -> SnailfishNumber {
+        text.parse().unwrap()
+    }
+
+    #[test]
+    fn displays_the_notation_it_parses() {
+        let text = "[[[0,[5,8]],[[1,7],[9,6]]],[[4,[1,2]],[[1,4],2]]]";
+        assert_eq!(number(text).to_string(), text);
+        assert!("[1,2".parse::<SnailfishNumber>().is_err());
+    }
+
+    #[test]
+    fn traces_the_worked_reduction() {
+        let sum = SnailfishNumber::Pair(Box::new((
+            number("[[[[4,3],4],4],[7,[[8,4],9]]]"),
+            number("[1,1]"),
+        )));
+
+        let steps: Vec<(Reduction, String)> = reduction_steps(sum)
+            .into_iter()
+            .map(|(reduction, result)| (reduction, result.to_string()))
+            .collect();
+        assert_eq!(
+            steps,
+            [
+                (
+                    Reduction::Exploded(4, 3),
+                    "[[[[0,7],4],[7,[[8,4],9]]],[1,1]]"
+                ),
+                (Reduction::Exploded(8, 4), "[[[[0,7],4],[15,[0,13]]],[1,1]]"),
+                (Reduction::Split(15), "[[[[0,7],4],[[7,8],[0,13]]],[1,1]]"),
+                (
+                    Reduction::Split(13),
+                    "[[[[0,7],4],[[7,8],[0,[6,7]]]],[1,1]]"
+                ),
+                (
+                    Reduction::Exploded(6, 7),
+                    "[[[[0,7],4],[[7,8],[6,0]]],[8,1]]"
+                ),
+            ]
+            .map(|(reduction, result)| (reduction, result.to_owned()))
+        );
+    }
+
+    #[test]
+    fn adds_up_lists() {
+        let list: Vec<SnailfishNumber> = ["[1,1]", "[2,2]", "[3,3]", "[4,4]", "[5,5]", "[6,6]"]
+            .into_iter()
+            .map(number)
+            .collect();
+
+        let first_four: SnailfishNumber = list[..4].iter().cloned().sum();
+        assert_eq!(first_four, number("[[[[1,1],[2,2]],[3,3]],[4,4]]"));
+        assert_eq!(
+            first_four + list[4].clone(),
+            number("[[[[3,0],[5,3]],[4,4]],[5,5]]")
+        );
+        assert_eq!(
+            list.into_iter().sum::<SnailfishNumber>(),
+            number("[[[[5,0],[7,4]],[5,5]],[6,6]]")
+        );
+        assert_eq!(number("[[9,1],[1,9]]").magnitude(), 129);
+    }
+
+    #[test]
+    fn reduces_pairs_nested_too_deep() {
+        let deep = number("[[[[[[1,2],3],4],5],6],7]");
+
+        assert_eq!(
+            reduction_steps(deep.clone()),
+            [
+                (Reduction::Exploded(1, 2), number("[[[[[0,5],4],5],6],7]")),
+                (Reduction::Exploded(0, 5), number("[[[[0,9],5],6],7]")),
+            ]
+        );
+        assert_eq!(deep + number("[1,1]"), number("[[[[7,0],[6,7]],7],[1,1]]"));
+    }
+
+    const EXAMPLE: &str = "[[[0,[5,8]],[[1,7],[9,6]]],[[4,[1,2]],[[1,4],2]]]
+[[[5,[2,8]],4],[5,[[9,9],0]]]
+[6,[[[6,2],[5,6]],[[7,6],[4,7]]]]
+[[[6,[0,7]],[0,9]],[4,[9,[9,0]]]]
+[[[7,[6,4]],[3,[1,3]]],[[[5,5],1],9]]
+[[6,[[7,3],[3,2]]],[[[3,8],[5,7]],4]]
+[[[[5,4],[7,7]],8],[[8,3],8]]
+[[9,3],[[9,9],[6,[4,9]]]]
+[[2,[[7,7],7]],[[5,8],[[9,3],[0,2]]]]
+[[[[5,2],5],[8,[3,7]]],[[5,[7,5]],[4,4]]]
+";
+
+    #[test]
+    fn example() {
+        assert_eq!(
+            solve::<Day18>(EXAMPLE, &[1, 2], &()).unwrap(),
+            [Answer::Integer(4140), Answer::Integer(3993)]
+        );
+    }
 }
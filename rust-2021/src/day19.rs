@@ -1,112 +1,95 @@
+use aoc_common::collections::upsert;
+use aoc_common::debug;
+use aoc_common::geometry::manhattan_distance;
+use aoc_common::parse::{parse_all, parse_number, ParseError};
+use aoc_common::solution::{Answer, Solution};
 use itertools::Itertools;
-use nom::{bytes::complete::tag, combinator::map_res, multi::separated_list1, IResult};
+use nom::{bytes::complete::tag, multi::separated_list1, IResult};
 use std::collections::{HashMap, HashSet};
 
 type BeaconLocation = (i32, i32, i32);
 
 #[derive(Clone)]
-struct ScannerData {
+pub struct ScannerData {
     id: i32,
     beacons: Vec<BeaconLocation>,
 }
 
 type Translation = (i32, i32, i32);
 
-pub fn part1() -> usize {
-    let input = include_str!("../../data/2021/19.txt");
-
-    match input_parser(input) {
-        Ok((remaining_input, scanners)) if remaining_input.is_empty() => {
-            println!("parsed entire input");
-            let mut all_beacons: HashSet<(i32, i32, i32)> =
-                scanners[0].beacons.clone().into_iter().collect();
-
-            let mut scanners_to_solve = scanners.iter().skip(1).collect_vec();
-
-            while !scanners_to_solve.is_empty() {
-                let mut next_scanners_to_solve = Vec::new();
-
-                for scanner in scanners_to_solve {
-                    if let Some((rotated_scanner, translation)) = overlaps(&all_beacons, scanner) {
-                        all_beacons.extend(rotated_scanner.beacons.iter().map(|&(x, y, z)| {
-                            (x + translation.0, y + translation.1, z + translation.2)
-                        }));
-                        println!("joined {} into the fold", scanner.id);
-                    } else {
-                        next_scanners_to_solve.push(scanner);
-                        println!("going to try {} again later", scanner.id);
-                    }
-                }
+pub struct Day19;
 
-                scanners_to_solve = next_scanners_to_solve;
-            }
+impl Solution for Day19 {
+    type Input<'a> = Vec<ScannerData>;
 
-            all_beacons.len()
-        }
-        Ok((remaining, _)) => {
-            println!("remaining unparsed \"{}\"", remaining);
-            0
-        }
-        Err(e) => {
-            println!("error parsing \"{}\"", e);
-            0
-        }
+    type Params = ();
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse_all(input, input_parser)
     }
-}
 
-pub fn part2() -> i32 {
-    let input = include_str!("../../data/2021/19.txt");
+    fn part1(scanners: &Self::Input<'_>, _params: &Self::Params) -> Answer {
+        let mut all_beacons: HashSet<(i32, i32, i32)> =
+            scanners[0].beacons.clone().into_iter().collect();
 
-    match input_parser(input) {
-        Ok((remaining_input, scanners)) if remaining_input.is_empty() => {
-            println!("parsed entire input");
+        let mut scanners_to_solve = scanners.iter().skip(1).collect_vec();
 
-            let mut all_beacons: HashSet<(i32, i32, i32)> =
-                scanners[0].beacons.clone().into_iter().collect();
+        while !scanners_to_solve.is_empty() {
+            let mut next_scanners_to_solve = Vec::new();
 
-            let mut scanner_positions: Vec<(i32, i32, i32)> = vec![(0, 0, 0)];
+            for scanner in scanners_to_solve {
+                if let Some((rotated_scanner, translation)) = overlaps(&all_beacons, scanner) {
+                    all_beacons.extend(rotated_scanner.beacons.iter().map(|&(x, y, z)| {
+                        (x + translation.0, y + translation.1, z + translation.2)
+                    }));
+                    debug!("joined {} into the fold", scanner.id);
+                } else {
+                    next_scanners_to_solve.push(scanner);
+                    debug!("going to try {} again later", scanner.id);
+                }
+            }
 
-            let mut scanners_to_solve = scanners.iter().skip(1).collect_vec();
+            scanners_to_solve = next_scanners_to_solve;
+        }
+
+        all_beacons.len().into()
+    }
 
-            while !scanners_to_solve.is_empty() {
-                let mut next_scanners_to_solve = Vec::new();
+    fn part2(scanners: &Self::Input<'_>, _params: &Self::Params) -> Answer {
+        let mut all_beacons: HashSet<(i32, i32, i32)> =
+            scanners[0].beacons.clone().into_iter().collect();
 
-                for scanner in scanners_to_solve {
-                    if let Some((rotated_scanner, translation)) = overlaps(&all_beacons, scanner) {
-                        all_beacons.extend(rotated_scanner.beacons.iter().map(|&(x, y, z)| {
-                            (x + translation.0, y + translation.1, z + translation.2)
-                        }));
-                        scanner_positions.push(translation);
-                        println!("joined {} into the fold", scanner.id);
-                    } else {
-                        next_scanners_to_solve.push(scanner);
-                        println!("going to try {} again later", scanner.id);
-                    }
-                }
+        let mut scanner_positions: Vec<(i32, i32, i32)> = vec![(0, 0, 0)];
 
-                scanners_to_solve = next_scanners_to_solve;
+        let mut scanners_to_solve = scanners.iter().skip(1).collect_vec();
+
+        while !scanners_to_solve.is_empty() {
+            let mut next_scanners_to_solve = Vec::new();
+
+            for scanner in scanners_to_solve {
+                if let Some((rotated_scanner, translation)) = overlaps(&all_beacons, scanner) {
+                    all_beacons.extend(rotated_scanner.beacons.iter().map(|&(x, y, z)| {
+                        (x + translation.0, y + translation.1, z + translation.2)
+                    }));
+                    scanner_positions.push(translation);
+                    debug!("joined {} into the fold", scanner.id);
+                } else {
+                    next_scanners_to_solve.push(scanner);
+                    debug!("going to try {} again later", scanner.id);
+                }
             }
 
-            scanner_positions
-                .iter()
-                .combinations(2)
-                .map(|pairs| manhattan_distance(*pairs[0], *pairs[1]))
-                .max()
-                .unwrap()
+            scanners_to_solve = next_scanners_to_solve;
         }
-        Ok((remaining, _)) => {
-            println!("remaining unparsed \"{}\"", remaining);
-            0
-        }
-        Err(e) => {
-            println!("error parsing \"{}\"", e);
-            0
-        }
-    }
-}
 
-fn manhattan_distance(a: (i32, i32, i32), b: (i32, i32, i32)) -> i32 {
-    (b.0 - a.0).abs() + (b.1 - a.1).abs() + (b.2 - a.2).abs()
+        scanner_positions
+            .iter()
+            .combinations(2)
+            .map(|pairs| manhattan_distance(*pairs[0], *pairs[1]))
+            .max()
+            .unwrap()
+            .into()
+    }
 }
 
 fn overlaps(
@@ -206,27 +189,8 @@ fn distance_between(first: &BeaconLocation, second: &BeaconLocation) -> (i32, i3
     )
 }
 
-fn upsert(map: &mut HashMap<(i32, i32, i32), usize>, key: (i32, i32, i32), val: usize) {
-    match map.get(&key) {
-        Some(&current_val) => {
-            map.insert(key, current_val + val);
-        }
-        None => {
-            map.insert(key, val);
-        }
-    }
-}
-
 // fn rotations(beacons: &[BeaconLocation]) -> Vec<Vec<BeaconLocation>> {}
 
-fn parse_number(input: &str) -> IResult<&str, i32> {
-    let (input, maybe_negative) = nom::combinator::opt(tag("-"))(input)?;
-    let (input, val) =
-        map_res(nom::character::complete::digit1, |s: &str| s.parse::<i32>())(input)?;
-
-    Ok((input, if maybe_negative.is_some() { -val } else { val }))
-}
-
 fn beacon_location_parser(input: &str) -> IResult<&str, BeaconLocation> {
     let (input, x) = parse_number(input)?;
     let (input, _) = tag(",")(input)?;
@@ -256,3 +220,98 @@ fn input_parser(input: &str) -> IResult<&str, Vec<ScannerData>> {
 
     Ok((input, scanners))
 }
+
+#[cfg(test)]
+mod day19_tests {
+    use crate::day19::Day19;
+    use aoc_common::solution::{solve, Answer};
+
+    // the puzzle's example is too long to keep here, so this one was put together the same way:
+    // three scanners, each sharing at least 12 beacons with the next one and turned a different way
+    const EXAMPLE: &str = "--- scanner 0 ---
+-977,-631,-215
+-590,-271,729
+-565,235,795
+-272,800,712
+-126,-249,-533
+143,-221,415
+309,-31,-24
+318,-805,845
+321,-245,35
+331,-557,117
+362,-480,-826
+469,-489,3
+504,-290,-562
+549,-405,64
+553,-875,684
+634,-729,-907
+651,-421,659
+725,-475,614
+759,-599,131
+928,-865,-330
+
+--- scanner 1 ---
+-984,-962,495
+-400,-787,925
+-960,-784,115
+-648,-774,197
+-725,-743,-746
+-716,-636,83
+-915,-601,-482
+-800,-556,144
+-330,-552,764
+-476,-471,-827
+-784,-454,739
+-730,-380,694
+-606,-346,211
+-340,-177,-250
+-267,51,-591
+-498,68,588
+-359,117,106
+-327,156,-766
+-218,226,63
+-839,242,293
+-942,272,-460
+-494,493,-167
+-417,574,662
+-532,621,453
+-486,650,-732
+-776,685,-242
+-382,756,-158
+-416,837,735
+-464,921,658
+-785,980,905
+
+--- scanner 2 ---
+-731,994,638
+448,977,407
+-34,928,546
+-906,889,578
+-77,819,687
+153,803,66
+-600,773,-37
+-307,552,411
+522,471,488
+313,424,373
+-872,395,419
+-382,360,129
+-298,289,523
+595,208,489
+-227,192,-974
+518,124,441
+765,65,120
+334,-69,83
+700,-111,352
+-455,-376,-805
+140,-859,-49
+562,-928,-344
+";
+
+    #[test]
+    fn example() {
+        assert_eq!(
+            solve::<Day19>(EXAMPLE, &[1, 2], &()).unwrap(),
+            [Answer::Integer(42), Answer::Integer(2510)]
+        );
+    }
+}
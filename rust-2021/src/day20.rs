@@ -1,100 +1,73 @@
+use aoc_common::frame;
+use aoc_common::params;
+use aoc_common::parse::{parse_all, ParseError};
+use aoc_common::render::{self, Canvas, Cell};
+use aoc_common::solution::{Answer, Solution};
 use itertools::Itertools;
-use nom::{
-    bytes::complete::tag, combinator::map_res, multi::separated_list1, sequence::separated_pair,
-    IResult,
-};
-use std::iter::Scan;
-use std::ops;
-use std::{
-    collections::{HashMap, HashSet},
-    hash::Hash,
-};
+use nom::{multi::separated_list1, IResult};
+use std::collections::HashMap;
 
 type Input = (Vec<bool>, Image);
 
-struct Image {
+#[derive(Clone)]
+pub struct Image {
     bounds: ((i32, i32), (i32, i32)),
     pixels: HashMap<(i32, i32), bool>,
 }
 
-pub fn part1() -> i32 {
-    let input = include_str!("../../data/2021/20-test.txt");
-
-    match input_parser(input) {
-        Ok((remaining_input, (algorithm, image))) if remaining_input.is_empty() => {
-            println!("parsed entire input");
-
-            // print(&image);
-
-            let first_enhance = enhance(&image, &algorithm, 1);
+params! {
+    pub struct Day20Params {
+        part1_steps: i32 = (example: 2, real: 2),
+        part2_steps: i32 = (example: 50, real: 50),
+    }
+}
 
-            // println!();
-            // print(&first_enhance);
+pub struct Day20;
 
-            let second_enhance = enhance(&first_enhance, &algorithm, 2);
+impl Solution for Day20 {
+    type Input<'a> = Input;
 
-            // println!();
-            // print(&second_enhance);
+    type Params = Day20Params;
 
-            second_enhance
-                .pixels
-                .iter()
-                .fold(0, |acc, (_, pixel)| if *pixel { acc + 1 } else { acc })
-        }
-        Ok((remaining, _)) => {
-            println!("remaining unparsed \"{}\"", remaining);
-            0
-        }
-        Err(e) => {
-            println!("error parsing \"{}\"", e);
-            0
-        }
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse_all(input, input_parser)
     }
-}
-
-pub fn part2() -> i32 {
-    let input = include_str!("../../data/2021/20.txt");
 
-    match input_parser(input) {
-        Ok((remaining_input, (algorithm, image))) if remaining_input.is_empty() => {
-            println!("parsed entire input");
-
-            let mut enhanced = image;
-            for generation in 1..51 {
-                enhanced = enhance(&enhanced, &algorithm, generation);
-            }
+    fn part1((algorithm, image): &Self::Input<'_>, params: &Self::Params) -> Answer {
+        lit_after(image, algorithm, params.part1_steps).into()
+    }
 
-            enhanced
-                .pixels
-                .iter()
-                .fold(0, |acc, (_, pixel)| if *pixel { acc + 1 } else { acc })
-        }
-        Ok((remaining, _)) => {
-            println!("remaining unparsed \"{}\"", remaining);
-            0
-        }
-        Err(e) => {
-            println!("error parsing \"{}\"", e);
-            0
-        }
+    fn part2((algorithm, image): &Self::Input<'_>, params: &Self::Params) -> Answer {
+        lit_after(image, algorithm, params.part2_steps).into()
     }
 }
 
-fn print(image: &Image) {
-    let y_min = (image.bounds.1 .0) - 1;
-    let y_max = (image.bounds.1 .1) + 1;
-
-    let x_min = (image.bounds.0 .0) - 1;
-    let x_max = (image.bounds.0 .1) + 1;
+fn lit_after(image: &Image, algorithm: &[bool], steps: i32) -> usize {
+    let mut enhanced = image.clone();
+    frame!(render_image(&enhanced));
+    for generation in 1..=steps {
+        enhanced = enhance(&enhanced, algorithm, generation);
+        frame!(render_image(&enhanced));
+    }
 
-    for y in y_min..y_max {
-        for x in x_min..x_max {
-            let pixel = read_pixel(image, &(x, y), false);
-            print!("{}", if pixel { '#' } else { '.' })
-        }
+    enhanced.pixels.values().filter(|&&pixel| pixel).count()
+}
 
-        println!();
-    }
+fn render_image(image: &Image) -> Canvas {
+    let ((x_min, x_max), (y_min, y_max)) = image.bounds;
+
+    render::points(
+        (x_min - 1..x_max + 1)
+            .flat_map(|x| (y_min - 1..y_max + 1).map(move |y| (x, y)))
+            .map(|(x, y)| {
+                let pixel = read_pixel(image, &(x, y), false);
+                (
+                    (x as isize, y as isize),
+                    Cell::from(if pixel { '#' } else { '.' }),
+                )
+            }),
+        '.'.into(),
+    )
 }
 
 fn enhance(image: &Image, algorithm: &[bool], generation: i32) -> Image {
@@ -193,3 +166,27 @@ fn input_parser(input: &str) -> IResult<&str, Input> {
 
     Ok((input, (algorithm, image)))
 }
+
+#[cfg(test)]
+mod day20_tests {
+    use crate::day20::{Day20, Day20Params};
+    use aoc_common::params::Params;
+    use aoc_common::solution::{solve, Answer};
+
+    const EXAMPLE: &str = "..#.#..#####.#.#.#.###.##.....###.##.#..###.####..#####..#....#..#..##..###..######.###...####..#..#####..##..#.#####...##.#.#..#.##..#.#......#.###.######.###.####...#.##.##..#..#..#####.....#.#....###..#.##......#.....#..#..#..##..#...##.######.####.####.#.#...#.......#..#.#.#...####.##.#......#..#...##.#.##..#...##.#.##..###.#......#.#.......#.#.#.####.###.##...#.....####.#..#..#.##.#....##..#.####....##...##..#...#......#.#.......#.......##..####..#...#.#.#...##..#.#..###..#####........#..####......#..#
+
+#..#.
+#....
+##..#
+..#..
+..###
+";
+
+    #[test]
+    fn example() {
+        assert_eq!(
+            solve::<Day20>(EXAMPLE, &[1, 2], &Day20Params::example()).unwrap(),
+            [Answer::Integer(35), Answer::Integer(3351)]
+        );
+    }
+}
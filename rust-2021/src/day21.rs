@@ -1,15 +1,10 @@
+use aoc_common::collections::upsert;
+use aoc_common::parse::{parse_all, parse_number, ParseError};
+use aoc_common::solution::{Answer, Solution};
 use itertools::Itertools;
 use nom::character::complete::newline;
-use nom::{
-    bytes::complete::tag, combinator::map_res, multi::separated_list1, sequence::separated_pair,
-    IResult,
-};
-use std::iter::Scan;
-use std::ops;
-use std::{
-    collections::{HashMap, HashSet},
-    hash::Hash,
-};
+use nom::{bytes::complete::tag, IResult};
+use std::{collections::HashMap, hash::Hash};
 
 #[derive(Clone)]
 struct GameState {
@@ -34,7 +29,7 @@ impl GameState {
     }
 
     fn play_deterministic_round(&mut self) {
-        let is_player_1_turn = self.rolls % 2 == 0;
+        let is_player_1_turn = self.rolls.is_multiple_of(2);
         let rolls = self.roll() + self.roll() + self.roll();
         if is_player_1_turn {
             self.player_1.advance(rolls);
@@ -95,7 +90,7 @@ impl DiracGameState {
     }
 
     fn play_round(&mut self) {
-        let is_player_1_turn = self.rolls % 2 == 0;
+        let is_player_1_turn = self.rolls.is_multiple_of(2);
 
         self.rolls += 3;
 
@@ -159,92 +154,49 @@ impl DiracGameState {
     }
 }
 
-fn upsert(
-    map: &mut HashMap<(PlayerState, PlayerState), usize>,
-    key: (PlayerState, PlayerState),
-    val: usize,
-) {
-    match map.get(&key) {
-        Some(&current_val) => {
-            map.insert(key, current_val + val);
-        }
-        None => {
-            map.insert(key, val);
-        }
-    }
-}
-
-pub fn part1() -> usize {
-    let input = include_str!("../../data/2021/21.txt");
+pub struct Day21;
 
-    match input_parser(input) {
-        Ok((remaining_input, (player_1_start, player_2_start))) if remaining_input.is_empty() => {
-            println!("parsed entire input");
+impl Solution for Day21 {
+    type Input<'a> = (usize, usize);
 
-            let mut state = GameState::new(player_1_start, player_2_start);
+    type Params = ();
 
-            while state.player_1.score < 1000 && state.player_2.score < 1000 {
-                state.play_deterministic_round()
-            }
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse_all(input, input_parser)
+    }
 
-            let losing_score = state.player_1.score.min(state.player_2.score);
+    fn part1((player_1_start, player_2_start): &Self::Input<'_>, _params: &Self::Params) -> Answer {
+        let mut state = GameState::new(*player_1_start, *player_2_start);
 
-            losing_score * state.rolls
-        }
-        Ok((remaining, _)) => {
-            println!("remaining unparsed \"{}\"", remaining);
-            0
-        }
-        Err(e) => {
-            println!("error parsing \"{}\"", e);
-            0
+        while state.player_1.score < 1000 && state.player_2.score < 1000 {
+            state.play_deterministic_round()
         }
-    }
-}
-
-pub fn part2() -> usize {
-    let input = include_str!("../../data/2021/21.txt");
 
-    match input_parser(input) {
-        Ok((remaining_input, (player_1_start, player_2_start))) if remaining_input.is_empty() => {
-            println!("parsed entire input");
+        let losing_score = state.player_1.score.min(state.player_2.score);
 
-            let mut state = DiracGameState::new(player_1_start, player_2_start);
+        (losing_score * state.rolls).into()
+    }
 
-            while !state.ongoing_games.is_empty() {
-                state.play_round()
-            }
+    fn part2((player_1_start, player_2_start): &Self::Input<'_>, _params: &Self::Params) -> Answer {
+        let mut state = DiracGameState::new(*player_1_start, *player_2_start);
 
-            let (count_of_1_wins, count_of_2_wins) = state.completed_games.iter().fold(
-                (0, 0),
-                |(player_1_wins, player_2_wins), ((player_1, player_2), count)| {
-                    if player_1.score > player_2.score {
-                        (player_1_wins + *count, player_2_wins)
-                    } else {
-                        (player_1_wins, player_2_wins + *count)
-                    }
-                },
-            );
-
-            count_of_1_wins.max(count_of_2_wins)
-        }
-        Ok((remaining, _)) => {
-            println!("remaining unparsed \"{}\"", remaining);
-            0
+        while !state.ongoing_games.is_empty() {
+            state.play_round()
         }
-        Err(e) => {
-            println!("error parsing \"{}\"", e);
-            0
-        }
-    }
-}
 
-fn parse_number(input: &str) -> IResult<&str, usize> {
-    let (input, val) = map_res(nom::character::complete::digit1, |s: &str| {
-        s.parse::<usize>()
-    })(input)?;
+        let (count_of_1_wins, count_of_2_wins) = state.completed_games.iter().fold(
+            (0, 0),
+            |(player_1_wins, player_2_wins), ((player_1, player_2), count)| {
+                if player_1.score > player_2.score {
+                    (player_1_wins + *count, player_2_wins)
+                } else {
+                    (player_1_wins, player_2_wins + *count)
+                }
+            },
+        );
 
-    Ok((input, val))
+        count_of_1_wins.max(count_of_2_wins).into()
+    }
 }
 
 fn input_parser(input: &str) -> IResult<&str, (usize, usize)> {
@@ -260,3 +212,21 @@ fn input_parser(input: &str) -> IResult<&str, (usize, usize)> {
 
     Ok((input, (player_1_start, player_2_start)))
 }
+
+#[cfg(test)]
+mod day21_tests {
+    use crate::day21::Day21;
+    use aoc_common::solution::{solve, Answer};
+
+    const EXAMPLE: &str = "Player 1 starting position: 4
+Player 2 starting position: 8
+";
+
+    #[test]
+    fn example() {
+        assert_eq!(
+            solve::<Day21>(EXAMPLE, &[1, 2], &()).unwrap(),
+            [Answer::Integer(739785), Answer::Integer(444356092776315)]
+        );
+    }
+}
@@ -1,13 +1,9 @@
-use itertools::Itertools;
-use nom::{
-    bytes::complete::tag, combinator::map_res, multi::separated_list1, sequence::separated_pair,
-    IResult,
-};
-use std::ops;
-use std::{
-    collections::{HashMap, HashSet},
-    hash::Hash,
-};
+use aoc_common::params;
+use aoc_common::parse::{parse_all, parse_number, ParseError};
+use aoc_common::solution::{Answer, Solution};
+use aoc_common::trace;
+use nom::{bytes::complete::tag, multi::separated_list1, IResult};
+use std::collections::HashSet;
 
 #[derive(Clone, Debug, PartialEq)]
 struct Range {
@@ -22,20 +18,34 @@ struct Cuboid {
     z: Range,
 }
 
-struct Instruction {
+pub struct Instruction {
     state: bool,
     cuboid: Cuboid,
 }
 
+// what's switched on in the reactor. there are two ways of keeping track, which should always
+// agree on the count
+trait Reactor {
+    fn add(&mut self, cuboid: Cuboid);
+    fn subtract(&mut self, cuboid: &Cuboid);
+    fn count_on(&self) -> usize;
+}
+
+// the cubes that are on as cuboids that never overlap, split apart whenever a new one cuts
+// through them
+#[derive(Default)]
 struct ReactorState {
     cubes: Vec<Cuboid>,
 }
 
-impl ReactorState {
-    fn new() -> Self {
-        ReactorState { cubes: Vec::new() }
-    }
+// every cuboid switched on, and each overlap between them with the opposite sign so that adding
+// up the signed sizes counts every cube once
+#[derive(Default)]
+struct SignedReactor {
+    cuboids: Vec<(Cuboid, i64)>,
+}
 
+impl Reactor for ReactorState {
     fn add(&mut self, cuboid: Cuboid) {
         //add and reduce
         // by reduce, I mean to split up the cuboid to only add new non-intersecting cubes
@@ -109,6 +119,49 @@ impl ReactorState {
     }
 }
 
+impl Reactor for SignedReactor {
+    fn add(&mut self, cuboid: Cuboid) {
+        self.subtract(&cuboid);
+        self.cuboids.push((cuboid, 1));
+    }
+
+    fn subtract(&mut self, cuboid: &Cuboid) {
+        let overlaps: Vec<(Cuboid, i64)> = self
+            .cuboids
+            .iter()
+            .filter_map(|(existing, sign)| Some((existing.intersection(cuboid)?, -sign)))
+            .collect();
+        self.cuboids.extend(overlaps);
+    }
+
+    fn count_on(&self) -> usize {
+        let count: i64 = self
+            .cuboids
+            .iter()
+            .map(|(cuboid, sign)| cuboid.size() as i64 * sign)
+            .sum();
+
+        count as usize
+    }
+}
+
+fn reboot<R: Reactor + Default>(instructions: &[Instruction]) -> usize {
+    let mut reactor = R::default();
+
+    for instruction in instructions {
+        if instruction.state {
+            trace!("adding cube {:?}", instruction.cuboid);
+            reactor.add(instruction.cuboid.clone());
+        } else {
+            trace!("removing cube {:?}", instruction.cuboid);
+            reactor.subtract(&instruction.cuboid);
+        }
+        trace!("cubes on: {}", reactor.count_on());
+    }
+
+    reactor.count_on()
+}
+
 impl Cuboid {
     fn split_x(&self, x: i32) -> (Cuboid, Cuboid) {
         let (lesser_x, greater_x) = self.x.split(x);
@@ -262,6 +315,14 @@ impl Cuboid {
         }
     }
 
+    fn intersection(&self, other: &Cuboid) -> Option<Cuboid> {
+        Some(Cuboid {
+            x: self.x.intersection(&other.x)?,
+            y: self.y.intersection(&other.y)?,
+            z: self.z.intersection(&other.z)?,
+        })
+    }
+
     fn envelops(&self, cuboid2: &Cuboid) -> bool {
         self.x.max >= cuboid2.x.max
             && self.x.min <= cuboid2.x.min
@@ -272,9 +333,9 @@ impl Cuboid {
     }
 
     fn size(&self) -> usize {
-        ((self.x.max + 1 - self.x.min).abs() as usize)
-            * ((self.y.max + 1 - self.y.min).abs() as usize)
-            * ((self.z.max + 1 - self.z.min).abs() as usize)
+        ((self.x.max + 1 - self.x.min).unsigned_abs() as usize)
+            * ((self.y.max + 1 - self.y.min).unsigned_abs() as usize)
+            * ((self.z.max + 1 - self.z.min).unsigned_abs() as usize)
     }
 }
 
@@ -296,99 +357,65 @@ impl Range {
         }
     }
 
+    fn intersection(&self, other: &Range) -> Option<Range> {
+        let (min, max) = (self.min.max(other.min), self.max.min(other.max));
+        (min <= max).then_some(Range { min, max })
+    }
+
     fn is_min_size(&self) -> bool {
         self.min == self.max
     }
 }
 
-pub fn part1() -> usize {
-    let input = include_str!("../../data/2021/22-test.txt");
-
-    match input_parser(input) {
-        Ok((remaining_input, instructions)) if remaining_input.is_empty() => {
-            println!("parsed entire input");
-
-            let mut core: HashSet<(i32, i32, i32)> = HashSet::new();
-
-            for instruction in instructions {
-                if is_valid_for_part1(&instruction.cuboid) {
-                    for x in instruction.cuboid.x.min..(instruction.cuboid.x.max + 1) {
-                        for y in instruction.cuboid.y.min..(instruction.cuboid.y.max + 1) {
-                            for z in instruction.cuboid.z.min..(instruction.cuboid.z.max + 1) {
-                                if instruction.state {
-                                    core.insert((x, y, z));
-                                } else {
-                                    core.remove(&(x, y, z));
-                                }
-                            }
-                        }
-                    }
-                }
-            }
-
-            core.len()
-        }
-        Ok((remaining, _)) => {
-            println!("remaining unparsed \"{}\"", remaining);
-            0
-        }
-        Err(e) => {
-            println!("error parsing \"{}\"", e);
-            0
-        }
+params! {
+    // `split_cuboids` reboots with the reactor that splits cuboids apart instead of the signed one
+    pub struct Day22Params {
+        split_cuboids: bool = (example: false, real: false),
     }
 }
 
-pub fn part2() -> usize {
-    let input = include_str!("../../data/2021/22.txt");
+pub struct Day22;
 
-    match input_parser(input) {
-        Ok((remaining_input, instructions)) if remaining_input.is_empty() => {
-            println!("parsed entire input");
-            let mut reactor_state = ReactorState::new();
+impl Solution for Day22 {
+    type Input<'a> = Vec<Instruction>;
 
-            for instruction in instructions {
-                // if !is_valid_for_part1(&instruction.cuboid) {
-                //     continue;
-                // }
+    type Params = Day22Params;
 
-                println!(
-                    "current state: cubes: {}, cubes_on: {}",
-                    reactor_state.cubes.len(),
-                    reactor_state.count_on()
-                );
-                // println!("current state: cubes: {}", reactor_state.cubes.len(),);
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse_all(input, input_parser)
+    }
 
-                if instruction.state {
-                    println!("adding cube {:?}", instruction.cuboid);
-                    reactor_state.add(instruction.cuboid);
-                } else {
-                    println!("removing cube {:?}", instruction.cuboid);
-                    reactor_state.subtract(&instruction.cuboid);
+    fn part1(instructions: &Self::Input<'_>, _params: &Self::Params) -> Answer {
+        let mut core: HashSet<(i32, i32, i32)> = HashSet::new();
+
+        for instruction in instructions {
+            if is_valid_for_part1(&instruction.cuboid) {
+                for x in instruction.cuboid.x.min..(instruction.cuboid.x.max + 1) {
+                    for y in instruction.cuboid.y.min..(instruction.cuboid.y.max + 1) {
+                        for z in instruction.cuboid.z.min..(instruction.cuboid.z.max + 1) {
+                            if instruction.state {
+                                core.insert((x, y, z));
+                            } else {
+                                core.remove(&(x, y, z));
+                            }
+                        }
+                    }
                 }
-
-                println!(
-                    "current state: cubes: {}, cubes_on: {}",
-                    reactor_state.cubes.len(),
-                    reactor_state.count_on()
-                );
-                // println!("current state: cubes: {}", reactor_state.cubes.len(),);
-
-                println!();
             }
-
-            reactor_state.count_on()
-        }
-        Ok((remaining, _)) => {
-            println!("remaining unparsed \"{}\"", remaining);
-            0
         }
-        Err(e) => {
-            println!("error parsing \"{}\"", e);
-            0
+
+        core.len().into()
+    }
+
+    fn part2(instructions: &Self::Input<'_>, params: &Self::Params) -> Answer {
+        if params.split_cuboids {
+            reboot::<ReactorState>(instructions).into()
+        } else {
+            reboot::<SignedReactor>(instructions).into()
         }
     }
 }
+
 fn is_valid_for_part1(cuboid: &Cuboid) -> bool {
     cuboid.x.min >= -50
         && cuboid.x.max <= 50
@@ -398,14 +425,6 @@ fn is_valid_for_part1(cuboid: &Cuboid) -> bool {
         && cuboid.z.max <= 50
 }
 
-fn parse_number(input: &str) -> IResult<&str, i32> {
-    let (input, maybe_negative) = nom::combinator::opt(tag("-"))(input)?;
-    let (input, val) =
-        map_res(nom::character::complete::digit1, |s: &str| s.parse::<i32>())(input)?;
-
-    Ok((input, if maybe_negative.is_some() { -val } else { val }))
-}
-
 fn range_parser(input: &str) -> IResult<&str, Range> {
     let (input, min) = parse_number(input)?;
     let (input, _) = tag("..")(input)?;
@@ -460,13 +479,24 @@ fn input_parser(input: &str) -> IResult<&str, Vec<Instruction>> {
 
 #[cfg(test)]
 mod day22_tests {
+    use crate::day22::input_parser;
+    use crate::day22::is_valid_for_part1;
+    use crate::day22::reboot;
     use crate::day22::Cuboid;
+    use crate::day22::Day22;
+    use crate::day22::Day22Params;
+    use crate::day22::Instruction;
     use crate::day22::Range;
+    use crate::day22::Reactor;
     use crate::day22::ReactorState;
+    use crate::day22::SignedReactor;
+    use aoc_common::params::Params;
+    use aoc_common::solution::{solve, Answer};
+    use std::collections::HashSet;
 
     #[test]
     fn adding_and_removing_results_in_empty() {
-        let mut state = ReactorState::new();
+        let mut state = ReactorState::default();
 
         let test_cuboid = Cuboid {
             x: Range::new(-10, 20),
@@ -482,7 +512,7 @@ mod day22_tests {
 
     #[test]
     fn adding_disjoint_cubes_results_in_correct_count() {
-        let mut state = ReactorState::new();
+        let mut state = ReactorState::default();
 
         let unit_cuboid = Cuboid {
             x: Range::new(1, 1),
@@ -522,7 +552,7 @@ mod day22_tests {
 
     #[test]
     fn adding_overlapping_cubes_results_in_correct_count() {
-        let mut state = ReactorState::new();
+        let mut state = ReactorState::default();
 
         let unit_cuboid = Cuboid {
             x: Range::new(1, 2),
@@ -545,7 +575,7 @@ mod day22_tests {
 
     #[test]
     fn removing_cube_that_covers_partial_results_in_correct_count() {
-        let mut state = ReactorState::new();
+        let mut state = ReactorState::default();
 
         let cuboid = Cuboid {
             x: Range::new(1, 1),
@@ -568,7 +598,7 @@ mod day22_tests {
 
     #[test]
     fn should_be_able_to_add() {
-        let mut state = ReactorState::new();
+        let mut state = ReactorState::default();
 
         let cuboid = Cuboid {
             x: Range::new(-1, 1),
@@ -591,7 +621,7 @@ mod day22_tests {
 
     #[test]
     fn should_be_able_to_add_real_examples() {
-        let mut state = ReactorState::new();
+        let mut state = ReactorState::default();
 
         let cuboid = Cuboid {
             x: Range::new(-20, 26),
@@ -623,4 +653,205 @@ mod day22_tests {
             (Range::new(-2, -2), Range::new(-1, 2))
         );
     }
+
+    #[test]
+    fn signed_reactor_cancels_overlaps() {
+        let mut state = SignedReactor::default();
+
+        state.add(Cuboid {
+            x: Range::new(1, 2),
+            y: Range::new(1, 2),
+            z: Range::new(1, 2),
+        });
+        state.add(Cuboid {
+            x: Range::new(2, 3),
+            y: Range::new(2, 2),
+            z: Range::new(2, 2),
+        });
+        assert_eq!(state.count_on(), 9);
+
+        state.subtract(&Cuboid {
+            x: Range::new(0, 5),
+            y: Range::new(2, 2),
+            z: Range::new(0, 5),
+        });
+        assert_eq!(state.count_on(), 4);
+    }
+
+    // a deterministic jumble of overlapping cuboids small enough to check cube by cube
+    fn jumbled_instructions() -> Vec<Instruction> {
+        let mut seed: u32 = 22;
+        let mut next = |limit: i32| {
+            seed = seed.wrapping_mul(1_103_515_245).wrapping_add(12_345);
+            ((seed >> 16) % limit as u32) as i32
+        };
+
+        (0..40)
+            .map(|_| {
+                let mut range = || {
+                    let min = next(12) - 6;
+                    Range::new(min, min + next(6))
+                };
+                Instruction {
+                    cuboid: Cuboid {
+                        x: range(),
+                        y: range(),
+                        z: range(),
+                    },
+                    state: next(3) > 0,
+                }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn reactors_agree() {
+        let example = input_parser(EXAMPLE).unwrap().1;
+        assert_eq!(reboot::<ReactorState>(&example), 39);
+        assert_eq!(reboot::<SignedReactor>(&example), 39);
+
+        let instructions = jumbled_instructions();
+        let mut cubes: HashSet<(i32, i32, i32)> = HashSet::new();
+        for instruction in &instructions {
+            let Cuboid { x, y, z } = &instruction.cuboid;
+            for cube in itertools::iproduct!(x.min..=x.max, y.min..=y.max, z.min..=z.max) {
+                if instruction.state {
+                    cubes.insert(cube);
+                } else {
+                    cubes.remove(&cube);
+                }
+            }
+        }
+
+        assert_eq!(reboot::<ReactorState>(&instructions), cubes.len());
+        assert_eq!(reboot::<SignedReactor>(&instructions), cubes.len());
+    }
+
+    #[test]
+    fn reactors_agree_on_the_larger_examples() {
+        let initialization: Vec<Instruction> = input_parser(LARGER_EXAMPLE)
+            .unwrap()
+            .1
+            .into_iter()
+            .filter(|instruction| is_valid_for_part1(&instruction.cuboid))
+            .collect();
+        assert_eq!(reboot::<ReactorState>(&initialization), 590784);
+        assert_eq!(reboot::<SignedReactor>(&initialization), 590784);
+
+        let steps = input_parser(REBOOT_EXAMPLE).unwrap().1;
+        assert_eq!(reboot::<ReactorState>(&steps), 2758514936282235);
+        assert_eq!(reboot::<SignedReactor>(&steps), 2758514936282235);
+    }
+
+    const EXAMPLE: &str = "on x=10..12,y=10..12,z=10..12
+on x=11..13,y=11..13,z=11..13
+off x=9..11,y=9..11,z=9..11
+on x=10..10,y=10..10,z=10..10
+";
+
+    const LARGER_EXAMPLE: &str = "on x=-20..26,y=-36..17,z=-47..7
+on x=-20..33,y=-21..23,z=-26..28
+on x=-22..28,y=-29..23,z=-38..16
+on x=-46..7,y=-6..46,z=-50..-1
+on x=-49..1,y=-3..46,z=-24..28
+on x=2..47,y=-22..22,z=-23..27
+on x=-27..23,y=-28..26,z=-21..29
+on x=-39..5,y=-6..47,z=-3..44
+on x=-30..21,y=-8..43,z=-13..34
+on x=-22..26,y=-27..20,z=-29..19
+off x=-48..-32,y=26..41,z=-47..-37
+on x=-12..35,y=6..50,z=-50..-2
+off x=-48..-32,y=-32..-16,z=-15..-5
+on x=-18..26,y=-33..15,z=-7..46
+off x=-40..-22,y=-38..-28,z=23..41
+on x=-16..35,y=-41..10,z=-47..6
+off x=-32..-23,y=11..30,z=-14..3
+on x=-49..-5,y=-3..45,z=-29..18
+off x=18..30,y=-20..-8,z=-3..13
+on x=-41..9,y=-7..43,z=-33..15
+on x=-54112..-39298,y=-85059..-49293,z=-27449..7877
+on x=967..23432,y=45373..81175,z=27513..53682
+";
+
+    const REBOOT_EXAMPLE: &str = "on x=-5..47,y=-31..22,z=-19..33
+on x=-44..5,y=-27..21,z=-14..35
+on x=-49..-1,y=-11..42,z=-10..38
+on x=-20..34,y=-40..6,z=-44..1
+off x=26..39,y=40..50,z=-2..11
+on x=-41..5,y=-41..6,z=-36..8
+off x=-43..-33,y=-45..-28,z=7..25
+on x=-33..15,y=-32..19,z=-34..11
+off x=35..47,y=-46..-34,z=-11..5
+on x=-14..36,y=-6..44,z=-16..29
+on x=-57795..-6158,y=29564..72030,z=20435..90618
+on x=36731..105352,y=-21140..28532,z=16094..90401
+on x=30999..107136,y=-53464..15513,z=8553..71215
+on x=13528..83982,y=-99403..-27377,z=-24141..23996
+on x=-72682..-12347,y=18159..111354,z=7391..80950
+on x=-1060..80757,y=-65301..-20884,z=-103788..-16709
+on x=-83015..-9461,y=-72160..-8347,z=-81239..-26856
+on x=-52752..22273,y=-49450..9096,z=54442..119054
+on x=-29982..40483,y=-108474..-28371,z=-24328..38471
+on x=-4958..62750,y=40422..118853,z=-7672..65583
+on x=55694..108686,y=-43367..46958,z=-26781..48729
+on x=-98497..-18186,y=-63569..3412,z=1232..88485
+on x=-726..56291,y=-62629..13224,z=18033..85226
+on x=-110886..-34664,y=-81338..-8658,z=8914..63723
+on x=-55829..24974,y=-16897..54165,z=-121762..-28058
+on x=-65152..-11147,y=22489..91432,z=-58782..1780
+on x=-120100..-32970,y=-46592..27473,z=-11695..61039
+on x=-18631..37533,y=-124565..-50804,z=-35667..28308
+on x=-57817..18248,y=49321..117703,z=5745..55881
+on x=14781..98692,y=-1341..70827,z=15753..70151
+on x=-34419..55919,y=-19626..40991,z=39015..114138
+on x=-60785..11593,y=-56135..2999,z=-95368..-26915
+on x=-32178..58085,y=17647..101866,z=-91405..-8878
+on x=-53655..12091,y=50097..105568,z=-75335..-4862
+on x=-111166..-40997,y=-71714..2688,z=5609..50954
+on x=-16602..70118,y=-98693..-44401,z=5197..76897
+on x=16383..101554,y=4615..83635,z=-44907..18747
+off x=-95822..-15171,y=-19987..48940,z=10804..104439
+on x=-89813..-14614,y=16069..88491,z=-3297..45228
+on x=41075..99376,y=-20427..49978,z=-52012..13762
+on x=-21330..50085,y=-17944..62733,z=-112280..-30197
+on x=-16478..35915,y=36008..118594,z=-7885..47086
+off x=-98156..-27851,y=-49952..43171,z=-99005..-8456
+off x=2032..69770,y=-71013..4824,z=7471..94418
+on x=43670..120875,y=-42068..12382,z=-24787..38892
+off x=37514..111226,y=-45862..25743,z=-16714..54663
+off x=25699..97951,y=-30668..59918,z=-15349..69697
+off x=-44271..17935,y=-9516..60759,z=49131..112598
+on x=-61695..-5813,y=40978..94975,z=8655..80240
+off x=-101086..-9439,y=-7088..67543,z=33935..83858
+off x=18020..114017,y=-48931..32606,z=21474..89843
+off x=-77139..10506,y=-89994..-18797,z=-80..59318
+off x=8476..79288,y=-75520..11602,z=-96624..-24783
+on x=-47488..-1262,y=24338..100707,z=16292..72967
+off x=-84341..13987,y=2429..92914,z=-90671..-1318
+off x=-37810..49457,y=-71013..-7894,z=-105357..-13188
+off x=-27365..46395,y=31009..98017,z=15428..76570
+off x=-70369..-16548,y=22648..78696,z=-1892..86821
+on x=-53470..21291,y=-120233..-33476,z=-44150..38147
+off x=-93533..-4276,y=-16170..68771,z=-104985..-24507
+";
+
+    #[test]
+    fn example() {
+        assert_eq!(
+            solve::<Day22>(EXAMPLE, &[1, 2], &Day22Params::example()).unwrap(),
+            [Answer::Integer(39), Answer::Integer(39)]
+        );
+    }
+
+    #[test]
+    fn larger_examples() {
+        assert_eq!(
+            solve::<Day22>(LARGER_EXAMPLE, &[1], &Day22Params::example()).unwrap(),
+            [Answer::Integer(590784)]
+        );
+        assert_eq!(
+            solve::<Day22>(REBOOT_EXAMPLE, &[1, 2], &Day22Params::example()).unwrap(),
+            [Answer::Integer(474140), Answer::Integer(2758514936282235)]
+        );
+    }
 }
@@ -1,470 +1,515 @@
-use itertools::Itertools;
-use nom::{
-    bytes::complete::tag, combinator::map_res, multi::separated_list1, sequence::separated_pair,
-    IResult,
-};
-use std::cmp::Ordering;
-use std::cmp::Reverse;
-use std::ops;
-use std::os::macos::raw::stat;
-use std::{
-    collections::{BinaryHeap, HashMap, HashSet},
-    hash::Hash,
-};
-
-#[derive(Eq, PartialEq, Clone, Debug)]
-struct Move {
-    to_move: char,
-    start: (usize, usize),
-    end: (usize, usize),
+use aoc_common::parse::ParseError;
+use aoc_common::search::dijkstra;
+use aoc_common::solution::{Answer, Solution};
+use aoc_common::{debug, warn};
+use std::fmt;
+
+// the rows part 2 unfolds into the middle of the rooms, one letter per room
+const FOLDED_ROWS: [&str; 2] = ["DCBA", "DBAC"];
+
+// the shape of a burrow: how long the hallway is, where along it each room's door is and how many
+// amphipods fit in a room. room `i` is where the amphipods written as the `i`th letter belong
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Burrow {
+    hallway: usize,
+    doors: Vec<usize>,
+    depth: usize,
 }
 
-impl Move {
-    fn cost(&self) -> usize {
-        if self.start.1 == 1 || self.end.1 == 1 {
-            //going to or from the hallway means manhattan can work
-            manhattan_distance(self.start, self.end) * Move::cost_modifier(self.to_move)
-        } else {
-            //going from a room to a room.
-            // we need to calculate the cost to go up to the hallway too
-            let lateral_cost = (self.start.0 as isize - self.end.0 as isize).abs() as usize;
-            let cost_to_hallway =
-                (self.start.1 as isize - 1) as usize + (self.end.1 as isize - 1) as usize;
-            (lateral_cost + cost_to_hallway) * Move::cost_modifier(self.to_move)
-        }
-    }
+// the kind of amphipod in every spot, rooms listed from the top down
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct State {
+    hallway: Vec<Option<usize>>,
+    rooms: Vec<Vec<Option<usize>>>,
+}
 
-    fn cost_modifier(c: char) -> usize {
-        match c {
-            'A' => 1,
-            'B' => 10,
-            'C' => 100,
-            'D' => 1000,
-            _ => {
-                panic!("cannot move a {}", c)
-            }
-        }
-    }
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Spot {
+    Hallway(usize),
+    // the room and how far down it
+    Room(usize, usize),
 }
 
-impl Ord for Move {
-    fn cmp(&self, other: &Self) -> Ordering {
-        self.cost().cmp(&other.cost())
-    }
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Move {
+    amphipod: usize,
+    from: Spot,
+    to: Spot,
+    energy: usize,
 }
 
-impl PartialOrd for Move {
-    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        Some(self.cmp(other))
-    }
+fn letter(amphipod: usize) -> char {
+    (b'A' + amphipod as u8) as char
 }
 
-#[derive(Eq, PartialEq)]
-struct UnstructuredState {
-    moves: usize,
-    history: Vec<Move>,
-    map: HashMap<(usize, usize), char>,
+fn energy_per_step(amphipod: usize) -> usize {
+    10_usize.pow(amphipod as u32)
 }
 
-impl UnstructuredState {
-    fn from_map(map: HashMap<(usize, usize), char>) -> Self {
-        UnstructuredState {
-            moves: 0,
-            history: vec![],
-            map,
+impl fmt::Display for Spot {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Spot::Hallway(x) => write!(f, "hallway {}", x),
+            Spot::Room(room, slot) => write!(f, "room {} slot {}", letter(*room), slot),
         }
     }
+}
 
-    fn is_complete(&self) -> bool {
-        for (&position, &c) in self.map.iter() {
-            if !self.is_in_final_destination(position, c) {
-                return false;
-            }
-        }
-
-        true
+impl fmt::Display for Move {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} from {} to {} ({} energy)",
+            letter(self.amphipod),
+            self.from,
+            self.to,
+            self.energy
+        )
     }
+}
 
-    fn apply_move(&self, m: Move) -> Self {
-        let mut new_map = self.map.clone();
-
-        new_map.insert(m.start, '.');
-        new_map.insert(m.end, m.to_move);
-
-        let mut new_history = self.history.clone();
-        new_history.push(m.clone());
-
-        UnstructuredState {
-            moves: self.moves + m.cost(),
-            history: new_history,
-            map: new_map,
+impl State {
+    fn get(&self, spot: Spot) -> Option<usize> {
+        match spot {
+            Spot::Hallway(x) => self.hallway[x],
+            Spot::Room(room, slot) => self.rooms[room][slot],
         }
     }
 
-    fn is_in_final_destination(&self, (x, y): (usize, usize), c: char) -> bool {
-        if !(('A'..'E').contains(&c)) {
-            return true;
+    fn set(&mut self, spot: Spot, amphipod: Option<usize>) {
+        match spot {
+            Spot::Hallway(x) => self.hallway[x] = amphipod,
+            Spot::Room(room, slot) => self.rooms[room][slot] = amphipod,
         }
+    }
 
-        let expected_x = (c as usize - 'A' as usize) * 2 + 3;
-
-        if x != expected_x {
-            return false;
-        }
-
-        if y == 5 {
-            //bottom spot
-            true
-        } else if y > 1 {
-            //check all the spots below
-            for to_check in (y + 1)..6 {
-                if *self.map.get(&(x, to_check)).unwrap() != c {
-                    return false;
-                }
-                // && self.is_in_final_destination((x, to_check), c)
-            }
-
-            true
-        } else {
-            //not in a final room
-            false
-        }
+    fn is_done(&self) -> bool {
+        self.rooms
+            .iter()
+            .enumerate()
+            .all(|(room, slots)| slots.iter().all(|&slot| slot == Some(room)))
     }
 
-    fn is_in_hallway(&self, (x, y): (usize, usize)) -> bool {
-        y == 1
+    // a room is settled when nobody in it has to leave, so its own amphipods can move in
+    fn is_settled(&self, room: usize) -> bool {
+        self.rooms[room]
+            .iter()
+            .all(|&slot| slot.is_none() || slot == Some(room))
     }
 
-    fn is_move_blocked(&self, m: &Move) -> bool {
-        //it's blocked if there's another item in the hallway that's between current and destination in the x direction
+    // where an amphipod moving into a room stops: the free spot just above whoever's in it already
+    fn entry(&self, room: usize) -> Option<usize> {
+        let slots = &self.rooms[room];
+        slots
+            .iter()
+            .position(Option::is_some)
+            .unwrap_or(slots.len())
+            .checked_sub(1)
+    }
 
-        //if starting in the bottom row
-        if m.start.1 > 2 {
-            for y in 1..(m.start.1) {
-                if self.map.get(&(m.start.0, y)) != Some(&'.') {
-                    return true;
-                }
-            }
-        }
+    fn apply(&self, m: &Move) -> State {
+        let mut next = self.clone();
+        next.set(m.from, None);
+        next.set(m.to, Some(m.amphipod));
+        next
+    }
+}
 
-        //if ending in the bottom row
-        if m.end.1 > 2 {
-            for y in 1..(m.end.1) {
-                if self.map.get(&(m.end.0, y)) != Some(&'.') {
-                    return true;
-                }
-            }
+impl Burrow {
+    fn position(&self, spot: Spot) -> (usize, usize) {
+        match spot {
+            Spot::Hallway(x) => (x, 0),
+            Spot::Room(room, slot) => (self.doors[room], slot + 1),
         }
+    }
 
-        let x_start = m.start.0.min(m.end.0);
-        let x_end = m.start.0.max(m.end.0);
+    // up to the hallway, along it and back down
+    fn distance(&self, from: Spot, to: Spot) -> usize {
+        let ((from_x, from_depth), (to_x, to_depth)) = (self.position(from), self.position(to));
+        from_x.abs_diff(to_x) + from_depth + to_depth
+    }
 
-        //these modifiers may be wrong
-        for x in (x_start + 1)..x_end {
-            if self.map.get(&(x, 1)) != Some(&'.') {
-                return true;
-            }
+    fn step(&self, amphipod: usize, from: Spot, to: Spot) -> Move {
+        Move {
+            amphipod,
+            from,
+            to,
+            energy: self.distance(from, to) * energy_per_step(amphipod),
         }
-
-        false
     }
 
-    fn is_in_room_opening(&(x, y): &(usize, usize)) -> bool {
-        y == 1 && (x == 3 || x == 5 || x == 7 || x == 9)
+    // whether the hallway is empty from next to `from` up to and including `to`
+    fn is_clear(state: &State, from: usize, to: usize) -> bool {
+        let cells = if from < to {
+            from + 1..to + 1
+        } else {
+            to..from
+        };
+        cells.into_iter().all(|x| state.hallway[x].is_none())
     }
 
-    fn is_valid_move(&self, possible_move: &Move) -> bool {
-        if UnstructuredState::is_in_room_opening(&possible_move.end) {
-            return false;
-        }
+    // amphipods only ever leave their room for a spot in the hallway that isn't in front of a
+    // door, or to go straight into their own settled room, and only ever leave the hallway for
+    // their own settled room
+    fn moves(&self, state: &State) -> Vec<Move> {
+        let mut moves = vec![];
 
-        if self.is_in_hallway(possible_move.start) {
-            if self.is_in_hallway(possible_move.end) {
-                return false;
+        for (x, &spot) in state.hallway.iter().enumerate() {
+            let Some(amphipod) = spot else {
+                continue;
+            };
+            let door = self.doors[amphipod];
+            if !state.is_settled(amphipod) || !Burrow::is_clear(state, x, door) {
+                continue;
             }
-
-            if !self.is_in_final_destination(possible_move.end, possible_move.to_move) {
-                return false;
+            if let Some(slot) = state.entry(amphipod) {
+                moves.push(self.step(amphipod, Spot::Hallway(x), Spot::Room(amphipod, slot)));
             }
-        } else if !self.is_in_hallway(possible_move.end)
-            && !self.is_in_final_destination(possible_move.end, possible_move.to_move)
-        {
-            return false;
-        }
-
-        if self.is_move_blocked(possible_move) {
-            return false;
         }
 
-        true
-    }
+        for (room, &door) in self.doors.iter().enumerate() {
+            if state.is_settled(room) {
+                continue;
+            }
+            let Some(slot) = state.rooms[room].iter().position(Option::is_some) else {
+                continue;
+            };
+            let amphipod = state.rooms[room][slot].unwrap();
+            if state.hallway[door].is_some() {
+                continue;
+            }
 
-    fn print(&self) -> String {
-        let mut output = String::new();
-        for y in 0..7 {
-            for x in 0..13 {
-                if let Some(c) = self.map.get(&(x, y)) {
-                    output.push(*c);
+            // rooms with doors side by side have nowhere to stop between them, so going straight
+            // across can be cheaper than stopping anywhere in the hallway
+            let target = self.doors[amphipod];
+            if state.is_settled(amphipod) && Burrow::is_clear(state, door, target) {
+                if let Some(target_slot) = state.entry(amphipod) {
+                    let to = Spot::Room(amphipod, target_slot);
+                    moves.push(self.step(amphipod, Spot::Room(room, slot), to));
                 }
             }
 
-            output.push('\n');
+            for x in (0..self.hallway).filter(|x| !self.doors.contains(x)) {
+                if Burrow::is_clear(state, door, x) {
+                    moves.push(self.step(amphipod, Spot::Room(room, slot), Spot::Hallway(x)));
+                }
+            }
         }
 
-        output
+        moves
     }
-}
 
-impl Ord for UnstructuredState {
-    fn cmp(&self, other: &Self) -> Ordering {
-        self.moves.cmp(&other.moves)
+    // the least energy to sort every amphipod into its room, with the moves that do it
+    fn organise(&self, start: &State) -> Option<(usize, Vec<Move>)> {
+        let result = dijkstra(
+            [start.clone()],
+            |state| {
+                self.moves(state)
+                    .iter()
+                    .map(|m| (state.apply(m), m.energy))
+                    .collect::<Vec<_>>()
+            },
+            State::is_done,
+        )?;
+
+        let moves = result
+            .path
+            .windows(2)
+            .map(|pair| self.move_between(&pair[0], &pair[1]))
+            .collect();
+
+        Some((result.cost, moves))
     }
-}
 
-impl PartialOrd for UnstructuredState {
-    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        Some(self.cmp(other))
-    }
-}
+    fn move_between(&self, before: &State, after: &State) -> Move {
+        let spots: Vec<Spot> = (0..self.hallway)
+            .map(Spot::Hallway)
+            .chain(
+                (0..self.doors.len())
+                    .flat_map(|room| (0..self.depth).map(move |slot| Spot::Room(room, slot))),
+            )
+            .collect();
 
-pub fn part1() -> usize {
-    return 0;
-    let input = include_str!("../../data/2021/23.txt");
+        let from = *spots
+            .iter()
+            .find(|&&spot| before.get(spot).is_some() && after.get(spot).is_none())
+            .expect("a move empties a spot");
+        let to = *spots
+            .iter()
+            .find(|&&spot| before.get(spot).is_none() && after.get(spot).is_some())
+            .expect("a move fills a spot");
 
-    let initial_state = input_parser(input);
+        self.step(before.get(from).unwrap(), from, to)
+    }
 
-    println!("Parsed! running");
+    // the burrow drawn the way the puzzle does
+    fn draw(&self, state: &State) -> String {
+        let cell = |spot: Option<usize>| spot.map(letter).unwrap_or('.');
+        let width = self.hallway + 2;
+        let (first_door, last_door) = (self.doors[0] + 1, self.doors[self.doors.len() - 1] + 1);
+
+        let mut lines = vec![
+            "#".repeat(width),
+            format!(
+                "#{}#",
+                state
+                    .hallway
+                    .iter()
+                    .map(|&spot| cell(spot))
+                    .collect::<String>()
+            ),
+        ];
+        for slot in 0..self.depth {
+            let (start, end) = if slot == 0 {
+                (0, width - 1)
+            } else {
+                (first_door - 1, last_door + 1)
+            };
+            let row: String = (start..=end)
+                .map(
+                    |x| match self.doors.iter().position(|&door| door + 1 == x) {
+                        Some(room) => cell(state.rooms[room][slot]),
+                        None => '#',
+                    },
+                )
+                .collect();
+            lines.push(format!("{}{}", " ".repeat(start), row));
+        }
+        lines.push(format!(
+            "{}{}",
+            " ".repeat(first_door - 1),
+            "#".repeat(last_door - first_door + 3)
+        ));
 
-    let mut states_to_consider: BinaryHeap<Reverse<UnstructuredState>> = BinaryHeap::new();
-    states_to_consider.push(Reverse(initial_state));
+        lines.join("\n")
+    }
 
-    //hacky
-    let mut states_seen_so_far: HashMap<String, usize> = HashMap::new();
+    // every move with the burrow after it
+    fn replay(&self, start: &State, moves: &[Move]) -> String {
+        let mut state = start.clone();
+        let mut steps = vec![self.draw(&state)];
+        for m in moves {
+            state = state.apply(m);
+            steps.push(format!("{}\n{}", m, self.draw(&state)));
+        }
 
-    loop {
-        if let Some(Reverse(state_to_try_next)) = states_to_consider.pop() {
-            if state_to_try_next.is_complete() {
-                println!("complete");
-                println!("{}", state_to_try_next.print());
-                println!("how we got here: {:?}", state_to_try_next.history);
-                return state_to_try_next.moves;
-            }
+        steps.join("\n\n")
+    }
 
-            if let Some(&minimum_score) = states_seen_so_far.get(&state_to_try_next.print()) {
-                if state_to_try_next.moves >= minimum_score {
-                    println!("skipping dupe");
-                    continue;
-                }
-            }
+    fn is_standard(&self) -> bool {
+        self.hallway == 11 && self.doors == [2, 4, 6, 8]
+    }
 
-            states_seen_so_far.insert(state_to_try_next.print(), state_to_try_next.moves);
+    // the burrow from part 2, with the extra rows in the middle of the rooms
+    fn unfolded(&self, state: &State) -> (Burrow, State) {
+        if !self.is_standard() || self.depth != 2 {
+            return (self.clone(), state.clone());
+        }
 
-            let possible_moves = generate_possible_moves(&state_to_try_next);
-            for possible_move in possible_moves {
-                states_to_consider.push(Reverse(state_to_try_next.apply_move(possible_move)))
+        let mut unfolded = state.clone();
+        for (room, slots) in unfolded.rooms.iter_mut().enumerate() {
+            for (index, row) in FOLDED_ROWS.iter().enumerate() {
+                let amphipod = row.as_bytes()[room] - b'A';
+                slots.insert(index + 1, Some(amphipod as usize));
             }
+        }
 
-            println!();
-            println!("{}", state_to_try_next.print());
-            println!();
+        (
+            Burrow {
+                depth: 4,
+                ..self.clone()
+            },
+            unfolded,
+        )
+    }
 
-            println!(
-                "current cost {} states to consider: {}",
-                state_to_try_next.moves,
-                states_to_consider.len()
-            );
+    // the part 1 burrow inside an unfolded one
+    fn folded(&self, state: &State) -> (Burrow, State) {
+        let unfolded_rows = (0..self.doors.len()).all(|room| {
+            (1..3).all(|slot| {
+                state.rooms[room].get(slot).copied().flatten().map(letter)
+                    == FOLDED_ROWS[slot - 1].chars().nth(room)
+            })
+        });
+        if !self.is_standard() || self.depth != 4 || !unfolded_rows {
+            return (self.clone(), state.clone());
+        }
 
-            println!();
-            println!();
-        } else {
-            panic!("failed to have a state to consider before completing")
+        let mut folded = state.clone();
+        for slots in folded.rooms.iter_mut() {
+            slots.drain(1..3);
         }
+
+        (
+            Burrow {
+                depth: 2,
+                ..self.clone()
+            },
+            folded,
+        )
     }
 }
 
-pub fn part2() -> usize {
-    let input = include_str!("../../data/2021/23.txt");
-
-    let initial_state = input_parser(input);
-
-    println!("Parsed! running");
-
-    let mut states_to_consider: BinaryHeap<Reverse<UnstructuredState>> = BinaryHeap::new();
-    states_to_consider.push(Reverse(initial_state));
-
-    //hacky
-    let mut states_seen_so_far: HashMap<String, usize> = HashMap::new();
-
-    loop {
-        if let Some(Reverse(state_to_try_next)) = states_to_consider.pop() {
-            if state_to_try_next.is_complete() {
-                println!("complete");
-                println!("{}", state_to_try_next.print());
-                println!("how we got here: {:?}", state_to_try_next.history);
-                return state_to_try_next.moves;
-            }
-
-            if let Some(&minimum_score) = states_seen_so_far.get(&state_to_try_next.print()) {
-                if state_to_try_next.moves >= minimum_score {
-                    println!("skipping dupe");
-                    continue;
-                }
-            }
-
-            states_seen_so_far.insert(state_to_try_next.print(), state_to_try_next.moves);
-
-            let possible_moves = generate_possible_moves(&state_to_try_next);
-            for possible_move in possible_moves {
-                states_to_consider.push(Reverse(state_to_try_next.apply_move(possible_move)))
-            }
-
-            println!();
-            println!("{}", state_to_try_next.print());
-            println!();
-
-            println!(
-                "current cost {} states to consider: {}",
-                state_to_try_next.moves,
-                states_to_consider.len()
-            );
-
-            println!();
-            println!();
-        } else {
-            panic!("failed to have a state to consider before completing")
+fn least_energy(burrow: &Burrow, start: &State) -> Answer {
+    match burrow.organise(start) {
+        Some((energy, moves)) => {
+            debug!("replay:\n{}", burrow.replay(start, &moves));
+            energy.into()
+        }
+        None => {
+            warn!("the amphipods can't all get to their rooms");
+            Answer::Unsolved
         }
     }
 }
 
-fn manhattan_distance(a: (usize, usize), b: (usize, usize)) -> usize {
-    (a.0 as isize - b.0 as isize).abs() as usize + (a.1 as isize - b.1 as isize).abs() as usize
-}
+pub struct Day23;
 
-fn generate_possible_moves(state: &UnstructuredState) -> Vec<Move> {
-    let mut moves = Vec::new();
-    for (&position, &c) in state.map.iter() {
-        if !(('A'..'E').contains(&c)) {
-            continue;
-        }
+impl Solution for Day23 {
+    type Input<'a> = (Burrow, State);
 
-        if state.is_in_final_destination(position, c) {
-            continue;
-        }
+    type Params = ();
 
-        for (&possible_destination_position, _) in state
-            .map
-            .iter()
-            .filter(|(_, &destination_c)| destination_c == '.')
-        {
-            let possible_move = Move {
-                to_move: c,
-                start: position,
-                end: possible_destination_position,
-            };
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        input_parser(input)
+    }
 
-            if state.is_valid_move(&possible_move) {
-                moves.push(possible_move)
-            }
-        }
+    // either part can be given the burrow folded or unfolded
+    fn part1((burrow, state): &Self::Input<'_>, _params: &Self::Params) -> Answer {
+        let (burrow, state) = burrow.folded(state);
+        least_energy(&burrow, &state)
     }
 
-    moves
+    fn part2((burrow, state): &Self::Input<'_>, _params: &Self::Params) -> Answer {
+        let (burrow, state) = burrow.unfolded(state);
+        least_energy(&burrow, &state)
+    }
 }
 
-fn input_parser(input: &str) -> UnstructuredState {
-    let mut state = HashMap::new();
+// the hallway is the second line, and every line after it with open spots is a row of the rooms.
+// the first row of the rooms says where the doors are and the rest have to agree
+fn input_parser(input: &str) -> Result<(Burrow, State), ParseError> {
+    let lines: Vec<&str> = input.lines().collect();
+    let is_open = |c: char| c == '.' || c.is_ascii_uppercase();
+    let kind = |c: char| c.is_ascii_uppercase().then(|| (c as u8 - b'A') as usize);
+
+    let hallway_line = *lines
+        .get(1)
+        .ok_or_else(|| ParseError::at(input, &input[input.len()..], "a hallway"))?;
+    let left = hallway_line
+        .find(is_open)
+        .ok_or_else(|| ParseError::at(input, hallway_line, "a hallway"))?;
+    let hallway: Vec<Option<usize>> = hallway_line[left..]
+        .chars()
+        .take_while(|&c| is_open(c))
+        .map(kind)
+        .collect();
+
+    let room_lines: Vec<&str> = lines[2..]
+        .iter()
+        .copied()
+        .filter(|line| line.contains(is_open))
+        .collect();
+    let open_columns = |line: &str| -> Vec<usize> {
+        line.char_indices()
+            .filter(|&(_, c)| is_open(c))
+            .map(|(x, _)| x)
+            .collect()
+    };
+    let columns = room_lines
+        .first()
+        .map(|line| open_columns(line))
+        .unwrap_or_default();
+    if columns.is_empty()
+        || columns
+            .iter()
+            .any(|&x| x < left || x >= left + hallway.len())
+    {
+        return Err(ParseError::at(
+            input,
+            room_lines.first().unwrap_or(&&input[input.len()..]),
+            "rooms below the hallway",
+        ));
+    }
+    if let Some(line) = room_lines.iter().find(|line| open_columns(line) != columns) {
+        return Err(ParseError::at(
+            input,
+            line,
+            "rooms lined up with the ones above",
+        ));
+    }
 
-    for (y, line) in input.lines().enumerate() {
-        for (x, c) in line.chars().enumerate() {
-            state.insert((x, y), c);
-        }
+    let rooms: Vec<Vec<Option<usize>>> = columns
+        .iter()
+        .map(|&x| {
+            room_lines
+                .iter()
+                .map(|line| kind(line.as_bytes()[x] as char))
+                .collect()
+        })
+        .collect();
+    if let Some(line) = room_lines.iter().chain([&hallway_line]).find(|line| {
+        line.chars()
+            .filter_map(kind)
+            .any(|amphipod| amphipod >= rooms.len())
+    }) {
+        return Err(ParseError::at(input, line, "amphipods that have a room"));
     }
 
-    UnstructuredState::from_map(state)
+    Ok((
+        Burrow {
+            hallway: hallway.len(),
+            doors: columns.iter().map(|x| x - left).collect(),
+            depth: room_lines.len(),
+        },
+        State { hallway, rooms },
+    ))
 }
 
 #[cfg(test)]
 mod day23_tests {
     use crate::day23::input_parser;
+    use crate::day23::Day23;
     use crate::day23::Move;
-    use crate::day23::UnstructuredState;
+    use crate::day23::Spot;
+    use aoc_common::solution::{solve, Answer, Solution};
+
+    fn moves_from(input: &str, from: Spot) -> Vec<Spot> {
+        let (burrow, state) = input_parser(input).unwrap();
+        burrow
+            .moves(&state)
+            .into_iter()
+            .filter(|m| m.from == from)
+            .map(|m| m.to)
+            .collect()
+    }
 
     #[test]
-    fn test_invalid_moves_are_marked_invalid() {
+    fn leaving_a_room_stops_outside_doors() {
         let input = "#############
 #...........#
 ###B#C#B#D###
   #A#D#C#A#
   #########
 ";
-        let state = input_parser(input);
-
-        let test_move = Move {
-            to_move: 'B',
-            start: (3, 2), //far left, top B
-            end: (4, 1),
-        };
-
-        assert!(state.is_valid_move(&test_move));
+        let moves = moves_from(input, Spot::Room(0, 0));
+        assert!(moves.contains(&Spot::Hallway(3)));
+        assert!(moves.contains(&Spot::Hallway(1)));
+        assert!(!moves.contains(&Spot::Hallway(2)));
 
-        let test_move = Move {
-            to_move: 'B',
-            start: (3, 2), //far left, top B
-            end: (2, 1),
-        };
-
-        assert!(state.is_valid_move(&test_move));
-
-        let test_move = Move {
-            to_move: 'B',
-            start: (3, 2), //far left, top B
-            end: (3, 1),
-        };
-
-        assert!(!state.is_valid_move(&test_move));
-
-        let test_move = Move {
-            to_move: 'A',
-            start: (3, 3), //far left, top A
-            end: (4, 1),
-        };
-
-        assert!(!state.is_valid_move(&test_move));
+        assert!(moves_from(input, Spot::Room(0, 1)).is_empty());
     }
 
     #[test]
-    fn test_moves_where_there_is_a_block() {
+    fn moves_where_there_is_a_block() {
         let input = "#############
 #...A.....AD#
 ###B#C#B#.###
   #.#D#C#.#
   #########
 ";
-        let state = input_parser(input);
-
-        let test_move = Move {
-            to_move: 'B',
-            start: (3, 2), //far left, top B
-            end: (6, 1),
-        };
-
-        assert!(!state.is_valid_move(&test_move));
-
-        let test_move = Move {
-            to_move: 'A',
-            start: (4, 4), //far left, top A
-            end: (3, 3),
-        };
-
-        assert!(!state.is_valid_move(&test_move));
-
-        let test_move = Move {
-            to_move: 'D',
-            start: (11, 4), //far right, top D
-            end: (9, 3),
-        };
-
-        assert!(!state.is_valid_move(&test_move));
+        assert!(!moves_from(input, Spot::Room(0, 0)).contains(&Spot::Hallway(5)));
+        assert!(moves_from(input, Spot::Hallway(10)).is_empty());
 
         let input = "#############
 #.......A..D#
@@ -472,23 +517,8 @@ mod day23_tests {
   #A#D#C#.#
   #########
 ";
-        let state = input_parser(input);
-
-        let test_move = Move {
-            to_move: 'A',
-            start: (3, 3), //bottom left A
-            end: (1, 1),
-        };
-
-        assert!(!state.is_valid_move(&test_move));
-
-        let test_move = Move {
-            to_move: 'D',
-            start: (11, 1), //top right D
-            end: (9, 2),
-        };
-
-        assert!(!state.is_valid_move(&test_move));
+        assert!(moves_from(input, Spot::Room(0, 1)).is_empty());
+        assert_eq!(moves_from(input, Spot::Hallway(10)), [Spot::Room(3, 1)]);
 
         let input = "#############
 #.A.B.C...D.#
@@ -496,34 +526,11 @@ mod day23_tests {
   #A#D#C#.#
   #########
 ";
-        let state = input_parser(input);
-
-        let test_move = Move {
-            to_move: 'D',
-            start: (5, 3),
-            end: (9, 3),
-        };
-
-        assert!(!state.is_valid_move(&test_move));
+        assert_eq!(moves_from(input, Spot::Room(1, 1)), []);
     }
 
     #[test]
-    fn test_final_state_determination() {
-        let input = "#############
-#...A.....A.#
-###B#C#B#D###
-  #D#C#C#A#
-  #D#B#A#C#
-  #.#D#C#.#
-  #########
-";
-        let state = input_parser(input);
-
-        assert!(state.is_in_final_destination((7, 5), 'C'));
-        assert!(!state.is_in_final_destination((7, 3), 'C'));
-        assert!(!state.is_in_final_destination((5, 3), 'D'));
-        assert!(!state.is_in_final_destination((9, 2), 'D'));
-
+    fn final_state_determination() {
         let input = "#############
 #...........#
 ###B#C#B#D###
@@ -532,13 +539,20 @@ mod day23_tests {
   #A#D#C#A#
   #########
 ";
-        let state = input_parser(input);
+        let (_, state) = input_parser(input).unwrap();
+        assert!(!state.is_settled(2));
+        assert!(!state.is_done());
 
-        assert!(state.is_in_final_destination((7, 5), 'C'));
-        assert!(!state.is_in_final_destination((5, 5), 'D'));
-        assert!(!state.is_in_final_destination((9, 4), 'D')); //because the one below it is wrong
-        assert!(state.is_in_final_destination((3, 3), 'A'));
-        assert!(!state.is_complete());
+        let input = "#############
+#...........#
+###.#.#C#D###
+  #A#B#C#D#
+  #A#B#C#D#
+  #########
+";
+        let (_, state) = input_parser(input).unwrap();
+        assert!(state.is_settled(0) && state.is_settled(1));
+        assert!(!state.is_done());
 
         let input = "#############
 #...........#
@@ -548,39 +562,131 @@ mod day23_tests {
   #A#B#C#D#
   #########
 ";
-        let state = input_parser(input);
-
-        assert!(state.is_complete());
+        let (_, state) = input_parser(input).unwrap();
+        assert!(state.is_done());
     }
 
     #[test]
-    fn test_move_cost() {
+    fn move_cost() {
+        let (burrow, _) = input_parser(EXAMPLE).unwrap();
+
+        assert_eq!(burrow.step(0, Spot::Room(0, 1), Spot::Hallway(0)).energy, 4);
         assert_eq!(
-            Move {
-                to_move: 'A',
-                start: (3, 3), //bottom left A
-                end: (1, 1),
-            }
-            .cost(),
-            4
+            burrow.step(1, Spot::Room(0, 1), Spot::Hallway(0)).energy,
+            40
         );
         assert_eq!(
+            burrow.step(3, Spot::Room(1, 1), Spot::Room(3, 1)),
             Move {
-                to_move: 'B',
-                start: (3, 3), //bottom left A
-                end: (1, 1),
+                amphipod: 3,
+                from: Spot::Room(1, 1),
+                to: Spot::Room(3, 1),
+                energy: 8000
             }
-            .cost(),
-            40
         );
+    }
+
+    #[test]
+    fn draws_the_burrow_it_parsed() {
+        let (burrow, state) = input_parser(EXAMPLE).unwrap();
+        assert_eq!(format!("{}\n", burrow.draw(&state)), EXAMPLE);
+
+        let (burrow, state) = burrow.unfolded(&state);
+        assert_eq!(format!("{}\n", burrow.draw(&state)), UNFOLDED);
+        let (burrow, state) = burrow.folded(&state);
+        assert_eq!(format!("{}\n", burrow.draw(&state)), EXAMPLE);
+
+        assert!(input_parser("#####\n#...#\n#####\n").is_err());
+        assert!(input_parser("#######\n#.....#\n###C#A#\n  #A#B#\n").is_err());
+    }
+
+    #[test]
+    fn replays_a_custom_burrow() {
+        let input = "#########
+#.......#
+###B#A#C#
+  #######
+";
+        let (burrow, state) = input_parser(input).unwrap();
+        let (energy, moves) = burrow.organise(&state).unwrap();
+        assert_eq!(energy, 46);
+        assert_eq!(moves.len(), 4);
+
+        let replay = burrow.replay(&state, &moves);
+        assert!(replay.starts_with(input.trim_end()));
+        assert!(replay.ends_with("#.......#\n###A#B#C#\n  #######"));
+        assert_eq!(Day23::part1(&(burrow, state), &()), Answer::Integer(46));
+    }
+
+    #[test]
+    fn crosses_between_rooms_with_doors_side_by_side() {
+        let input = "#######
+#....B#
+###.A##
+  #AB#
+  ####
+";
+        assert!(moves_from(input, Spot::Room(1, 0)).contains(&Spot::Room(0, 0)));
+
+        // the A crossing over costs 3 where stopping in the hallway on the way would cost 5
+        let (burrow, state) = input_parser(input).unwrap();
+        let (energy, moves) = burrow.organise(&state).unwrap();
+        assert_eq!(energy, 23);
+        assert_eq!(moves.len(), 2);
+        assert_eq!(format!("{}\n", burrow.draw(&state)), input);
+    }
+
+    #[test]
+    fn enters_a_room_above_whoever_is_in_it() {
+        let input = "#########
+#A......#
+###.#.###
+  #A#B#
+  #.#B#
+  #####
+";
+        assert_eq!(moves_from(input, Spot::Hallway(0)), [Spot::Room(0, 0)]);
+
+        let input = "#########
+#A......#
+###.#.###
+  #.#B#
+  #A#B#
+  #####
+";
+        assert_eq!(moves_from(input, Spot::Hallway(0)), [Spot::Room(0, 1)]);
+    }
+
+    const EXAMPLE: &str = "#############
+#...........#
+###B#C#B#D###
+  #A#D#C#A#
+  #########
+";
+
+    const UNFOLDED: &str = "#############
+#...........#
+###B#C#B#D###
+  #D#C#B#A#
+  #D#B#A#C#
+  #A#D#C#A#
+  #########
+";
+
+    #[test]
+    fn example() {
         assert_eq!(
-            Move {
-                to_move: 'D',
-                start: (5, 3),
-                end: (9, 3)
-            }
-            .cost(),
-            8000
+            solve::<Day23>(EXAMPLE, &[1, 2], &()).unwrap(),
+            [Answer::Integer(12521), Answer::Integer(44169)]
+        );
+    }
+
+    // the input can be the unfolded burrow too
+    #[test]
+    fn unfolded_example() {
+        assert_eq!(
+            solve::<Day23>(UNFOLDED, &[1, 2], &()).unwrap(),
+            [Answer::Integer(12521), Answer::Integer(44169)]
         );
     }
 }
@@ -1,34 +1,36 @@
-use itertools::Itertools;
-use nom::error::Error;
+use aoc_common::parse::{parse_all, parse_number, ParseError};
+use aoc_common::solution::{Answer, Solution};
+use aoc_common::{debug, trace, warn};
 use nom::{
-    bytes::complete::tag, combinator::map_res, multi::separated_list1, sequence::separated_pair,
+    bytes::complete::tag,
+    error::{Error, ErrorKind},
+    multi::separated_list1,
     IResult,
 };
-use std::cmp::Ordering;
-use std::cmp::Reverse;
 use std::fmt;
-use std::ops;
-use std::{
-    collections::{BinaryHeap, HashMap, HashSet},
-    hash::Hash,
-};
 
-#[derive(Debug, Clone, PartialEq)]
-enum Register {
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Register {
     W,
     X,
     Y,
     Z,
 }
 
-#[derive(Clone, PartialEq)]
-enum Operand {
+impl Register {
+    fn index(self) -> usize {
+        self as usize
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Operand {
     Literal(i64),
     Variable(Register),
 }
 
-#[derive(Clone, PartialEq)]
-enum Instruction {
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Instruction {
     Input(Register),
     Add(Register, Operand),
     Multiply(Register, Operand),
@@ -37,509 +39,647 @@ enum Instruction {
     Equals(Register, Operand),
 }
 
-#[derive(Eq, PartialEq, Clone, Debug, Hash)]
-struct ALUState {
-    w: i64,
-    x: i64,
-    y: i64,
-    z: i64,
-    input_so_far: Vec<i64>,
+// the instructions print back out the way they're written in the puzzle input
+impl fmt::Display for Register {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Register::W => write!(f, "w"),
+            Register::X => write!(f, "x"),
+            Register::Y => write!(f, "y"),
+            Register::Z => write!(f, "z"),
+        }
+    }
 }
 
-impl ALUState {
-    fn new() -> Self {
-        ALUState {
-            w: 0,
-            x: 0,
-            y: 0,
-            z: 0,
-            input_so_far: vec![],
+impl fmt::Display for Operand {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Operand::Literal(value) => write!(f, "{}", value),
+            Operand::Variable(register) => write!(f, "{}", register),
         }
     }
+}
 
-    fn from_z(z: i64) -> Self {
-        ALUState {
-            w: 0,
-            x: 0,
-            y: 0,
-            z,
-            input_so_far: vec![],
+impl fmt::Display for Instruction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Instruction::Input(register) => write!(f, "inp {}", register),
+            Instruction::Add(register, operand) => write!(f, "add {} {}", register, operand),
+            Instruction::Multiply(register, operand) => write!(f, "mul {} {}", register, operand),
+            Instruction::Divide(register, operand) => write!(f, "div {} {}", register, operand),
+            Instruction::Modulus(register, operand) => write!(f, "mod {} {}", register, operand),
+            Instruction::Equals(register, operand) => write!(f, "eql {} {}", register, operand),
         }
     }
+}
 
-    fn execute(&self, instruction: &Instruction, input_stack: &mut Vec<i64>) -> Self {
-        match instruction {
-            Instruction::Input(register) => match input_stack.pop() {
-                Some(value) => {
-                    let mut next_state = self.set_register(register, value);
-                    next_state.input_so_far.push(value);
-                    next_state
-                }
-                None => {
-                    panic!("attempted to read input with no input left")
-                }
-            },
-            Instruction::Add(register, operand) => self.set_register(
-                register,
-                self.read_register(register) + self.read_operand(operand),
-            ),
-            Instruction::Multiply(register, operand) => self.set_register(
-                register,
-                self.read_register(register) * self.read_operand(operand),
-            ),
-            Instruction::Divide(register, operand) => self.set_register(
-                register,
-                self.read_register(register) / self.read_operand(operand),
-            ),
-            Instruction::Modulus(register, operand) => self.set_register(
-                register,
-                self.read_register(register) % self.read_operand(operand),
-            ),
-            Instruction::Equals(register, operand) => self.set_register(
-                register,
-                if self.read_register(register) == self.read_operand(operand) {
-                    1
-                } else {
-                    0
-                },
+// the ways the ALU can crash
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AluError {
+    OutOfInput,
+    DivideByZero,
+    InvalidModulus,
+}
+
+impl fmt::Display for AluError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            AluError::OutOfInput => write!(f, "read input with no input left"),
+            AluError::DivideByZero => write!(f, "divided by zero"),
+            AluError::InvalidModulus => write!(
+                f,
+                "took a modulus of a negative number or by a non-positive one"
             ),
         }
     }
+}
 
-    fn set_register(&self, register: &Register, value: i64) -> Self {
-        match register {
-            Register::W => ALUState {
-                w: value,
-                x: self.x,
-                y: self.y,
-                z: self.z,
-                input_so_far: self.input_so_far.clone(),
-            },
-            Register::X => ALUState {
-                x: value,
-                w: self.w,
-                y: self.y,
-                z: self.z,
-                input_so_far: self.input_so_far.clone(),
-            },
-            Register::Y => ALUState {
-                y: value,
-                w: self.w,
-                x: self.x,
-                z: self.z,
-                input_so_far: self.input_so_far.clone(),
-            },
-            Register::Z => ALUState {
-                z: value,
-                w: self.w,
-                x: self.x,
-                y: self.y,
-                input_so_far: self.input_so_far.clone(),
-            },
-        }
-    }
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Alu {
+    registers: [i64; 4],
+}
 
-    fn read_register(&self, register: &Register) -> i64 {
-        match register {
-            Register::W => self.w,
-            Register::X => self.x,
-            Register::Y => self.y,
-            Register::Z => self.z,
-        }
+impl Alu {
+    pub fn read(&self, register: Register) -> i64 {
+        self.registers[register.index()]
     }
 
-    fn read_operand(&self, operand: &Operand) -> i64 {
+    fn operand(&self, operand: Operand) -> i64 {
         match operand {
-            Operand::Literal(value) => *value,
-            Operand::Variable(register) => self.read_register(register),
+            Operand::Literal(value) => value,
+            Operand::Variable(register) => self.read(register),
         }
     }
 
-    fn print(&self) {
-        println!("W: {}, X: {}, Y: {}, Z: {}", self.w, self.x, self.y, self.z);
+    fn execute(
+        &mut self,
+        instruction: &Instruction,
+        input: &mut impl Iterator<Item = i64>,
+    ) -> Result<(), AluError> {
+        let (register, value) = match *instruction {
+            Instruction::Input(register) => (register, input.next().ok_or(AluError::OutOfInput)?),
+            Instruction::Add(register, operand) => {
+                (register, self.read(register) + self.operand(operand))
+            }
+            Instruction::Multiply(register, operand) => {
+                (register, self.read(register) * self.operand(operand))
+            }
+            Instruction::Divide(register, operand) => match self.operand(operand) {
+                0 => return Err(AluError::DivideByZero),
+                divisor => (register, self.read(register) / divisor),
+            },
+            Instruction::Modulus(register, operand) => {
+                match (self.read(register), self.operand(operand)) {
+                    (value, modulus) if value >= 0 && modulus > 0 => (register, value % modulus),
+                    _ => return Err(AluError::InvalidModulus),
+                }
+            }
+            Instruction::Equals(register, operand) => (
+                register,
+                (self.read(register) == self.operand(operand)) as i64,
+            ),
+        };
+
+        self.registers[register.index()] = value;
+        Ok(())
     }
 }
 
-impl Ord for ALUState {
-    fn cmp(&self, other: &Self) -> Ordering {
-        self.z.cmp(&other.z)
+// runs the whole program from zeroed registers, reading `input` in order
+pub fn run(program: &[Instruction], input: &[i64]) -> Result<Alu, AluError> {
+    let mut alu = Alu::default();
+    let mut input = input.iter().copied();
+    for instruction in program {
+        alu.execute(instruction, &mut input)?;
     }
+
+    Ok(alu)
 }
 
-impl PartialOrd for ALUState {
-    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        Some(self.cmp(other))
-    }
+// a register's value in terms of the digits read so far, `d0` being the first. `Z` stands for
+// whatever z held when a block started
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Expr {
+    Const(i64),
+    Digit(usize),
+    Z,
+    Add(Box<Expr>, Box<Expr>),
+    Mul(Box<Expr>, Box<Expr>),
+    Div(Box<Expr>, Box<Expr>),
+    Mod(Box<Expr>, Box<Expr>),
+    Eql(Box<Expr>, Box<Expr>),
 }
 
-impl fmt::Debug for Instruction {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+impl Expr {
+    // the smallest and largest values the expression can take. z is never negative going into a
+    // block since the ALU would have crashed taking its modulus
+    fn range(&self) -> (i64, i64) {
         match self {
-            Instruction::Input(register) => {
-                write!(f, "read input into {:?}", register)
-            }
-            Instruction::Add(register, operand) => {
-                write!(f, "{:?} <- {:?} + {:?}", register, register, operand)
-            }
-            Instruction::Multiply(register, operand) => {
-                write!(f, "{:?} <- {:?} * {:?}", register, register, operand)
-            }
-            Instruction::Divide(register, operand) => {
-                write!(f, "{:?} <- {:?} / {:?}", register, register, operand)
-            }
-            Instruction::Modulus(register, operand) => {
-                write!(f, "{:?} <- {:?} % {:?}", register, register, operand)
+            Expr::Const(value) => (*value, *value),
+            Expr::Digit(_) => (1, 9),
+            Expr::Z => (0, i64::MAX),
+            Expr::Add(a, b) => {
+                let ((a_min, a_max), (b_min, b_max)) = (a.range(), b.range());
+                (a_min.saturating_add(b_min), a_max.saturating_add(b_max))
             }
-            Instruction::Equals(register, operand) => {
-                write!(
-                    f,
-                    "{:?} <- {:?} = {:?} ? 1 : 0",
-                    register, register, operand
+            Expr::Mul(a, b) => {
+                let ((a_min, a_max), (b_min, b_max)) = (a.range(), b.range());
+                let products = [
+                    a_min.saturating_mul(b_min),
+                    a_min.saturating_mul(b_max),
+                    a_max.saturating_mul(b_min),
+                    a_max.saturating_mul(b_max),
+                ];
+                (
+                    *products.iter().min().unwrap(),
+                    *products.iter().max().unwrap(),
                 )
             }
+            Expr::Div(a, b) => match (a.range(), b.range()) {
+                ((a_min, a_max), (divisor, _)) if b.is_const() && divisor > 0 => {
+                    (a_min / divisor, a_max / divisor)
+                }
+                _ => (i64::MIN, i64::MAX),
+            },
+            Expr::Mod(a, b) => match (a.range(), b.range()) {
+                ((a_min, a_max), (modulus, _)) if b.is_const() && a_min >= 0 && modulus > 0 => {
+                    (0, a_max.min(modulus - 1))
+                }
+                _ => (i64::MIN, i64::MAX),
+            },
+            Expr::Eql(_, _) => (0, 1),
         }
     }
-}
 
-impl fmt::Debug for Operand {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        match self {
-            Operand::Variable(register) => {
-                write!(f, "{:?}", register)
-            }
-            Operand::Literal(literal) => {
-                write!(f, "{}", literal)
-            }
-        }
+    fn is_const(&self) -> bool {
+        matches!(self, Expr::Const(_))
     }
-}
-
-pub fn part1() -> u64 {
-    let input = include_str!("../../data/2021/24.txt");
 
-    match input_parser(input) {
-        Ok((remaining_input, nomad_program)) if remaining_input.is_empty() => {
-            println!("parsed entire input");
-
-            let grouped_by_input = split_by_input(&nomad_program);
+    fn within(&self, min: i64, max: i64) -> bool {
+        let (low, high) = self.range();
+        low >= min && high <= max
+    }
 
-            let mut pushes: Vec<usize> = vec![];
-            let mut pairs: Vec<(usize, usize)> = vec![];
+    // the constructors below fold whatever they can, keeping constants on the right
 
-            for (index, group) in grouped_by_input.iter().enumerate() {
-                if group.get(4).unwrap() == &Instruction::Divide(Register::Z, Operand::Literal(1)) {
-                    //push
-                    pushes.push(index);
-                } else {
-                    //pop
-                    let paired_push = pushes.pop().unwrap();
-                    pairs.push((paired_push, index));
-                }
+    fn sum(a: Expr, b: Expr) -> Expr {
+        match (a, b) {
+            (Expr::Const(a), Expr::Const(b)) => Expr::Const(a + b),
+            (a, Expr::Const(0)) | (Expr::Const(0), a) => a,
+            (Expr::Const(a), b) => Expr::sum(b, Expr::Const(a)),
+            (Expr::Add(a, c), Expr::Const(b)) if c.is_const() => {
+                Expr::sum(*a, Expr::sum(*c, Expr::Const(b)))
             }
-
-            let mut digits: Vec<i64> = vec![0; 14];
-
-            for (push, pop) in pairs {
-                let mut found_pair = false;
-                for first_input in (1..10).rev() {
-                    for second_input in (1..10).rev() {
-                        let resulting_state = run_program_segment(
-                            &grouped_by_input[push],
-                            first_input,
-                            ALUState::new(),
-                        );
-                        let resulting_state = run_program_segment(
-                            &grouped_by_input[pop],
-                            second_input,
-                            resulting_state,
-                        );
-
-                        if resulting_state.z == 0 {
-                            digits[push] = first_input;
-                            digits[pop] = second_input;
-                            found_pair = true;
-                            break;
-                        }
-                    }
-
-                    if found_pair {
-                        break;
-                    }
-                }
-            }
-
-            println!("found the digits: {:?}", digits);
-
-            input_to_int(&digits)
-        }
-        Ok((remaining, _)) => {
-            println!("remaining unparsed \"{}\"", remaining);
-            0
-        }
-        Err(e) => {
-            println!("error parsing \"{}\"", e);
-            0
+            (a, b) => Expr::Add(Box::new(a), Box::new(b)),
         }
     }
-}
-
-pub fn part2() -> u64 {
-    let input = include_str!("../../data/2021/24.txt");
-
-    match input_parser(input) {
-        Ok((remaining_input, nomad_program)) if remaining_input.is_empty() => {
-            println!("parsed entire input");
 
-            println!("parsed entire input");
-
-            let grouped_by_input = split_by_input(&nomad_program);
-
-            let mut pushes: Vec<usize> = vec![];
-            let mut pairs: Vec<(usize, usize)> = vec![];
+    fn product(a: Expr, b: Expr) -> Expr {
+        match (a, b) {
+            (Expr::Const(a), Expr::Const(b)) => Expr::Const(a * b),
+            (_, Expr::Const(0)) | (Expr::Const(0), _) => Expr::Const(0),
+            (a, Expr::Const(1)) | (Expr::Const(1), a) => a,
+            (Expr::Const(a), b) => Expr::product(b, Expr::Const(a)),
+            (a, b) => Expr::Mul(Box::new(a), Box::new(b)),
+        }
+    }
 
-            for (index, group) in grouped_by_input.iter().enumerate() {
-                if group.get(4).unwrap() == &Instruction::Divide(Register::Z, Operand::Literal(1)) {
-                    //push
-                    pushes.push(index);
-                } else {
-                    //pop
-                    let paired_push = pushes.pop().unwrap();
-                    pairs.push((paired_push, index));
+    fn quotient(a: Expr, b: Expr) -> Expr {
+        match (a, b) {
+            (Expr::Const(a), Expr::Const(b)) if b != 0 => Expr::Const(a / b),
+            (a, Expr::Const(1)) => a,
+            (a, Expr::Const(divisor)) if divisor > 0 && a.within(0, divisor - 1) => Expr::Const(0),
+            // (p * d + q) / d is p when q is a single base d digit
+            (Expr::Add(high, low), Expr::Const(divisor))
+                if divisor > 0 && low.within(0, divisor - 1) && high.within(0, i64::MAX) =>
+            {
+                match *high {
+                    Expr::Mul(p, d) if *d == Expr::Const(divisor) => *p,
+                    high => Expr::Div(
+                        Box::new(Expr::Add(Box::new(high), low)),
+                        Box::new(Expr::Const(divisor)),
+                    ),
                 }
             }
+            (Expr::Mul(p, d), Expr::Const(divisor)) if *d == Expr::Const(divisor) => *p,
+            (a, b) => Expr::Div(Box::new(a), Box::new(b)),
+        }
+    }
 
-            let mut digits: Vec<i64> = vec![0; 14];
-
-            for (push, pop) in pairs {
-                let mut found_pair = false;
-                for first_input in 1..10 {
-                    for second_input in 1..10 {
-                        let resulting_state = run_program_segment(
-                            &grouped_by_input[push],
-                            first_input,
-                            ALUState::new(),
-                        );
-                        let resulting_state = run_program_segment(
-                            &grouped_by_input[pop],
-                            second_input,
-                            resulting_state,
-                        );
-
-                        if resulting_state.z == 0 {
-                            digits[push] = first_input;
-                            digits[pop] = second_input;
-                            found_pair = true;
-                            break;
-                        }
-                    }
-
-                    if found_pair {
-                        break;
-                    }
+    fn remainder(a: Expr, b: Expr) -> Expr {
+        match (a, b) {
+            (Expr::Const(a), Expr::Const(b)) if a >= 0 && b > 0 => Expr::Const(a % b),
+            (a, Expr::Const(modulus)) if modulus > 0 && a.within(0, modulus - 1) => a,
+            // (p * m + q) % m is q for the same reason
+            (Expr::Add(high, low), Expr::Const(modulus))
+                if modulus > 0 && low.within(0, modulus - 1) && high.within(0, i64::MAX) =>
+            {
+                match *high {
+                    Expr::Mul(_, m) if *m == Expr::Const(modulus) => *low,
+                    high => Expr::Mod(
+                        Box::new(Expr::Add(Box::new(high), low)),
+                        Box::new(Expr::Const(modulus)),
+                    ),
                 }
             }
+            (Expr::Mul(_, m), Expr::Const(modulus)) if *m == Expr::Const(modulus) => Expr::Const(0),
+            (a, b) => Expr::Mod(Box::new(a), Box::new(b)),
+        }
+    }
 
-            println!("found the digits: {:?}", digits);
+    fn equals(a: Expr, b: Expr) -> Expr {
+        let ((a_min, a_max), (b_min, b_max)) = (a.range(), b.range());
+        if a == b {
+            Expr::Const(1)
+        } else if a_max < b_min || b_max < a_min || (a.is_const() && b.is_const()) {
+            Expr::Const(0)
+        } else {
+            Expr::Eql(Box::new(a), Box::new(b))
+        }
+    }
 
-            input_to_int(&digits)
+    // the value with the digits and the starting z filled in
+    pub fn evaluate(&self, digits: &[i64], z: i64) -> i64 {
+        match self {
+            Expr::Const(value) => *value,
+            Expr::Digit(index) => digits[*index],
+            Expr::Z => z,
+            Expr::Add(a, b) => a.evaluate(digits, z) + b.evaluate(digits, z),
+            Expr::Mul(a, b) => a.evaluate(digits, z) * b.evaluate(digits, z),
+            Expr::Div(a, b) => a.evaluate(digits, z) / b.evaluate(digits, z),
+            Expr::Mod(a, b) => a.evaluate(digits, z) % b.evaluate(digits, z),
+            Expr::Eql(a, b) => (a.evaluate(digits, z) == b.evaluate(digits, z)) as i64,
         }
-        Ok((remaining, _)) => {
-            println!("remaining unparsed \"{}\"", remaining);
-            0
+    }
+
+    fn precedence(&self) -> u8 {
+        match self {
+            Expr::Add(_, _) => 1,
+            Expr::Mul(_, _) | Expr::Div(_, _) | Expr::Mod(_, _) => 2,
+            _ => 3,
         }
-        Err(e) => {
-            println!("error parsing \"{}\"", e);
-            0
+    }
+
+    fn write_operand(&self, f: &mut fmt::Formatter, operand: &Expr, right: bool) -> fmt::Result {
+        let associative = matches!(self, Expr::Add(_, _) | Expr::Mul(_, _));
+        if operand.precedence() < self.precedence()
+            || (right && !associative && operand.precedence() == self.precedence())
+        {
+            write!(f, "({})", operand)
+        } else {
+            write!(f, "{}", operand)
         }
     }
 }
 
-fn input_to_int(input: &Vec<i64>) -> u64 {
-    let mut result: u64 = 0;
-    for digit in input {
-        result *= 10;
-        result += *digit as u64;
+// `z / 26 * ((z % 26 - 8 != d3) * 25 + 1)`, with comparisons always in brackets
+impl fmt::Display for Expr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let (a, b, operator) = match self {
+            Expr::Const(value) => return write!(f, "{}", value),
+            Expr::Digit(index) => return write!(f, "d{}", index),
+            Expr::Z => return write!(f, "z"),
+            Expr::Eql(a, b) => {
+                return match (&**a, &**b) {
+                    (Expr::Eql(a, b), Expr::Const(0)) => write!(f, "({} != {})", a, b),
+                    (a, b) => write!(f, "({} == {})", a, b),
+                };
+            }
+            Expr::Add(a, b) => match &**b {
+                Expr::Const(value) if *value < 0 => {
+                    self.write_operand(f, a, false)?;
+                    return write!(f, " - {}", -value);
+                }
+                _ => (a, b, "+"),
+            },
+            Expr::Mul(a, b) => (a, b, "*"),
+            Expr::Div(a, b) => (a, b, "/"),
+            Expr::Mod(a, b) => (a, b, "%"),
+        };
+
+        self.write_operand(f, a, false)?;
+        write!(f, " {} ", operator)?;
+        self.write_operand(f, b, true)
     }
+}
 
-    result
+// one way through the program run symbolically, with the outcomes of the comparisons it took
+// for granted to get there
+#[derive(Debug, Clone)]
+struct Path {
+    registers: [Expr; 4],
+    digits_read: usize,
+    assumptions: Vec<(Expr, Expr, bool)>,
 }
 
-fn next_input(input: &Vec<i64>) -> Option<Vec<i64>> {
-    let mut generated_input = input.to_owned();
+impl Path {
+    fn new(z: Expr, digits_read: usize) -> Self {
+        Path {
+            registers: [Expr::Const(0), Expr::Const(0), Expr::Const(0), z],
+            digits_read,
+            assumptions: vec![],
+        }
+    }
 
-    generated_input.reverse();
+    fn operand(&self, operand: Operand) -> Expr {
+        match operand {
+            Operand::Literal(value) => Expr::Const(value),
+            Operand::Variable(register) => self.registers[register.index()].clone(),
+        }
+    }
 
-    let mut rolled = false;
-    let mut done = false;
-    for value in generated_input.iter_mut() {
-        if rolled {
-            if *value > 1 {
-                *value -= 1;
-                done = true;
-                break;
-            } else {
-                *value = 9;
+    // applies one instruction. a comparison that depends on the digits is left in the register
+    // and handed back so the caller can decide whether to split on it
+    fn execute(&mut self, instruction: &Instruction) -> Option<Register> {
+        let (register, value) = match *instruction {
+            Instruction::Input(register) => {
+                self.digits_read += 1;
+                (register, Expr::Digit(self.digits_read - 1))
             }
-        } else if *value > 1 {
-            *value -= 1;
-            done = true;
-            break;
-        } else {
-            rolled = true;
-            *value = 9;
-        }
+            Instruction::Add(register, operand) => (
+                register,
+                Expr::sum(
+                    self.operand(Operand::Variable(register)),
+                    self.operand(operand),
+                ),
+            ),
+            Instruction::Multiply(register, operand) => (
+                register,
+                Expr::product(
+                    self.operand(Operand::Variable(register)),
+                    self.operand(operand),
+                ),
+            ),
+            Instruction::Divide(register, operand) => (
+                register,
+                Expr::quotient(
+                    self.operand(Operand::Variable(register)),
+                    self.operand(operand),
+                ),
+            ),
+            Instruction::Modulus(register, operand) => (
+                register,
+                Expr::remainder(
+                    self.operand(Operand::Variable(register)),
+                    self.operand(operand),
+                ),
+            ),
+            Instruction::Equals(register, operand) => (
+                register,
+                Expr::equals(
+                    self.operand(Operand::Variable(register)),
+                    self.operand(operand),
+                ),
+            ),
+        };
+
+        let undecided = matches!(value, Expr::Eql(_, _));
+        self.registers[register.index()] = value;
+        undecided.then_some(register)
     }
 
-    if done {
-        generated_input.reverse();
-        Some(generated_input)
-    } else {
-        None
+    // both outcomes of the comparison just left in `register`
+    fn split(self, register: Register) -> [Path; 2] {
+        let Expr::Eql(a, b) = &self.registers[register.index()] else {
+            unreachable!("only comparisons are split on");
+        };
+        let (a, b) = (*a.clone(), *b.clone());
+
+        [true, false].map(|equal| {
+            let mut path = self.clone();
+            path.registers[register.index()] = Expr::Const(equal as i64);
+            path.assumptions.push((a.clone(), b.clone(), equal));
+            path
+        })
     }
 }
 
-fn advance_input_when_z_is_zero(program: &Vec<Instruction>) {
-    //try piecing together the number one input at a time
-    //start with 9 and go down to 1 until the result is 0
-    //on each input, save the previous input to the input stack
-    //if z > 0, then rewind to the last input step
-    //if there are multiple "solutions" to a part, then we'll have to keep a set of possibilities per index
+// the blocks of the program, each starting at an `inp`
+fn split_by_input(program: &[Instruction]) -> Vec<&[Instruction]> {
+    let mut blocks = vec![];
+    let mut start = 0;
+    for (index, instruction) in program.iter().enumerate().skip(1) {
+        if let Instruction::Input(_) = instruction {
+            blocks.push(&program[start..index]);
+            start = index;
+        }
+    }
+    blocks.push(&program[start..]);
 
-    let mut guessed_input = vec![];
-    let mut accepted_program_len: usize = 0;
+    blocks
+}
 
-    let mut state;
+// z after each block in terms of the z it started with and the digit it read
+pub fn block_formulas(program: &[Instruction]) -> Vec<Expr> {
+    split_by_input(program)
+        .iter()
+        .enumerate()
+        .map(|(index, block)| {
+            let mut path = Path::new(Expr::Z, index);
+            for instruction in block.iter() {
+                path.execute(instruction);
+            }
+            path.registers[Register::Z.index()].clone()
+        })
+        .collect()
+}
 
-    let mut state_save_point = ALUState::new();
-    let mut this_guess: i64 = 10;
+// the program's listing with each block headed by what it does to z
+pub fn disassemble(program: &[Instruction]) -> String {
+    let mut lines = vec![];
+    for (index, (block, formula)) in split_by_input(program)
+        .iter()
+        .zip(block_formulas(program))
+        .enumerate()
+    {
+        lines.push(format!("; block {}: z = {}", index, formula));
+        lines.extend(block.iter().map(Instruction::to_string));
+    }
 
-    while guessed_input.len() < 14 {
-        state = state_save_point.clone();
-        this_guess -= 1;
+    lines.join("\n")
+}
 
-        for (instruction_read_so_far, instruction) in
-            program.iter().skip(accepted_program_len).enumerate()
-        {
-            if let Instruction::Input(_) = instruction {
-                if instruction_read_so_far > 0 {
-                    //then this isn't our first input
-                    if state.z == 0 {
-                        guessed_input.push(this_guess);
-                        state_save_point = state.clone();
-                        this_guess = 10;
-                        accepted_program_len += instruction_read_so_far - 1;
-                    }
-
-                    break;
-                }
-            }
+// `d{first} + offset == d{second}`, or `!=` when `equal` is false
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Constraint {
+    first: usize,
+    offset: i64,
+    second: usize,
+    equal: bool,
+}
 
-            state = state.execute(instruction, &mut vec![this_guess]);
-        }
+impl Constraint {
+    fn from_assumption(a: &Expr, b: &Expr, equal: bool) -> Option<Self> {
+        let offset_digit = |expr: &Expr| match expr {
+            Expr::Digit(index) => Some((*index, 0)),
+            Expr::Add(digit, offset) => match (&**digit, &**offset) {
+                (Expr::Digit(index), Expr::Const(offset)) => Some((*index, *offset)),
+                _ => None,
+            },
+            _ => None,
+        };
+
+        let ((first, a_offset), (second, b_offset)) = (offset_digit(a)?, offset_digit(b)?);
+        Some(Constraint {
+            first,
+            offset: a_offset - b_offset,
+            second,
+            equal,
+        })
+    }
 
-        if this_guess == 1 {
-            println!("failed with input at 1. would need to rewind input.");
-            return;
+    // whether the digits picked so far can still satisfy it
+    fn allows(&self, digits: &[Option<i64>]) -> bool {
+        match (digits[self.first], digits[self.second]) {
+            (Some(first), Some(second)) => (first + self.offset == second) == self.equal,
+            (Some(first), None) => !self.equal || (1..=9).contains(&(first + self.offset)),
+            (None, Some(second)) => !self.equal || (1..=9).contains(&(second - self.offset)),
+            (None, None) => true,
         }
     }
-
-    println!("succeeded with input {:?}", guessed_input);
 }
 
-fn split_by_input(program: &Vec<Instruction>) -> Vec<Vec<Instruction>> {
-    let mut grouped_by_instruction: Vec<Vec<Instruction>> = vec![];
-    let mut accepted_program_len: usize = 0;
-
-    while grouped_by_instruction.len() < 14 {
-        let mut this_group: Vec<Instruction> = vec![];
-
-        for (instruction_read_so_far, instruction) in
-            program.iter().skip(accepted_program_len).enumerate()
-        {
-            if let Instruction::Input(_) = instruction {
-                if instruction_read_so_far > 0 {
-                    //then this isn't our first input
-                    accepted_program_len += instruction_read_so_far;
-                    break;
-                }
+// runs the program from zero, splitting on every comparison that depends on the digits, and
+// keeps the constraints of each way through that finishes with z at 0
+fn accepting_constraints(program: &[Instruction]) -> Vec<Vec<Constraint>> {
+    let mut paths = vec![Path::new(Expr::Const(0), 0)];
+    for instruction in program {
+        paths = paths
+            .into_iter()
+            .flat_map(|mut path| match path.execute(instruction) {
+                Some(register) => path.split(register).to_vec(),
+                None => vec![path],
+            })
+            .collect();
+    }
+    debug!("{} ways through the program", paths.len());
+
+    paths
+        .into_iter()
+        .filter(|path| path.registers[Register::Z.index()] == Expr::Const(0))
+        .filter_map(|path| {
+            let constraints: Option<Vec<Constraint>> = path
+                .assumptions
+                .iter()
+                .map(|(a, b, equal)| Constraint::from_assumption(a, b, *equal))
+                .collect();
+            if constraints.is_none() {
+                warn!("skipping a way through with comparisons that aren't between two digits");
             }
+            constraints
+        })
+        .collect()
+}
 
-            this_group.push(instruction.to_owned());
+// fills in the digits first to last, trying `candidates` in order, so the first complete model
+// number found is the best one for that order
+fn pick_digits(
+    digits: &mut Vec<Option<i64>>,
+    constraints: &[Constraint],
+    candidates: &[i64],
+) -> bool {
+    let Some(next) = digits.iter().position(Option::is_none) else {
+        return true;
+    };
+
+    for &candidate in candidates {
+        digits[next] = Some(candidate);
+        if constraints
+            .iter()
+            .all(|constraint| constraint.allows(digits))
+            && pick_digits(digits, constraints, candidates)
+        {
+            return true;
         }
-
-        grouped_by_instruction.push(this_group);
     }
+    digits[next] = None;
 
-    grouped_by_instruction
+    false
 }
 
-fn run_program(program: &Vec<Instruction>, input: &Vec<i64>) -> Option<i64> {
-    let mut state = ALUState::new();
-
-    let mut input_stack = input.clone();
-    input_stack.reverse();
-
-    for (instruction_counter, instruction) in program.iter().enumerate() {
-        state = state.execute(instruction, &mut input_stack);
+fn model_number(program: &[Instruction], largest: bool) -> Answer {
+    for (index, formula) in block_formulas(program).iter().enumerate() {
+        debug!("block {}: z = {}", index, formula);
+    }
 
-        if state.z != 0 {
-            return None;
+    let digit_count = program
+        .iter()
+        .filter(|instruction| matches!(instruction, Instruction::Input(_)))
+        .count();
+    let candidates: Vec<i64> = if largest {
+        (1..=9).rev().collect()
+    } else {
+        (1..=9).collect()
+    };
+
+    let numbers = accepting_constraints(program)
+        .into_iter()
+        .filter_map(|constraints| {
+            trace!("constraints: {:?}", constraints);
+            let mut digits = vec![None; digit_count];
+            pick_digits(&mut digits, &constraints, &candidates)
+                .then(|| digits.into_iter().map(Option::unwrap).collect::<Vec<i64>>())
+        });
+    let best = if largest {
+        numbers.max()
+    } else {
+        numbers.min()
+    };
+
+    let Some(digits) = best else {
+        warn!("no model number is accepted");
+        return Answer::Unsolved;
+    };
+    debug!("found the digits: {:?}", digits);
+
+    match run(program, &digits) {
+        Ok(alu) if alu.read(Register::Z) == 0 => input_to_int(&digits).into(),
+        result => {
+            warn!("the program doesn't accept {:?}: {:?}", digits, result);
+            Answer::Unsolved
         }
     }
-
-    Some(state.z)
 }
 
-fn run_program_segment(
-    program: &Vec<Instruction>,
-    input: i64,
-    initial_state: ALUState,
-) -> ALUState {
-    let mut state = initial_state;
-
-    let mut input_stack = vec![input];
-
-    for instruction in program {
-        state = state.execute(instruction, &mut input_stack);
+fn input_to_int(input: &[i64]) -> u64 {
+    let mut result: u64 = 0;
+    for digit in input {
+        result *= 10;
+        result += *digit as u64;
     }
 
-    state
+    result
 }
 
-fn debug_program(program: &Vec<Instruction>, input: &Vec<i64>) -> Option<i64> {
-    let mut state = ALUState::new();
+pub struct Day24;
 
-    let mut input_stack = input.clone();
-    input_stack.reverse();
+impl Solution for Day24 {
+    type Input<'a> = Vec<Instruction>;
 
-    state.print();
+    type Params = ();
 
-    for instruction in program {
-        println!("{:?}", instruction);
-        state = state.execute(instruction, &mut input_stack);
-        state.print();
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse_all(input, input_parser)
     }
 
-    Some(state.z)
-}
-
-fn parse_number(input: &str) -> IResult<&str, i64> {
-    let (input, maybe_negative) = nom::combinator::opt(tag("-"))(input)?;
-    let (input, val) =
-        map_res(nom::character::complete::digit1, |s: &str| s.parse::<i64>())(input)?;
+    fn part1(monad_program: &Self::Input<'_>, _params: &Self::Params) -> Answer {
+        model_number(monad_program, true)
+    }
 
-    Ok((input, if maybe_negative.is_some() { -val } else { val }))
+    fn part2(monad_program: &Self::Input<'_>, _params: &Self::Params) -> Answer {
+        model_number(monad_program, false)
+    }
 }
 
-fn register_parser(input: &str) -> IResult<&str, Register> {
-    let (input, register) = nom::character::complete::alpha1(input)?;
+fn register_parser(start: &str) -> IResult<&str, Register> {
+    let (input, register) = nom::character::complete::alpha1(start)?;
 
     match register {
         "w" => Ok((input, Register::W)),
         "x" => Ok((input, Register::X)),
         "y" => Ok((input, Register::Y)),
         "z" => Ok((input, Register::Z)),
-        _ => panic!("Invalid register"),
+        _ => Err(nom::Err::Error(Error::new(start, ErrorKind::Alpha))),
     }
 }
 
@@ -559,8 +699,8 @@ fn operand_parser(input: &str) -> IResult<&str, Operand> {
     nom::branch::alt((literal_parser, register_operand_parser))(input)
 }
 
-fn instruction_parser(input: &str) -> IResult<&str, Instruction> {
-    let (input, operator) = nom::character::complete::alpha1(input)?;
+fn instruction_parser(start: &str) -> IResult<&str, Instruction> {
+    let (input, operator) = nom::character::complete::alpha1(start)?;
     let (input, _) = tag(" ")(input)?;
 
     match operator {
@@ -598,7 +738,7 @@ fn instruction_parser(input: &str) -> IResult<&str, Instruction> {
             let (input, operand) = operand_parser(input)?;
             Ok((input, Instruction::Equals(register, operand)))
         }
-        _ => panic!("invalid operator"),
+        _ => Err(nom::Err::Error(Error::new(start, ErrorKind::Tag))),
     }
 }
 
@@ -613,20 +753,122 @@ fn input_parser(input: &str) -> IResult<&str, Vec<Instruction>> {
 
 #[cfg(test)]
 mod day24_tests {
-    use crate::day24::ALUState;
-    use crate::day24::Instruction;
-    use crate::day24::Operand;
-    use crate::day24::Register;
+    use crate::day24::{
+        block_formulas, disassemble, run, AluError, Day24, Instruction, Operand, Register,
+    };
+    use aoc_common::solution::{Answer, Solution};
+
+    // a program shaped like the puzzle's, one block per (divide z by, add to x, add to y)
+    fn monad(blocks: &[(i64, i64, i64)]) -> String {
+        blocks
+            .iter()
+            .map(|(divide, add_x, add_y)| {
+                format!(
+                    "inp w\nmul x 0\nadd x z\nmod x 26\ndiv z {}\nadd x {}\neql x w\neql x 0\n\
+                     mul y 0\nadd y 25\nmul y x\nadd y 1\nmul z y\nmul y 0\nadd y w\nadd y {}\n\
+                     mul y x\nadd z y\n",
+                    divide, add_x, add_y
+                )
+            })
+            .collect()
+    }
+
+    const BLOCKS: [(i64, i64, i64); 14] = [
+        (1, 11, 6),
+        (1, 13, 14),
+        (1, 15, 14),
+        (26, -8, 10),
+        (1, 13, 9),
+        (1, 15, 12),
+        (26, -11, 8),
+        (26, -4, 13),
+        (26, -15, 12),
+        (1, 14, 6),
+        (1, 14, 9),
+        (26, -1, 15),
+        (26, -8, 4),
+        (26, -14, 10),
+    ];
+
+    fn digits(number: u64) -> Vec<i64> {
+        number
+            .to_string()
+            .chars()
+            .map(|c| c.to_digit(10).unwrap() as i64)
+            .collect()
+    }
 
     #[test]
     fn test_divide() {
-        let mut input = vec![-5];
-        let negative_divide = ALUState::new()
-            .execute(&Instruction::Input(Register::W), &mut input)
-            .execute(
-                &Instruction::Divide(Register::W, Operand::Literal(2)),
-                &mut input,
-            );
-        assert_eq!(negative_divide.w, -2);
+        let program = [
+            Instruction::Input(Register::W),
+            Instruction::Divide(Register::W, Operand::Literal(2)),
+        ];
+        assert_eq!(run(&program, &[-5]).unwrap().read(Register::W), -2);
+    }
+
+    #[test]
+    fn the_alu_can_crash() {
+        let program = [
+            Instruction::Input(Register::W),
+            Instruction::Modulus(Register::W, Operand::Variable(Register::X)),
+        ];
+        assert_eq!(run(&program, &[]), Err(AluError::OutOfInput));
+        assert_eq!(run(&program, &[3]), Err(AluError::InvalidModulus));
+
+        let divide = [Instruction::Divide(
+            Register::Z,
+            Operand::Variable(Register::W),
+        )];
+        assert_eq!(run(&divide, &[]), Err(AluError::DivideByZero));
+    }
+
+    #[test]
+    fn disassembles_back_to_the_source() {
+        let source = monad(&BLOCKS[..2]);
+        let program = Day24::parse(&source).unwrap();
+
+        let listing = disassemble(&program);
+        let code: Vec<&str> = listing
+            .lines()
+            .filter(|line| !line.starts_with(';'))
+            .collect();
+        assert_eq!(code, source.lines().collect::<Vec<&str>>());
+        assert!(listing.starts_with("; block 0: z = z * 26 + d0 + 6\ninp w\n"));
+        assert!(Day24::parse("jmp w 2\n").is_err());
+    }
+
+    #[test]
+    fn block_formulas_match_the_interpreter() {
+        let program = Day24::parse(&monad(&BLOCKS)).unwrap();
+        let formulas = block_formulas(&program);
+        assert_eq!(
+            formulas[3].to_string(),
+            "z / 26 * ((z % 26 - 8 != d3) * 25 + 1) + (d3 + 10) * (z % 26 - 8 != d3)"
+        );
+
+        let input = digits(13579246813579);
+        let mut z = 0;
+        for (index, formula) in formulas.iter().enumerate() {
+            z = formula.evaluate(&input, z);
+            let alu = run(&program[..(index + 1) * 18], &input[..=index]).unwrap();
+            assert_eq!(alu.read(Register::Z), z);
+        }
+        assert_ne!(z, 0);
+    }
+
+    #[test]
+    fn solves_for_the_largest_and_smallest_model_numbers() {
+        let program = Day24::parse(&monad(&BLOCKS)).unwrap();
+
+        assert_eq!(Day24::part1(&program, &()), Answer::Integer(99394899891971));
+        assert_eq!(Day24::part2(&program, &()), Answer::Integer(92171126131911));
+
+        for number in [99394899891971, 92171126131911] {
+            let alu = run(&program, &digits(number)).unwrap();
+            assert_eq!(alu.read(Register::Z), 0);
+        }
+        let alu = run(&program, &digits(99394899891972)).unwrap();
+        assert_ne!(alu.read(Register::Z), 0);
     }
 }
@@ -1,22 +1,18 @@
-use itertools::Itertools;
-use nom::{
-    bytes::complete::tag, combinator::map_res, multi::separated_list1, sequence::separated_pair,
-    IResult,
-};
-use std::{
-    collections::{HashMap, HashSet},
-    fmt,
-    hash::Hash,
-};
-
-#[derive(Copy, Clone)]
-enum Spot {
+use aoc_common::cycle::run_until_stable;
+use aoc_common::grid::Grid;
+use aoc_common::parse::ParseError;
+use aoc_common::solution::{Answer, Solution};
+use aoc_common::trace;
+use std::fmt;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Spot {
     EastMoving,
     SouthMoving,
     Empty,
 }
 
-impl fmt::Debug for Spot {
+impl fmt::Display for Spot {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
             Spot::Empty => {
@@ -32,184 +28,84 @@ impl fmt::Debug for Spot {
     }
 }
 
-#[derive(Clone)]
-struct FloorMap {
-    x_len: usize,
-    y_len: usize,
-    map: HashMap<(usize, usize), Spot>,
-}
+// sea cucumbers leaving one edge come back in on the opposite one
+type FloorMap = Grid<Spot>;
 
-impl FloorMap {
-    fn from_lines(lines: Vec<Vec<Spot>>) -> Self {
-        let mut map: HashMap<(usize, usize), Spot> = HashMap::new();
+pub struct Day25;
 
-        for (y, line) in lines.iter().enumerate() {
-            for (x, value) in line.iter().enumerate() {
-                map.insert((x, y), *value);
-            }
-        }
+impl Solution for Day25 {
+    type Input<'a> = FloorMap;
 
-        FloorMap {
-            x_len: lines[0].len(),
-            y_len: lines.len(),
-            map,
-        }
-    }
+    type Params = ();
 
-    fn with_maximums(map: &FloorMap) -> Self {
-        FloorMap {
-            x_len: map.x_len,
-            y_len: map.y_len,
-            map: HashMap::new(),
-        }
-    }
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        let floor_map = Grid::parse(input, "'.', '>' or 'v'", |c| match c {
+            '.' => Some(Spot::Empty),
+            '>' => Some(Spot::EastMoving),
+            'v' => Some(Spot::SouthMoving),
+            _ => None,
+        })?;
 
-    fn is_occupied(&self, location: &(usize, usize)) -> bool {
-        match self.map.get(location) {
-            None => false,
-            Some(Spot::Empty) => false,
-            Some(Spot::SouthMoving) => true,
-            Some(Spot::EastMoving) => true,
-        }
+        Ok(floor_map.wrapping())
     }
 
-    fn print(&self) {
-        for y in 0..self.y_len {
-            for x in 0..self.x_len {
-                print!("{:?}", self.map.get(&(x, y)).unwrap_or(&Spot::Empty))
-            }
-            println!();
-        }
-        println!();
-    }
-}
-
-pub fn part1() -> usize {
-    let input = include_str!("../../data/2021/25.txt");
-
-    match input_parser(input) {
-        Ok((remaining_input, initial_floor_map)) if remaining_input.is_empty() => {
-            // println!("parsed entire input");
-
-            initial_floor_map.print();
-
-            let mut floor_map: FloorMap = initial_floor_map;
-            let mut counter: usize = 0;
-            loop {
-                let (next_floor_map, changed) = step(&floor_map);
-                counter += 1;
-
-                if !changed {
-                    return counter;
-                }
+    fn part1(initial_floor_map: &Self::Input<'_>, _params: &Self::Params) -> Answer {
+        trace!("initial floor:\n{}", initial_floor_map);
 
-                floor_map = next_floor_map;
+        let (rounds, _) =
+            run_until_stable(initial_floor_map.clone(), |floor_map, _| step(floor_map));
 
-                // println!("after {}, {}", counter, changed);
-                // floor_map.print();
-            }
-        }
-        Ok((remaining, _)) => {
-            println!("remaining unparsed \"{}\"", remaining);
-            0
-        }
-        Err(e) => {
-            println!("error parsing \"{}\"", e);
-            0
-        }
+        rounds.into()
     }
-}
-
-pub fn part2() -> usize {
-    let input = include_str!("../../data/2021/25-test.txt");
 
-    match input_parser(input) {
-        Ok((remaining_input, floor_map)) if remaining_input.is_empty() => {
-            println!("parsed entire input");
-
-            0
-        }
-        Ok((remaining, _)) => {
-            println!("remaining unparsed \"{}\"", remaining);
-            0
-        }
-        Err(e) => {
-            println!("error parsing \"{}\"", e);
-            0
-        }
+    fn part2(_floor_map: &Self::Input<'_>, _params: &Self::Params) -> Answer {
+        Answer::Unsolved
     }
 }
 
-fn step(floor_map: &FloorMap) -> (FloorMap, bool) {
-    let mut after_east_move: FloorMap = FloorMap::with_maximums(floor_map);
-    let mut changed = false;
+fn step(floor_map: &FloorMap) -> FloorMap {
+    let mut after_east_move = floor_map.clone();
 
-    for (&(x, y), &spot) in floor_map.map.iter() {
-        match spot {
-            Spot::EastMoving => {
-                let next_x = (x + 1) % floor_map.x_len;
-                if floor_map.is_occupied(&(next_x, y)) {
-                    after_east_move.map.insert((x, y), spot);
-                } else {
-                    after_east_move.map.insert((next_x, y), spot);
-                    changed = true;
-                }
-            }
-            Spot::SouthMoving => {
-                after_east_move.map.insert((x, y), spot);
-            }
-            Spot::Empty => {
-                //do nothing
-            }
+    for ((x, y), &spot) in floor_map.iter() {
+        if spot == Spot::EastMoving && floor_map[(x + 1, y)] == Spot::Empty {
+            after_east_move[(x, y)] = Spot::Empty;
+            after_east_move[(x + 1, y)] = spot;
         }
     }
 
-    let mut result: FloorMap = FloorMap::with_maximums(floor_map);
+    let mut result = after_east_move.clone();
 
-    for (&(x, y), &spot) in after_east_move.map.iter() {
-        match spot {
-            Spot::EastMoving => {
-                result.map.insert((x, y), spot);
-            }
-            Spot::SouthMoving => {
-                let next_y = (y + 1) % after_east_move.y_len;
-                if after_east_move.is_occupied(&(x, next_y)) || result.is_occupied(&(x, next_y)) {
-                    result.map.insert((x, y), spot);
-                } else {
-                    result.map.insert((x, next_y), spot);
-                    changed = true;
-                }
-            }
-            Spot::Empty => {
-                //do nothing
-            }
+    for ((x, y), &spot) in after_east_move.iter() {
+        if spot == Spot::SouthMoving && after_east_move[(x, y + 1)] == Spot::Empty {
+            result[(x, y)] = Spot::Empty;
+            result[(x, y + 1)] = spot;
         }
     }
 
-    (result, changed)
+    result
 }
 
-fn line_parser(input: &str) -> IResult<&str, Vec<Spot>> {
-    let (input, line) = nom::character::complete::not_line_ending(input)?;
-    let spots = line
-        .chars()
-        .filter_map(|c| match c {
-            '.' => Some(Spot::Empty),
-            '>' => Some(Spot::EastMoving),
-            'v' => Some(Spot::SouthMoving),
-            _ => None,
-        })
-        .collect_vec();
-
-    Ok((input, spots))
-}
-
-fn input_parser(input: &str) -> IResult<&str, FloorMap> {
-    let (input, lines) = separated_list1(nom::character::complete::newline, line_parser)(input)?;
-
-    // let (input, _) = nom::character::complete::newline(input)?;
-
-    let floor_map = FloorMap::from_lines(lines.into_iter().dropping_back(1).collect_vec());
-
-    Ok((input, floor_map))
+#[cfg(test)]
+mod day25_tests {
+    use crate::day25::Day25;
+    use aoc_common::solution::{solve, Answer};
+
+    const EXAMPLE: &str = "v...>>.vv>
+.vv>>.vv..
+>>.>v>...v
+>>v>>.>.v.
+v>v.vv.v..
+>.>>..v...
+.vv..>.>v.
+v.v..>>v.v
+....v..v.>
+";
+
+    #[test]
+    fn example() {
+        assert_eq!(
+            solve::<Day25>(EXAMPLE, &[1, 2], &()).unwrap(),
+            [Answer::Integer(58), Answer::Unsolved]
+        );
+    }
 }
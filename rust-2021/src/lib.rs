@@ -0,0 +1,57 @@
+pub mod day01;
+pub mod day02;
+pub mod day03;
+pub mod day04;
+pub mod day05;
+pub mod day06;
+pub mod day07;
+pub mod day08;
+pub mod day09;
+pub mod day10;
+pub mod day11;
+pub mod day12;
+pub mod day13;
+pub mod day14;
+pub mod day15;
+pub mod day16;
+pub mod day17;
+pub mod day18;
+pub mod day19;
+pub mod day20;
+pub mod day21;
+pub mod day22;
+pub mod day23;
+pub mod day24;
+pub mod day25;
+
+use aoc_common::solution::Day;
+
+pub const YEAR: u32 = 2021;
+
+pub const DAYS: [(u32, Day); 25] = [
+    (1, Day::of::<day01::Day01>()),
+    (2, Day::of::<day02::Day02>()),
+    (3, Day::of::<day03::Day03>()),
+    (4, Day::of::<day04::Day04>()),
+    (5, Day::of::<day05::Day05>()),
+    (6, Day::of::<day06::Day06>()),
+    (7, Day::of::<day07::Day07>()),
+    (8, Day::of::<day08::Day08>()),
+    (9, Day::of::<day09::Day09>()),
+    (10, Day::of::<day10::Day10>()),
+    (11, Day::of::<day11::Day11>()),
+    (12, Day::of::<day12::Day12>()),
+    (13, Day::of::<day13::Day13>()),
+    (14, Day::of::<day14::Day14>()),
+    (15, Day::of::<day15::Day15>()),
+    (16, Day::of::<day16::Day16>()),
+    (17, Day::of::<day17::Day17>()),
+    (18, Day::of::<day18::Day18>()),
+    (19, Day::of::<day19::Day19>()),
+    (20, Day::of::<day20::Day20>()),
+    (21, Day::of::<day21::Day21>()),
+    (22, Day::of::<day22::Day22>()),
+    (23, Day::of::<day23::Day23>()),
+    (24, Day::of::<day24::Day24>()),
+    (25, Day::of::<day25::Day25>()),
+];
@@ -1,51 +1,37 @@
+use aoc_common::parse::{parse_all, ParseError};
+use aoc_common::solution::{Answer, Solution};
 use itertools::Itertools;
 use nom::{combinator::map_res, multi::separated_list0, IResult};
 
-pub fn part1() -> usize {
-    let input = include_str!("../../data/2022/01.txt");
-
-    match input_parser(input) {
-        Ok((remaining_input, elves)) if remaining_input.is_empty() => {
-            println!("parsed entire input");
-            elves
-                .iter()
-                .max_by(|&x, &y| x.iter().sum::<usize>().cmp(&(y.iter().sum::<usize>())))
-                .unwrap_or(&vec![])
-                .iter()
-                .sum::<usize>()
-        }
-        Ok((remaining, _)) => {
-            println!("remaining unparsed \"{}\"", remaining);
-            0
-        }
-        Err(e) => {
-            println!("error parsing \"{}\"", e);
-            0
-        }
+pub struct Day01;
+
+impl Solution for Day01 {
+    type Input<'a> = Vec<Vec<usize>>;
+
+    type Params = ();
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse_all(input, input_parser)
     }
-}
 
-pub fn part2() -> usize {
-    let input = include_str!("../../data/2022/01.txt");
-
-    match input_parser(input) {
-        Ok((remaining_input, elves)) if remaining_input.is_empty() => {
-            println!("parsed entire input");
-            elves
-                .iter()
-                .sorted_by(|&x, &y| y.iter().sum::<usize>().cmp(&(x.iter().sum::<usize>())))
-                .take(3)
-                .map(|elf| elf.iter().sum::<usize>())
-                .sum::<usize>()
-        }
-        Ok((remaining, _)) => {
-            println!("remaining unparsed \"{}\"", remaining);
-            0
-        }
-        Err(e) => {
-            println!("error parsing \"{}\"", e);
-            0
-        }
+    fn part1(elves: &Self::Input<'_>, _params: &Self::Params) -> Answer {
+        elves
+            .iter()
+            .max_by(|&x, &y| x.iter().sum::<usize>().cmp(&(y.iter().sum::<usize>())))
+            .unwrap_or(&vec![])
+            .iter()
+            .sum::<usize>()
+            .into()
+    }
+
+    fn part2(elves: &Self::Input<'_>, _params: &Self::Params) -> Answer {
+        elves
+            .iter()
+            .sorted_by(|&x, &y| y.iter().sum::<usize>().cmp(&(x.iter().sum::<usize>())))
+            .take(3)
+            .map(|elf| elf.iter().sum::<usize>())
+            .sum::<usize>()
+            .into()
     }
 }
 
@@ -68,3 +54,33 @@ fn input_parser(input: &str) -> IResult<&str, Vec<Vec<usize>>> {
 
     Ok((input, lines))
 }
+
+#[cfg(test)]
+mod day01_tests {
+    use crate::day01::Day01;
+    use aoc_common::solution::{solve, Answer};
+
+    const EXAMPLE: &str = "1000
+2000
+3000
+
+4000
+
+5000
+6000
+
+7000
+8000
+9000
+
+10000
+";
+
+    #[test]
+    fn example() {
+        assert_eq!(
+            solve::<Day01>(EXAMPLE, &[1, 2], &()).unwrap(),
+            [Answer::Integer(24000), Answer::Integer(45000)]
+        );
+    }
+}
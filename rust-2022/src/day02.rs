@@ -1,63 +1,51 @@
-use itertools::Itertools;
+use aoc_common::parse::{parse_all, ParseError};
+use aoc_common::solution::{Answer, Solution};
 use nom::{
     branch::alt, bytes::complete::tag, combinator::map_res, multi::separated_list0, IResult,
 };
 
 #[derive(Copy, Clone)]
-enum Shape {
+pub enum Shape {
     Rock,
     Paper,
     Scissors,
 }
 
 #[derive(Copy, Clone)]
-enum RoundResult {
+pub enum RoundResult {
     Loss,
     Win,
     Draw,
 }
 
-pub fn part1() -> usize {
-    let input = include_str!("../../data/2022/02.txt");
-
-    match part1_input_parser(input) {
-        Ok((remaining_input, rounds)) if remaining_input.is_empty() => {
-            println!("parsed entire input");
-            rounds.iter().map(score_round).sum::<usize>()
-        }
-        Ok((remaining, _)) => {
-            println!("remaining unparsed \"{}\"", remaining);
-            0
-        }
-        Err(e) => {
-            println!("error parsing \"{}\"", e);
-            0
-        }
+pub struct Day02;
+
+impl Solution for Day02 {
+    type Input<'a> = (Vec<(Shape, Shape)>, Vec<(Shape, RoundResult)>);
+
+    type Params = ();
+
+    // the second column means something different in each part, so read it both ways
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        Ok((
+            parse_all(input, part1_input_parser)?,
+            parse_all(input, part2_input_parser)?,
+        ))
     }
-}
 
-pub fn part2() -> usize {
-    let input = include_str!("../../data/2022/02.txt");
-
-    match part2_input_parser(input) {
-        Ok((remaining_input, rounds)) if remaining_input.is_empty() => {
-            println!("parsed entire input");
-            rounds
-                .iter()
-                .map(|&(opponent, result)| -> (Shape, Shape) {
-                    (opponent, select_reaction(&(opponent, result)))
-                })
-                .map(|(opponent, reaction)| score_round(&(opponent, reaction)))
-                .sum::<usize>()
-        }
-        Ok((remaining, _)) => {
-            println!("remaining unparsed \"{}\"", remaining);
-            0
-        }
-        Err(e) => {
-            println!("error parsing \"{}\"", e);
-            0
-        }
+    fn part1((rounds, _): &Self::Input<'_>, _params: &Self::Params) -> Answer {
+        rounds.iter().map(score_round).sum::<usize>().into()
+    }
+
+    fn part2((_, rounds): &Self::Input<'_>, _params: &Self::Params) -> Answer {
+        rounds
+            .iter()
+            .map(|&(opponent, result)| -> (Shape, Shape) {
+                (opponent, select_reaction(&(opponent, result)))
+            })
+            .map(|(opponent, reaction)| score_round(&(opponent, reaction)))
+            .sum::<usize>()
+            .into()
     }
 }
 
@@ -184,3 +172,22 @@ fn part2_input_parser(input: &str) -> IResult<&str, Vec<(Shape, RoundResult)>> {
 
     Ok((input, lines))
 }
+
+#[cfg(test)]
+mod day02_tests {
+    use crate::day02::Day02;
+    use aoc_common::solution::{solve, Answer};
+
+    const EXAMPLE: &str = "A Y
+B X
+C Z
+";
+
+    #[test]
+    fn example() {
+        assert_eq!(
+            solve::<Day02>(EXAMPLE, &[1, 2], &()).unwrap(),
+            [Answer::Integer(15), Answer::Integer(12)]
+        );
+    }
+}
@@ -1,77 +1,60 @@
+use aoc_common::parse::{parse_all, ParseError};
+use aoc_common::solution::{Answer, Solution};
 use itertools::Itertools;
-use nom::{combinator::map_res, multi::many1, IResult};
-use std::{collections::HashSet, hash::Hash};
+use nom::{multi::separated_list1, IResult};
+use std::collections::HashSet;
 
-fn intersection<T: Eq + Hash>(a: HashSet<T>, b: &HashSet<T>) -> HashSet<T> {
-    a.into_iter().filter(|e| b.contains(e)).collect()
-}
+type Rucksack = (HashSet<char>, HashSet<char>);
 
-pub fn part1() -> u32 {
-    let input = include_str!("../../data/2022/03.txt");
-
-    match input_parser(input) {
-        Ok((remaining_input, rucksacks)) if remaining_input.is_empty() => {
-            println!("parsed entire input");
-            rucksacks
-                .iter()
-                .map(|rucksack| {
-                    rucksack
-                        .0
-                        .intersection(&rucksack.1)
-                        .map(value_of_item)
-                        .sum::<u32>()
-                })
-                .sum()
-        }
-        Ok((remaining, _)) => {
-            println!("remaining unparsed \"{}\"", remaining);
-            0
-        }
-        Err(e) => {
-            println!("error parsing \"{}\"", e);
-            0
-        }
+pub struct Day03;
+
+impl Solution for Day03 {
+    type Input<'a> = Vec<Rucksack>;
+
+    type Params = ();
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse_all(input, input_parser)
     }
-}
 
-pub fn part2() -> u32 {
-    let input = include_str!("../../data/2022/03.txt");
-
-    match input_parser(input) {
-        Ok((remaining_input, rucksacks)) if remaining_input.is_empty() => {
-            println!("parsed entire input");
-            let groups = rucksacks.chunks(3).collect_vec();
-
-            let mut total_score: u32 = 0;
-
-            for group in groups {
-                let mut group_intersection = HashSet::new();
-                group.into_iter().for_each(|sack| {
-                    let mut entire_sack = HashSet::new();
-                    entire_sack.extend(sack.0.iter());
-                    entire_sack.extend(sack.1.iter());
-
-                    if group_intersection.is_empty() {
-                        group_intersection = entire_sack.clone()
-                    } else {
-                        group_intersection.retain(|item| entire_sack.contains(item))
-                    }
-                });
-
-                let group_badge = group_intersection.into_iter().next().unwrap();
-                let group_score = value_of_item(&group_badge);
-                total_score += group_score
-            }
-            total_score
-        }
-        Ok((remaining, _)) => {
-            println!("remaining unparsed \"{}\"", remaining);
-            0
-        }
-        Err(e) => {
-            println!("error parsing \"{}\"", e);
-            0
+    fn part1(rucksacks: &Self::Input<'_>, _params: &Self::Params) -> Answer {
+        rucksacks
+            .iter()
+            .map(|rucksack| {
+                rucksack
+                    .0
+                    .intersection(&rucksack.1)
+                    .map(value_of_item)
+                    .sum::<u32>()
+            })
+            .sum::<u32>()
+            .into()
+    }
+
+    fn part2(rucksacks: &Self::Input<'_>, _params: &Self::Params) -> Answer {
+        let groups = rucksacks.chunks(3).collect_vec();
+
+        let mut total_score: u32 = 0;
+
+        for group in groups {
+            let mut group_intersection = HashSet::new();
+            group.iter().for_each(|sack| {
+                let mut entire_sack = HashSet::new();
+                entire_sack.extend(sack.0.iter());
+                entire_sack.extend(sack.1.iter());
+
+                if group_intersection.is_empty() {
+                    group_intersection = entire_sack.clone()
+                } else {
+                    group_intersection.retain(|item| entire_sack.contains(item))
+                }
+            });
+
+            let group_badge = group_intersection.into_iter().next().unwrap();
+            let group_score = value_of_item(&group_badge);
+            total_score += group_score
         }
+        total_score.into()
     }
 }
 
@@ -85,7 +68,6 @@ fn value_of_item(&item: &char) -> u32 {
 
 fn rucksack_parser(input: &str) -> IResult<&str, (HashSet<char>, HashSet<char>)> {
     let (input, items) = nom::character::complete::alpha1(input)?;
-    let (input, _) = nom::character::complete::newline(input)?;
     let per_compartment = items.len() / 2;
     let left = HashSet::from_iter(items.chars().take(per_compartment));
     let right = HashSet::from_iter(items.chars().skip(per_compartment));
@@ -94,8 +76,31 @@ fn rucksack_parser(input: &str) -> IResult<&str, (HashSet<char>, HashSet<char>)>
     Ok((input, (left, right)))
 }
 
-fn input_parser(input: &str) -> IResult<&str, Vec<(HashSet<char>, HashSet<char>)>> {
-    let (input, lines) = many1(rucksack_parser)(input)?;
+fn input_parser(input: &str) -> IResult<&str, Vec<Rucksack>> {
+    let (input, lines) =
+        separated_list1(nom::character::complete::newline, rucksack_parser)(input)?;
 
     Ok((input, lines))
 }
+
+#[cfg(test)]
+mod day03_tests {
+    use crate::day03::Day03;
+    use aoc_common::solution::{solve, Answer};
+
+    const EXAMPLE: &str = "vJrwpWtwJgWrhcsFMMfFFhFp
+jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL
+PmmdzqPrVvPwwTWBwg
+wMqvLMZHhHMvwLHjbvcjnnSBnvTQFn
+ttgJtRGJQctTZtZT
+CrZsJsPPZsGzwwsLwLmpwMDw
+";
+
+    #[test]
+    fn example() {
+        assert_eq!(
+            solve::<Day03>(EXAMPLE, &[1, 2], &()).unwrap(),
+            [Answer::Integer(157), Answer::Integer(70)]
+        );
+    }
+}
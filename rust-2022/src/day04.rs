@@ -1,47 +1,35 @@
-use itertools::Itertools;
+use aoc_common::parse::{parse_all, ParseError};
+use aoc_common::solution::{Answer, Solution};
 use nom::{combinator::map_res, multi::separated_list0, IResult};
 
 type SectionRange = (usize, usize);
 type ElfPair = (SectionRange, SectionRange);
 
-pub fn part1() -> usize {
-    let input = include_str!("../../data/2022/04.txt");
+pub struct Day04;
 
-    match input_parser(input) {
-        Ok((remaining_input, pairings)) if remaining_input.is_empty() => {
-            println!("parsed entire input");
-            pairings
-                .iter()
-                .filter(|&pair| fully_overlapping(pair))
-                .count()
-        }
-        Ok((remaining, _)) => {
-            println!("remaining unparsed \"{}\"", remaining);
-            0
-        }
-        Err(e) => {
-            println!("error parsing \"{}\"", e);
-            0
-        }
+impl Solution for Day04 {
+    type Input<'a> = Vec<ElfPair>;
+
+    type Params = ();
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse_all(input, input_parser)
     }
-}
 
-pub fn part2() -> usize {
-    let input = include_str!("../../data/2022/04.txt");
+    fn part1(pairings: &Self::Input<'_>, _params: &Self::Params) -> Answer {
+        pairings
+            .iter()
+            .filter(|&pair| fully_overlapping(pair))
+            .count()
+            .into()
+    }
 
-    match input_parser(input) {
-        Ok((remaining_input, pairings)) if remaining_input.is_empty() => {
-            println!("parsed entire input");
-            pairings.iter().filter(|&pair| overlapping(pair)).count()
-        }
-        Ok((remaining, _)) => {
-            println!("remaining unparsed \"{}\"", remaining);
-            0
-        }
-        Err(e) => {
-            println!("error parsing \"{}\"", e);
-            0
-        }
+    fn part2(pairings: &Self::Input<'_>, _params: &Self::Params) -> Answer {
+        pairings
+            .iter()
+            .filter(|&pair| overlapping(pair))
+            .count()
+            .into()
     }
 }
 
@@ -101,3 +89,25 @@ fn input_parser(input: &str) -> IResult<&str, Vec<ElfPair>> {
 
     Ok((input, lines))
 }
+
+#[cfg(test)]
+mod day04_tests {
+    use crate::day04::Day04;
+    use aoc_common::solution::{solve, Answer};
+
+    const EXAMPLE: &str = "2-4,6-8
+2-3,4-5
+5-7,7-9
+2-8,3-7
+6-6,4-6
+2-6,4-8
+";
+
+    #[test]
+    fn example() {
+        assert_eq!(
+            solve::<Day04>(EXAMPLE, &[1, 2], &()).unwrap(),
+            [Answer::Integer(2), Answer::Integer(4)]
+        );
+    }
+}
@@ -1,69 +1,51 @@
+use aoc_common::parse::{parse_all, ParseError};
+use aoc_common::solution::{Answer, Solution};
 use std::collections::VecDeque;
 
-use itertools::Itertools;
-use nom::{
-    bytes::complete::tag, combinator::map_res, multi::separated_list0, multi::separated_list1,
-    IResult,
-};
+use nom::{bytes::complete::tag, combinator::map_res, multi::separated_list1, IResult};
 
 type Input = (Vec<VecDeque<char>>, Vec<MoveInstruction>);
 
 #[derive(Debug, PartialEq)]
-struct MoveInstruction {
+pub struct MoveInstruction {
     from: usize,
     to: usize,
     count: usize,
 }
 
-pub fn part1() -> usize {
-    let input = include_str!("../../data/2022/05.txt");
+pub struct Day05;
 
-    match input_parser(input) {
-        Ok((remaining_input, (mut stacks, instructions))) if remaining_input.is_empty() => {
-            println!("parsed entire input");
-            for instruction in instructions {
-                perform_move_as_9000(&mut stacks, &instruction);
-            }
-            for mut stack in stacks {
-                print!("{}", stack.pop_front().unwrap())
-            }
-            println!();
-            0
-        }
-        Ok((remaining, _)) => {
-            println!("remaining unparsed \"{}\"", remaining);
-            0
-        }
-        Err(e) => {
-            println!("error parsing \"{}\"", e);
-            0
-        }
-    }
-}
+impl Solution for Day05 {
+    type Input<'a> = Input;
 
-pub fn part2() -> usize {
-    let input = include_str!("../../data/2022/05.txt");
+    type Params = ();
 
-    match input_parser(input) {
-        Ok((remaining_input, (mut stacks, instructions))) if remaining_input.is_empty() => {
-            println!("parsed entire input");
-            for instruction in instructions {
-                perform_move_as_9001(&mut stacks, &instruction);
-            }
-            for mut stack in stacks {
-                print!("{}", stack.pop_front().unwrap())
-            }
-            println!();
-            0
-        }
-        Ok((remaining, _)) => {
-            println!("remaining unparsed \"{}\"", remaining);
-            0
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse_all(input, input_parser)
+    }
+
+    fn part1((stacks, instructions): &Self::Input<'_>, _params: &Self::Params) -> Answer {
+        let mut stacks = stacks.clone();
+        for instruction in instructions {
+            perform_move_as_9000(&mut stacks, instruction);
         }
-        Err(e) => {
-            println!("error parsing \"{}\"", e);
-            0
+        stacks
+            .iter()
+            .map(|stack| stack.front().unwrap())
+            .collect::<String>()
+            .into()
+    }
+
+    fn part2((stacks, instructions): &Self::Input<'_>, _params: &Self::Params) -> Answer {
+        let mut stacks = stacks.clone();
+        for instruction in instructions {
+            perform_move_as_9001(&mut stacks, instruction);
         }
+        stacks
+            .iter()
+            .map(|stack| stack.front().unwrap())
+            .collect::<String>()
+            .into()
     }
 }
 
@@ -184,3 +166,28 @@ fn input_parser(input: &str) -> IResult<&str, Input> {
 
     Ok((input, (stacks, instructions)))
 }
+
+#[cfg(test)]
+mod day05_tests {
+    use crate::day05::Day05;
+    use aoc_common::solution::{solve, Answer};
+
+    const EXAMPLE: &str = "    [D]    
+[N] [C]    
+[Z] [M] [P]
+ 1   2   3 
+
+move 1 from 2 to 1
+move 3 from 1 to 3
+move 2 from 2 to 1
+move 1 from 1 to 2
+";
+
+    #[test]
+    fn example() {
+        assert_eq!(
+            solve::<Day05>(EXAMPLE, &[1, 2], &()).unwrap(),
+            [Answer::from("CMZ"), Answer::from("MCD")]
+        );
+    }
+}
@@ -1,26 +1,27 @@
+use aoc_common::parse::ParseError;
+use aoc_common::solution::{Answer, Solution};
 use std::collections::VecDeque;
 
 use itertools::Itertools;
-use nom::{
-    bytes::complete::tag, combinator::map_res, multi::separated_list0, multi::separated_list1,
-    IResult,
-};
-
-#[derive(Debug, PartialEq)]
-struct MoveInstruction {
-    from: usize,
-    to: usize,
-    count: usize,
-}
 
-pub fn part1() -> usize {
-    let input = include_str!("../../data/2022/06.txt");
-    length_before_packet_start(input)
-}
+pub struct Day06;
+
+impl Solution for Day06 {
+    type Input<'a> = &'a str;
+
+    type Params = ();
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        Ok(input)
+    }
+
+    fn part1(input: &Self::Input<'_>, _params: &Self::Params) -> Answer {
+        length_before_packet_start(input).into()
+    }
 
-pub fn part2() -> usize {
-    let input = include_str!("../../data/2022/06.txt");
-    length_before_message_start(input)
+    fn part2(input: &Self::Input<'_>, _params: &Self::Params) -> Answer {
+        length_before_message_start(input).into()
+    }
 }
 
 pub fn length_before_packet_start(stream: &str) -> usize {
@@ -74,6 +75,8 @@ fn is_unique(packet: &VecDeque<char>, len: usize) -> bool {
 #[cfg(test)]
 mod day06_tests {
     use crate::day06::length_before_packet_start;
+    use crate::day06::Day06;
+    use aoc_common::solution::{solve, Answer};
 
     #[test]
     fn example_0() {
@@ -119,4 +122,15 @@ mod day06_tests {
             11
         );
     }
+
+    const EXAMPLE: &str = "mjqjpqmgbljsphdztnvjfqwrcgsmlb
+";
+
+    #[test]
+    fn example() {
+        assert_eq!(
+            solve::<Day06>(EXAMPLE, &[1, 2], &()).unwrap(),
+            [Answer::Integer(7), Answer::Integer(19)]
+        );
+    }
 }
@@ -1,14 +1,15 @@
+use aoc_common::debug;
+use aoc_common::params;
+use aoc_common::parse::{parse_all, ParseError};
+use aoc_common::solution::{Answer, Solution};
 use std::collections::HashMap;
 
 use itertools::Itertools;
-use nom::{
-    bytes::complete::tag, combinator::map_res, multi::separated_list0, multi::separated_list1,
-    IResult,
-};
+use nom::{bytes::complete::tag, combinator::map_res, multi::separated_list1, IResult};
 
 type Input<'a> = Vec<TerminalLine<'a>>;
 
-enum TerminalLine<'a> {
+pub enum TerminalLine<'a> {
     Directory(&'a str),
     File(&'a str, usize),
     ListFiles,
@@ -17,70 +18,60 @@ enum TerminalLine<'a> {
     GoToRoot,
 }
 
-pub fn part1() -> usize {
-    let input = include_str!("../../data/2022/07.txt");
+params! {
+    pub struct Day07Params {
+        disk_size: usize = (example: 70_000_000, real: 70_000_000),
+        needed_space: usize = (example: 30_000_000, real: 30_000_000),
+    }
+}
 
-    match input_parser(input) {
-        Ok((remaining_input, input)) if remaining_input.is_empty() => {
-            println!("parsed entire input");
-            let mut model: HashMap<String, usize> = HashMap::new();
-            sum_directories(&input, &mut model);
-            let mut sum = 0;
+pub struct Day07;
 
-            for (_name, &size) in model.iter() {
-                if size <= 100000 {
-                    // println!("{}: {}", name, size);
-                    sum += size
-                }
-            }
+impl Solution for Day07 {
+    type Input<'a> = Input<'a>;
 
-            sum
-        }
-        Ok((remaining, _)) => {
-            println!("remaining unparsed \"{}\"", remaining);
-            0
-        }
-        Err(e) => {
-            println!("error parsing \"{}\"", e);
-            0
-        }
+    type Params = Day07Params;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse_all(input, input_parser)
     }
-}
 
-pub fn part2() -> usize {
-    let input = include_str!("../../data/2022/07.txt");
-
-    match input_parser(input) {
-        Ok((remaining_input, input)) if remaining_input.is_empty() => {
-            println!("parsed entire input");
-            let mut model: HashMap<String, usize> = HashMap::new();
-            sum_directories(&input, &mut model);
-
-            let total_space = 70000000;
-            let required_space = 30000000;
-            let used_space = *model.get("/").unwrap();
-            let currently_free = total_space - used_space;
-            let to_free_up = required_space - currently_free;
-
-            println!("used space: {}", used_space);
-            println!("currently free: {}", currently_free);
-            println!("to free up: {}", to_free_up);
-
-            *model
-                .iter()
-                .sorted_by(|a, b| Ord::cmp(a.1, b.1))
-                .find(|(name, size)| **size >= to_free_up)
-                .unwrap()
-                .1
-        }
-        Ok((remaining, _)) => {
-            println!("remaining unparsed \"{}\"", remaining);
-            0
-        }
-        Err(e) => {
-            println!("error parsing \"{}\"", e);
-            0
+    fn part1(input: &Self::Input<'_>, _params: &Self::Params) -> Answer {
+        let mut model: HashMap<String, usize> = HashMap::new();
+        sum_directories(input, &mut model);
+        let mut sum = 0;
+
+        for (_name, &size) in model.iter() {
+            if size <= 100000 {
+                // println!("{}: {}", name, size);
+                sum += size
+            }
         }
+
+        sum.into()
+    }
+
+    fn part2(input: &Self::Input<'_>, params: &Self::Params) -> Answer {
+        let mut model: HashMap<String, usize> = HashMap::new();
+        sum_directories(input, &mut model);
+
+        let total_space = params.disk_size;
+        let required_space = params.needed_space;
+        let used_space = *model.get("/").unwrap();
+        let currently_free = total_space - used_space;
+        let to_free_up = required_space - currently_free;
+
+        debug!("used space: {}", used_space);
+        debug!("currently free: {}", currently_free);
+        debug!("to free up: {}", to_free_up);
+
+        (*model
+            .iter()
+            .sorted_by(|a, b| Ord::cmp(a.1, b.1))
+            .find(|(_name, size)| **size >= to_free_up)
+            .unwrap()
+            .1)
+            .into()
     }
 }
 
@@ -92,7 +83,7 @@ fn sum_directories(input: &[TerminalLine], model: &mut HashMap<String, usize>) {
                 current_directory.clear();
                 current_directory.push("/");
             }
-            TerminalLine::Directory(dir_name) => {}
+            TerminalLine::Directory(_dir_name) => {}
             TerminalLine::File(_, size) => {
                 let mut qualified_directory_name = String::new();
                 for directory in current_directory.iter() {
@@ -118,7 +109,7 @@ fn sum_directories(input: &[TerminalLine], model: &mut HashMap<String, usize>) {
     }
 }
 
-fn file_description_parser(input: &str) -> IResult<&str, TerminalLine> {
+fn file_description_parser(input: &str) -> IResult<&str, TerminalLine<'_>> {
     let (input, file_size) = map_res(nom::character::complete::digit1, |s: &str| {
         s.parse::<usize>()
     })(input)?;
@@ -127,13 +118,13 @@ fn file_description_parser(input: &str) -> IResult<&str, TerminalLine> {
     Ok((input, TerminalLine::File(file_name, file_size)))
 }
 
-fn directory_name_parser(input: &str) -> IResult<&str, TerminalLine> {
+fn directory_name_parser(input: &str) -> IResult<&str, TerminalLine<'_>> {
     let (input, _) = tag("dir ")(input)?;
     let (input, dir_name) = nom::bytes::complete::take_while(|c| c != '\n')(input)?;
     Ok((input, TerminalLine::Directory(dir_name)))
 }
 
-fn change_directory_parser(input: &str) -> IResult<&str, TerminalLine> {
+fn change_directory_parser(input: &str) -> IResult<&str, TerminalLine<'_>> {
     let (input, _) = tag("$ cd ")(input)?;
     let (input, dir_name) = nom::bytes::complete::take_while(|c| c != '\n')(input)?;
 
@@ -144,13 +135,13 @@ fn change_directory_parser(input: &str) -> IResult<&str, TerminalLine> {
     }
 }
 
-fn list_files_parser(input: &str) -> IResult<&str, TerminalLine> {
+fn list_files_parser(input: &str) -> IResult<&str, TerminalLine<'_>> {
     let (input, _) = tag("$ ls")(input)?;
 
     Ok((input, TerminalLine::ListFiles))
 }
 
-fn terminal_line_parser(input: &str) -> IResult<&str, TerminalLine> {
+fn terminal_line_parser(input: &str) -> IResult<&str, TerminalLine<'_>> {
     nom::branch::alt((
         file_description_parser,
         directory_name_parser,
@@ -159,9 +150,49 @@ fn terminal_line_parser(input: &str) -> IResult<&str, TerminalLine> {
     ))(input)
 }
 
-fn input_parser(input: &str) -> IResult<&str, Input> {
+fn input_parser(input: &str) -> IResult<&str, Input<'_>> {
     let (input, lines) =
         separated_list1(nom::character::complete::newline, terminal_line_parser)(input)?;
 
     Ok((input, lines))
 }
+
+#[cfg(test)]
+mod day07_tests {
+    use crate::day07::{Day07, Day07Params};
+    use aoc_common::params::Params;
+    use aoc_common::solution::{solve, Answer};
+
+    const EXAMPLE: &str = "$ cd /
+$ ls
+dir a
+14848514 b.txt
+8504156 c.dat
+dir d
+$ cd a
+$ ls
+dir e
+29116 f
+2557 g
+62596 h.lst
+$ cd e
+$ ls
+584 i
+$ cd ..
+$ cd ..
+$ cd d
+$ ls
+4060174 j
+8033020 d.log
+5626152 d.ext
+7214296 k
+";
+
+    #[test]
+    fn example() {
+        assert_eq!(
+            solve::<Day07>(EXAMPLE, &[1, 2], &Day07Params::example()).unwrap(),
+            [Answer::Integer(95437), Answer::Integer(24933642)]
+        );
+    }
+}
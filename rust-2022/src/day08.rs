@@ -1,47 +1,97 @@
+use aoc_common::parse::ParseError;
+use aoc_common::solution::{Answer, Solution};
 use std::collections::HashMap;
 
-use itertools::Itertools;
+pub struct Day08;
 
-pub fn part1() -> usize {
-    let input = include_str!("../../data/2022/08.txt");
+impl Solution for Day08 {
+    type Input<'a> = HashMap<(i32, i32), u32>;
 
-    let mut grid: HashMap<(i32, i32), u32> = HashMap::new();
-    for (row, line) in input.lines().enumerate() {
-        for (column, height_char) in line.chars().enumerate() {
-            let height = height_char.to_digit(10).unwrap();
-            grid.insert((row as i32, column as i32), height);
+    type Params = ();
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        let mut grid: HashMap<(i32, i32), u32> = HashMap::new();
+        for (row, line) in input.lines().enumerate() {
+            for (column, height_char) in line.char_indices() {
+                let height = height_char
+                    .to_digit(10)
+                    .ok_or_else(|| ParseError::at(input, &line[column..], "a digit"))?;
+                grid.insert((row as i32, column as i32), height);
+            }
         }
+
+        Ok(grid)
     }
 
-    //need to consider trees farther over - not just siblings
-    let mut visible_trees: usize = 0;
-    for (coordinate, height) in grid.iter() {
-        if is_visible_along_line(
-            coordinate,
-            |&(row, column)| (row, column - 1),
-            &grid,
-            *height,
-        ) || is_visible_along_line(
-            coordinate,
-            |&(row, column)| (row, column + 1),
-            &grid,
-            *height,
-        ) || is_visible_along_line(
-            coordinate,
-            |&(row, column)| (row - 1, column),
-            &grid,
-            *height,
-        ) || is_visible_along_line(
-            coordinate,
-            |&(row, column)| (row + 1, column),
-            &grid,
-            *height,
-        ) {
-            visible_trees += 1;
+    fn part1(grid: &Self::Input<'_>, _params: &Self::Params) -> Answer {
+        //need to consider trees farther over - not just siblings
+        let mut visible_trees: usize = 0;
+        for (coordinate, height) in grid.iter() {
+            if is_visible_along_line(
+                coordinate,
+                |&(row, column)| (row, column - 1),
+                grid,
+                *height,
+            ) || is_visible_along_line(
+                coordinate,
+                |&(row, column)| (row, column + 1),
+                grid,
+                *height,
+            ) || is_visible_along_line(
+                coordinate,
+                |&(row, column)| (row - 1, column),
+                grid,
+                *height,
+            ) || is_visible_along_line(
+                coordinate,
+                |&(row, column)| (row + 1, column),
+                grid,
+                *height,
+            ) {
+                visible_trees += 1;
+            }
         }
+
+        visible_trees.into()
     }
 
-    visible_trees
+    fn part2(grid: &Self::Input<'_>, _params: &Self::Params) -> Answer {
+        //need to consider trees farther over - not just siblings
+        let mut max_tree_score: usize = 0;
+        for (coordinate, height) in grid.iter() {
+            let coordinate_score = count_visible_along_line(
+                coordinate,
+                |&(row, column)| (row, column - 1),
+                grid,
+                *height,
+                0,
+            ) * count_visible_along_line(
+                coordinate,
+                |&(row, column)| (row, column + 1),
+                grid,
+                *height,
+                0,
+            ) * count_visible_along_line(
+                coordinate,
+                |&(row, column)| (row - 1, column),
+                grid,
+                *height,
+                0,
+            ) * count_visible_along_line(
+                coordinate,
+                |&(row, column)| (row + 1, column),
+                grid,
+                *height,
+                0,
+            );
+
+            if coordinate_score > max_tree_score {
+                max_tree_score = coordinate_score;
+            }
+        }
+
+        max_tree_score.into()
+    }
 }
 
 fn is_visible_along_line(
@@ -63,54 +113,6 @@ fn is_visible_along_line(
     }
 }
 
-pub fn part2() -> usize {
-    let input = include_str!("../../data/2022/08.txt");
-
-    let mut grid: HashMap<(i32, i32), u32> = HashMap::new();
-    for (row, line) in input.lines().enumerate() {
-        for (column, height_char) in line.chars().enumerate() {
-            let height = height_char.to_digit(10).unwrap();
-            grid.insert((row as i32, column as i32), height);
-        }
-    }
-
-    //need to consider trees farther over - not just siblings
-    let mut max_tree_score: usize = 0;
-    for (coordinate, height) in grid.iter() {
-        let coordinate_score = count_visible_along_line(
-            coordinate,
-            |&(row, column)| (row, column - 1),
-            &grid,
-            *height,
-            0,
-        ) * count_visible_along_line(
-            coordinate,
-            |&(row, column)| (row, column + 1),
-            &grid,
-            *height,
-            0,
-        ) * count_visible_along_line(
-            coordinate,
-            |&(row, column)| (row - 1, column),
-            &grid,
-            *height,
-            0,
-        ) * count_visible_along_line(
-            coordinate,
-            |&(row, column)| (row + 1, column),
-            &grid,
-            *height,
-            0,
-        );
-
-        if coordinate_score > max_tree_score {
-            max_tree_score = coordinate_score;
-        }
-    }
-
-    max_tree_score
-}
-
 fn count_visible_along_line(
     start_coordinate: &(i32, i32),
     next_coordinate: impl Fn(&(i32, i32)) -> (i32, i32),
@@ -130,3 +132,24 @@ fn count_visible_along_line(
         None => accum,
     }
 }
+
+#[cfg(test)]
+mod day08_tests {
+    use crate::day08::Day08;
+    use aoc_common::solution::{solve, Answer};
+
+    const EXAMPLE: &str = "30373
+25512
+65332
+33549
+35390
+";
+
+    #[test]
+    fn example() {
+        assert_eq!(
+            solve::<Day08>(EXAMPLE, &[1, 2], &()).unwrap(),
+            [Answer::Integer(21), Answer::Integer(8)]
+        );
+    }
+}
@@ -1,94 +1,72 @@
-use std::collections::{HashMap, HashSet};
+use aoc_common::frame;
+use aoc_common::parse::{parse_all, ParseError};
+use aoc_common::render::{self, Canvas, Cell, Colour};
+use aoc_common::solution::{Answer, Solution};
+use std::collections::HashSet;
 
-use itertools::Itertools;
-use nom::{
-    bytes::complete::tag, combinator::map_res, multi::separated_list0, multi::separated_list1,
-    IResult,
-};
+use nom::{bytes::complete::tag, combinator::map_res, multi::separated_list1, IResult};
 
 type Input = Vec<Move>;
 
 #[derive(Debug, Clone, Copy)]
-enum Move {
+pub enum Move {
     Up(usize),
     Down(usize),
     Left(usize),
     Right(usize),
 }
 
-pub fn part1() -> usize {
-    let input = include_str!("../../data/2022/09.txt");
+pub struct Day09;
 
-    match input_parser(input) {
-        Ok((remaining_input, moves)) if remaining_input.is_empty() => {
-            println!("parsed entire input");
-            let mut tail_positions: HashSet<(i32, i32)> = HashSet::new();
+impl Solution for Day09 {
+    type Input<'a> = Input;
 
-            let mut head_coordinate = (0, 0);
-            let mut tail_coordinate = (0, 0);
-            tail_positions.insert(tail_coordinate);
-            for step in moves.iter().flat_map(expand_move) {
-                simulate_movement(&mut head_coordinate, &mut tail_coordinate, step);
-                tail_positions.insert(tail_coordinate);
-            }
+    type Params = ();
 
-            tail_positions.len()
-        }
-        Ok((remaining, _)) => {
-            println!("remaining unparsed \"{}\"", remaining);
-            0
-        }
-        Err(e) => {
-            println!("error parsing \"{}\"", e);
-            0
-        }
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse_all(input, input_parser)
     }
-}
 
-pub fn part2() -> usize {
-    let input = include_str!("../../data/2022/09.txt");
-
-    match input_parser(input) {
-        Ok((remaining_input, moves)) if remaining_input.is_empty() => {
-            println!("parsed entire input");
-            let mut tail_positions: HashSet<(i32, i32)> = HashSet::new();
-
-            //0 will be the head, 8 will be the tail
-            let mut rope_positions = vec![(0, 0); 10];
-
-            // print_map(&rope_positions);
-            tail_positions.insert((0, 0));
-            for (_step_num, step) in moves.iter().flat_map(expand_move).enumerate() {
-                let mut head_coordinate = *rope_positions.get(0).unwrap();
-                let mut next_coordinate = *rope_positions.get(1).unwrap();
-                simulate_movement(&mut head_coordinate, &mut next_coordinate, step);
-                rope_positions[0] = head_coordinate;
-                rope_positions[1] = next_coordinate;
-
-                for index in 1..(rope_positions.len() - 1) {
-                    let head_coordinate = *rope_positions.get(index).unwrap();
-                    let mut next_coordinate = *rope_positions.get(index + 1).unwrap();
-                    pull_tail(&head_coordinate, &mut next_coordinate);
-                    rope_positions[index + 1] = next_coordinate;
-                }
-                tail_positions.insert(*rope_positions.last().unwrap());
-
-                // println!("{:?}", *rope_positions.get(8).unwrap());
-                // println!("After Step {}:", step_num);
-                // print_map(&rope_positions);
-            }
+    fn part1(moves: &Self::Input<'_>, _params: &Self::Params) -> Answer {
+        let mut tail_positions: HashSet<(i32, i32)> = HashSet::new();
 
-            // print_map(&rope_positions);
-            tail_positions.len()
-        }
-        Ok((remaining, _)) => {
-            println!("remaining unparsed \"{}\"", remaining);
-            0
+        let mut head_coordinate = (0, 0);
+        let mut tail_coordinate = (0, 0);
+        tail_positions.insert(tail_coordinate);
+        for step in moves.iter().flat_map(expand_move) {
+            simulate_movement(&mut head_coordinate, &mut tail_coordinate, step);
+            tail_positions.insert(tail_coordinate);
+            frame!(draw_rope(&[head_coordinate, tail_coordinate]));
         }
-        Err(e) => {
-            println!("error parsing \"{}\"", e);
-            0
+
+        tail_positions.len().into()
+    }
+
+    fn part2(moves: &Self::Input<'_>, _params: &Self::Params) -> Answer {
+        let mut tail_positions: HashSet<(i32, i32)> = HashSet::new();
+
+        //0 will be the head, 8 will be the tail
+        let mut rope_positions = [(0, 0); 10];
+
+        tail_positions.insert((0, 0));
+        for step in moves.iter().flat_map(expand_move) {
+            let mut head_coordinate = *rope_positions.first().unwrap();
+            let mut next_coordinate = *rope_positions.get(1).unwrap();
+            simulate_movement(&mut head_coordinate, &mut next_coordinate, step);
+            rope_positions[0] = head_coordinate;
+            rope_positions[1] = next_coordinate;
+
+            for index in 1..(rope_positions.len() - 1) {
+                let head_coordinate = *rope_positions.get(index).unwrap();
+                let mut next_coordinate = *rope_positions.get(index + 1).unwrap();
+                pull_tail(&head_coordinate, &mut next_coordinate);
+                rope_positions[index + 1] = next_coordinate;
+            }
+            tail_positions.insert(*rope_positions.last().unwrap());
+            frame!(draw_rope(&rope_positions));
         }
+
+        tail_positions.len().into()
     }
 }
 
@@ -160,40 +138,18 @@ fn pull_tail(head_coordinate: &(i32, i32), tail_coordinate: &mut (i32, i32)) {
     }
 }
 
-fn print_map(rope: &[(i32, i32)]) {
-    let mut grid = HashMap::<(i32, i32), char>::new();
-
-    for (index, position) in rope.iter().enumerate() {
-        print!("{}:{:?},", index, position);
-        if index == 0 {
-            grid.insert(*position, 'H');
-        } else {
-            match grid.get(position) {
-                Some(_) => {
-                    continue;
-                }
-                None => {
-                    grid.insert(*position, index.to_string().chars().next().unwrap());
-                }
-            }
-        }
-    }
-    println!();
-
-    for y in (-5..16).rev() {
-        for x in -11..12 {
-            match grid.get(&(x, y)) {
-                Some(c) => {
-                    print!("{}", c);
-                }
-                None => {
-                    print!(".");
-                }
-            }
-        }
-
-        println!();
-    }
+// the head is `H` and the knots behind it are numbered. y goes up here, so it's flipped to draw
+fn draw_rope(rope: &[(i32, i32)]) -> Canvas {
+    let knots = rope.iter().enumerate().rev().map(|(index, &(x, y))| {
+        let cell = match index {
+            0 => Cell::coloured('H', Colour::RED),
+            _ => Cell::from(char::from_digit(index as u32 % 10, 10).unwrap()),
+        };
+        ((x as isize, -y as isize), cell)
+    });
+
+    // the start stays in the picture so the rope doesn't look like it's standing still
+    render::points([((0, 0), 's'.into())].into_iter().chain(knots), '.'.into())
 }
 
 fn too_far(a: &(i32, i32), b: &(i32, i32)) -> bool {
@@ -248,3 +204,45 @@ fn input_parser(input: &str) -> IResult<&str, Input> {
 
     Ok((input, lines))
 }
+
+#[cfg(test)]
+mod day09_tests {
+    use crate::day09::Day09;
+    use aoc_common::solution::{solve, Answer};
+
+    const EXAMPLE: &str = "R 4
+U 4
+L 3
+D 1
+R 4
+D 1
+L 5
+R 2
+";
+
+    #[test]
+    fn example() {
+        assert_eq!(
+            solve::<Day09>(EXAMPLE, &[1, 2], &()).unwrap(),
+            [Answer::Integer(13), Answer::Integer(1)]
+        );
+    }
+
+    const LARGER_EXAMPLE: &str = "R 5
+U 8
+L 8
+D 3
+R 17
+D 10
+L 25
+U 20
+";
+
+    #[test]
+    fn larger_example_part2() {
+        assert_eq!(
+            solve::<Day09>(LARGER_EXAMPLE, &[2], &()).unwrap(),
+            [Answer::Integer(36)]
+        );
+    }
+}
@@ -1,103 +1,80 @@
-use std::collections::{HashMap, HashSet};
+use aoc_common::ocr;
+use aoc_common::parse::{parse_all, parse_number, ParseError};
+use aoc_common::solution::{Answer, Solution};
 
-use itertools::Itertools;
-use nom::{
-    bytes::complete::tag,
-    combinator::{map_res, opt},
-    multi::separated_list0,
-    multi::separated_list1,
-    IResult,
-};
+use nom::{bytes::complete::tag, multi::separated_list1, IResult};
 
 type Input = Vec<Instruction>;
 
 #[derive(Debug, Clone, Copy)]
-enum Instruction {
+pub enum Instruction {
     AddX(i32),
     NoOp,
 }
 
-pub fn part1() -> i32 {
-    let input = include_str!("../../data/2022/10.txt");
-
-    match input_parser(input) {
-        Ok((remaining_input, instructions)) if remaining_input.is_empty() => {
-            println!("parsed entire input");
-
-            let mut instructions_iter = instructions.iter().flat_map(expand_add);
-
-            let mut sum = 0;
-            let mut register_x: i32 = 1;
-            for cycle in 1..221 {
-                match cycle {
-                    20 => sum += 20 * register_x,
-                    60 => sum += 60 * register_x,
-                    100 => sum += 100 * register_x,
-                    140 => sum += 140 * register_x,
-                    180 => sum += 180 * register_x,
-                    220 => sum += 220 * register_x,
-                    _ => {}
-                }
+pub struct Day10;
 
-                let instruction = instructions_iter.next().unwrap();
+impl Solution for Day10 {
+    type Input<'a> = Input;
 
-                match instruction {
-                    Instruction::AddX(amount) => register_x += amount,
-                    Instruction::NoOp => {}
-                }
-            }
+    type Params = ();
 
-            sum
-        }
-        Ok((remaining, _)) => {
-            println!("remaining unparsed \"{}\"", remaining);
-            0
-        }
-        Err(e) => {
-            println!("error parsing \"{}\"", e);
-            0
-        }
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse_all(input, input_parser)
     }
-}
 
-pub fn part2() -> usize {
-    let input = include_str!("../../data/2022/10.txt");
+    fn part1(instructions: &Self::Input<'_>, _params: &Self::Params) -> Answer {
+        let mut instructions_iter = instructions.iter().flat_map(expand_add);
+
+        let mut sum = 0;
+        let mut register_x: i32 = 1;
+        for cycle in 1..221 {
+            match cycle {
+                20 => sum += 20 * register_x,
+                60 => sum += 60 * register_x,
+                100 => sum += 100 * register_x,
+                140 => sum += 140 * register_x,
+                180 => sum += 180 * register_x,
+                220 => sum += 220 * register_x,
+                _ => {}
+            }
 
-    match input_parser(input) {
-        Ok((remaining_input, instructions)) if remaining_input.is_empty() => {
-            println!("parsed entire input");
+            let instruction = instructions_iter.next().unwrap();
 
-            let mut instructions_iter = instructions.iter().flat_map(expand_add);
-            let mut register_x: i32 = 1;
+            match instruction {
+                Instruction::AddX(amount) => register_x += amount,
+                Instruction::NoOp => {}
+            }
+        }
 
-            for _ow in 0..6 {
-                for column in 0..40 {
-                    if register_x.abs_diff(column) <= 1 {
-                        print!("#");
-                    } else {
-                        print!(".");
-                    }
+        sum.into()
+    }
 
-                    let instruction = instructions_iter.next().unwrap();
+    fn part2(instructions: &Self::Input<'_>, _params: &Self::Params) -> Answer {
+        let mut instructions_iter = instructions.iter().flat_map(expand_add);
+        let mut register_x: i32 = 1;
+        let mut screen: Vec<String> = Vec::with_capacity(6);
 
-                    match instruction {
-                        Instruction::AddX(amount) => register_x += amount,
-                        Instruction::NoOp => {}
-                    }
+        for _ow in 0..6 {
+            let mut row = String::with_capacity(40);
+            for column in 0..40 {
+                if register_x.abs_diff(column) <= 1 {
+                    row.push('#');
+                } else {
+                    row.push('.');
                 }
-                println!();
-            }
 
-            0
-        }
-        Ok((remaining, _)) => {
-            println!("remaining unparsed \"{}\"", remaining);
-            0
-        }
-        Err(e) => {
-            println!("error parsing \"{}\"", e);
-            0
+                let instruction = instructions_iter.next().unwrap();
+
+                match instruction {
+                    Instruction::AddX(amount) => register_x += amount,
+                    Instruction::NoOp => {}
+                }
+            }
+            screen.push(row);
         }
+
+        ocr::answer(screen)
     }
 }
 
@@ -110,18 +87,9 @@ fn expand_add(instruction: &Instruction) -> Vec<Instruction> {
 
 fn add_parser(input: &str) -> IResult<&str, Instruction> {
     let (input, _) = tag("addx ")(input)?;
-    let (input, negative_sign) = opt(nom::character::complete::char('-'))(input)?;
-    let (input, amount) =
-        map_res(nom::character::complete::digit1, |s: &str| s.parse::<i32>())(input)?;
-
-    Ok((
-        input,
-        Instruction::AddX(if negative_sign.is_some() {
-            -amount
-        } else {
-            amount
-        }),
-    ))
+    let (input, amount) = parse_number(input)?;
+
+    Ok((input, Instruction::AddX(amount)))
 }
 
 fn noop_parser(input: &str) -> IResult<&str, Instruction> {
@@ -139,3 +107,178 @@ fn input_parser(input: &str) -> IResult<&str, Input> {
 
     Ok((input, lines))
 }
+
+#[cfg(test)]
+mod day10_tests {
+    use crate::day10::Day10;
+    use aoc_common::solution::{solve, Answer};
+
+    const EXAMPLE: &str = "addx 15
+addx -11
+addx 6
+addx -3
+addx 5
+addx -1
+addx -8
+addx 13
+addx 4
+noop
+addx -1
+addx 5
+addx -1
+addx 5
+addx -1
+addx 5
+addx -1
+addx 5
+addx -1
+addx -35
+addx 1
+addx 24
+addx -19
+addx 1
+addx 16
+addx -11
+noop
+noop
+addx 21
+addx -15
+noop
+noop
+addx -3
+addx 9
+addx 1
+addx -3
+addx 8
+addx 1
+addx 5
+noop
+noop
+noop
+noop
+noop
+addx -36
+noop
+addx 1
+addx 7
+noop
+noop
+noop
+addx 2
+addx 6
+noop
+noop
+noop
+noop
+noop
+addx 1
+noop
+noop
+addx 7
+addx 1
+noop
+addx -13
+addx 13
+addx 7
+noop
+addx 1
+addx -33
+noop
+noop
+noop
+addx 2
+noop
+noop
+noop
+addx 8
+noop
+addx -1
+addx 2
+addx 1
+noop
+addx 17
+addx -9
+addx 1
+addx 1
+addx -3
+addx 11
+noop
+noop
+addx 1
+noop
+addx 1
+noop
+noop
+addx -13
+addx -19
+addx 1
+addx 3
+addx 26
+addx -30
+addx 12
+addx -1
+addx 3
+addx 1
+noop
+noop
+noop
+addx -9
+addx 18
+addx 1
+addx 2
+noop
+noop
+addx 9
+noop
+noop
+noop
+addx -1
+addx 2
+addx -37
+addx 1
+addx 3
+noop
+addx 15
+addx -21
+addx 22
+addx -6
+addx 1
+noop
+addx 2
+addx 1
+noop
+addx -10
+noop
+noop
+addx 20
+addx 1
+addx 2
+addx 2
+addx -6
+addx -11
+noop
+noop
+noop
+";
+
+    #[test]
+    fn example() {
+        assert_eq!(
+            solve::<Day10>(EXAMPLE, &[1, 2], &()).unwrap(),
+            [
+                Answer::Integer(13140),
+                Answer::grid(
+                    [
+                        "##..##..##..##..##..##..##..##..##..##..",
+                        "###...###...###...###...###...###...###.",
+                        "####....####....####....####....####....",
+                        "#####.....#####.....#####.....#####.....",
+                        "######......######......######......####",
+                        "#######.......#######.......#######....."
+                    ]
+                    .map(str::to_owned)
+                )
+            ]
+        );
+    }
+}
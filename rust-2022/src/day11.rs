@@ -1,13 +1,9 @@
-use ibig::{modular::ModuloRing, ops::DivRem, ubig, UBig};
+use aoc_common::debug;
+use aoc_common::parse::{parse_all, ParseError};
+use aoc_common::solution::{Answer, Solution};
+use ibig::{ops::DivRem, ubig, UBig};
 use itertools::Itertools;
-use nom::{
-    bytes::complete::tag,
-    combinator::{map_res, opt},
-    multi::separated_list0,
-    multi::separated_list1,
-    IResult,
-};
-use std::collections::{HashMap, HashSet};
+use nom::{bytes::complete::tag, combinator::map_res, multi::separated_list1, IResult};
 
 type Input = Vec<Monkey>;
 
@@ -19,8 +15,8 @@ enum Operation {
     MultiplyOld,
 }
 
-#[derive(Debug, PartialEq)]
-struct Monkey {
+#[derive(Debug, Clone, PartialEq)]
+pub struct Monkey {
     items: Vec<UBig>,
     operation: Operation,
     divisible_test: UBig,
@@ -28,117 +24,98 @@ struct Monkey {
     negative_monkey: usize,
 }
 
-pub fn part1() -> usize {
-    let input = include_str!("../../data/2022/11.txt");
-
-    match input_parser(input) {
-        Ok((remaining_input, mut monkeys)) if remaining_input.is_empty() => {
-            println!("parsed entire input");
-
-            let mut counts = vec![0; monkeys.len()];
-            for _round in 0..20 {
-                let mut holding: Vec<Vec<UBig>> = vec![vec![]; monkeys.len()];
-                for (monkey_num, monkey) in monkeys.iter_mut().enumerate() {
-                    monkey.items.extend(holding[monkey_num].clone());
-                    holding[monkey_num].clear();
-
-                    for item in monkey.items.iter() {
-                        let (new_value, _) =
-                            (do_operation(item, monkey.operation)).div_rem(ubig!(3));
-                        if new_value.clone().div_rem(monkey.divisible_test.clone()).1 == ubig!(0) {
-                            holding[monkey.positive_monkey].push(new_value);
-                        } else {
-                            holding[monkey.negative_monkey].push(new_value);
-                        }
-                    }
+pub struct Day11;
 
-                    counts[monkey_num] += monkey.items.len();
+impl Solution for Day11 {
+    type Input<'a> = Input;
 
-                    monkey.items.clear();
-                }
+    type Params = ();
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse_all(input, input_parser)
+    }
 
-                for (monkey_num, monkey_holding) in holding.iter().enumerate() {
-                    monkeys[monkey_num].items.extend(monkey_holding.clone())
+    fn part1(monkeys: &Self::Input<'_>, _params: &Self::Params) -> Answer {
+        let mut monkeys = monkeys.clone();
+        let mut counts = vec![0; monkeys.len()];
+        for _round in 0..20 {
+            let mut holding: Vec<Vec<UBig>> = vec![vec![]; monkeys.len()];
+            for (monkey_num, monkey) in monkeys.iter_mut().enumerate() {
+                monkey.items.extend(holding[monkey_num].clone());
+                holding[monkey_num].clear();
+
+                for item in monkey.items.iter() {
+                    let (new_value, _) = (do_operation(item, monkey.operation)).div_rem(ubig!(3));
+                    if new_value.clone().div_rem(monkey.divisible_test.clone()).1 == ubig!(0) {
+                        holding[monkey.positive_monkey].push(new_value);
+                    } else {
+                        holding[monkey.negative_monkey].push(new_value);
+                    }
                 }
 
-                // println!("round {:?}:", round);
-                // for monkey in monkeys.iter() {
-                //     println!("{:?}:", monkey.items);
-                // }
+                counts[monkey_num] += monkey.items.len();
+
+                monkey.items.clear();
             }
 
-            println!("{:?}", counts);
+            for (monkey_num, monkey_holding) in holding.iter().enumerate() {
+                monkeys[monkey_num].items.extend(monkey_holding.clone())
+            }
 
-            let mut most_active = counts.iter().sorted().rev();
-            most_active.next().unwrap() * most_active.next().unwrap()
-        }
-        Ok((remaining, _)) => {
-            println!("remaining unparsed \"{}\"", remaining);
-            0
-        }
-        Err(e) => {
-            println!("error parsing \"{}\"", e);
-            0
+            // println!("round {:?}:", round);
+            // for monkey in monkeys.iter() {
+            //     println!("{:?}:", monkey.items);
+            // }
         }
-    }
-}
 
-pub fn part2() -> usize {
-    let input = include_str!("../../data/2022/11.txt");
-
-    match input_parser(input) {
-        Ok((remaining_input, mut monkeys)) if remaining_input.is_empty() => {
-            println!("parsed entire input");
-
-            let divisor = monkeys
-                .iter()
-                .fold(ubig!(1), |acc, m| acc * m.divisible_test.clone());
-
-            let mut counts = vec![0; monkeys.len()];
-            for _round in 0..10_000 {
-                let mut holding: Vec<Vec<UBig>> = vec![vec![]; monkeys.len()];
-                for (monkey_num, monkey) in monkeys.iter_mut().enumerate() {
-                    monkey.items.extend(holding[monkey_num].clone());
-                    holding[monkey_num].clear();
-
-                    for item in monkey.items.iter() {
-                        let (_, new_value) =
-                            (do_operation(item, monkey.operation)).div_rem(divisor.clone());
-                        if new_value.clone().div_rem(monkey.divisible_test.clone()).1 == ubig!(0) {
-                            holding[monkey.positive_monkey].push(new_value);
-                        } else {
-                            holding[monkey.negative_monkey].push(new_value);
-                        }
-                    }
+        debug!("items inspected: {:?}", counts);
 
-                    counts[monkey_num] += monkey.items.len();
+        let mut most_active = counts.iter().sorted().rev();
+        (most_active.next().unwrap() * most_active.next().unwrap()).into()
+    }
 
-                    monkey.items.clear();
+    fn part2(monkeys: &Self::Input<'_>, _params: &Self::Params) -> Answer {
+        let mut monkeys = monkeys.clone();
+        let divisor = monkeys
+            .iter()
+            .fold(ubig!(1), |acc, m| acc * m.divisible_test.clone());
+
+        let mut counts = vec![0; monkeys.len()];
+        for _round in 0..10_000 {
+            let mut holding: Vec<Vec<UBig>> = vec![vec![]; monkeys.len()];
+            for (monkey_num, monkey) in monkeys.iter_mut().enumerate() {
+                monkey.items.extend(holding[monkey_num].clone());
+                holding[monkey_num].clear();
+
+                for item in monkey.items.iter() {
+                    let (_, new_value) =
+                        (do_operation(item, monkey.operation)).div_rem(divisor.clone());
+                    if new_value.clone().div_rem(monkey.divisible_test.clone()).1 == ubig!(0) {
+                        holding[monkey.positive_monkey].push(new_value);
+                    } else {
+                        holding[monkey.negative_monkey].push(new_value);
+                    }
                 }
 
-                for (monkey_num, monkey_holding) in holding.iter().enumerate() {
-                    monkeys[monkey_num].items.extend(monkey_holding.clone())
-                }
+                counts[monkey_num] += monkey.items.len();
 
-                // println!("round {:?}:", _round);
-                // for monkey in monkeys.iter() {
-                //     println!("{:?}:", monkey.items);
-                // }
+                monkey.items.clear();
             }
 
-            println!("{:?}", counts);
+            for (monkey_num, monkey_holding) in holding.iter().enumerate() {
+                monkeys[monkey_num].items.extend(monkey_holding.clone())
+            }
 
-            let mut most_active = counts.iter().sorted().rev();
-            most_active.next().unwrap() * most_active.next().unwrap()
-        }
-        Ok((remaining, _)) => {
-            println!("remaining unparsed \"{}\"", remaining);
-            0
-        }
-        Err(e) => {
-            println!("error parsing \"{}\"", e);
-            0
+            // println!("round {:?}:", _round);
+            // for monkey in monkeys.iter() {
+            //     println!("{:?}:", monkey.items);
+            // }
         }
+
+        debug!("items inspected: {:?}", counts);
+
+        let mut most_active = counts.iter().sorted().rev();
+        (most_active.next().unwrap() * most_active.next().unwrap()).into()
     }
 }
 
@@ -234,3 +211,46 @@ fn input_parser(input: &str) -> IResult<&str, Input> {
 
     Ok((input, monkeys))
 }
+
+#[cfg(test)]
+mod day11_tests {
+    use crate::day11::Day11;
+    use aoc_common::solution::{solve, Answer};
+
+    const EXAMPLE: &str = "Monkey 0:
+  Starting items: 79, 98
+  Operation: new = old * 19
+  Test: divisible by 23
+    If true: throw to monkey 2
+    If false: throw to monkey 3
+
+Monkey 1:
+  Starting items: 54, 65, 75, 74
+  Operation: new = old + 6
+  Test: divisible by 19
+    If true: throw to monkey 2
+    If false: throw to monkey 0
+
+Monkey 2:
+  Starting items: 79, 60, 97
+  Operation: new = old * old
+  Test: divisible by 13
+    If true: throw to monkey 1
+    If false: throw to monkey 3
+
+Monkey 3:
+  Starting items: 74
+  Operation: new = old + 3
+  Test: divisible by 17
+    If true: throw to monkey 0
+    If false: throw to monkey 1
+";
+
+    #[test]
+    fn example() {
+        assert_eq!(
+            solve::<Day11>(EXAMPLE, &[1, 2], &()).unwrap(),
+            [Answer::Integer(10605), Answer::Integer(2713310158)]
+        );
+    }
+}
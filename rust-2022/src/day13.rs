@@ -1,20 +1,16 @@
+use aoc_common::parse::{parse_all, ParseError};
+use aoc_common::solution::{Answer, Solution};
 use itertools::Itertools;
 use nom::{
-    bytes::complete::tag,
-    character::complete::anychar,
-    combinator::{map_res, opt},
-    multi::separated_list0,
-    multi::{many1, separated_list1},
+    bytes::complete::tag, combinator::map_res, multi::separated_list0, multi::separated_list1,
     IResult,
 };
-use std::collections::BinaryHeap;
-use std::collections::{HashMap, HashSet};
-use std::{cmp::Ordering, hash::Hash};
+use std::cmp::Ordering;
 
 type Input = Vec<(Packet, Packet)>;
 
 #[derive(Debug, Clone, Eq, PartialEq)]
-enum Packet {
+pub enum Packet {
     List(Vec<Packet>),
     Value(usize),
 }
@@ -52,65 +48,50 @@ impl PartialOrd for Packet {
     }
 }
 
-pub fn part1() -> usize {
-    let input = include_str!("../../data/2022/13.txt");
+pub struct Day13;
 
-    match part_1_input_parser(input) {
-        Ok((remaining_input, packets)) if remaining_input.is_empty() => {
-            println!("parsed entire input");
+impl Solution for Day13 {
+    type Input<'a> = (Input, Vec<Packet>);
 
-            let mut sum = 0;
-            for (index, (left, right)) in packets.iter().enumerate() {
-                // println!("{} : {}", index, left < right);
-                if left < right {
-                    sum += index + 1;
-                }
-            }
+    type Params = ();
 
-            sum
-        }
-        Ok((remaining, _)) => {
-            println!("remaining unparsed \"{}\"", remaining);
-            0
-        }
-        Err(e) => {
-            println!("error parsing \"{}\"", e);
-            0
-        }
+    // part 1 reads the packets in pairs and part 2 as one list
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        Ok((
+            parse_all(input, part_1_input_parser)?,
+            parse_all(input, part2_input_parser)?,
+        ))
     }
-}
 
-pub fn part2() -> usize {
-    let input = include_str!("../../data/2022/13.txt");
+    fn part1((packets, _): &Self::Input<'_>, _params: &Self::Params) -> Answer {
+        let mut sum = 0;
+        for (index, (left, right)) in packets.iter().enumerate() {
+            // println!("{} : {}", index, left < right);
+            if left < right {
+                sum += index + 1;
+            }
+        }
 
-    match part2_input_parser(input) {
-        Ok((remaining_input, mut packets)) if remaining_input.is_empty() => {
-            println!("parsed entire input");
+        sum.into()
+    }
 
-            let first_divider = Packet::List(vec![Packet::List(vec![Packet::Value(2)])]);
-            let second_divider = Packet::List(vec![Packet::List(vec![Packet::Value(6)])]);
+    fn part2((_, packets): &Self::Input<'_>, _params: &Self::Params) -> Answer {
+        let mut packets = packets.clone();
+        let first_divider = Packet::List(vec![Packet::List(vec![Packet::Value(2)])]);
+        let second_divider = Packet::List(vec![Packet::List(vec![Packet::Value(6)])]);
 
-            packets.push(first_divider.clone());
-            packets.push(second_divider.clone());
+        packets.push(first_divider.clone());
+        packets.push(second_divider.clone());
 
-            let mut result = 1;
+        let mut result = 1;
 
-            for (index, packet) in packets.iter().sorted().enumerate() {
-                if *packet == first_divider || *packet == second_divider {
-                    result *= index + 1;
-                }
+        for (index, packet) in packets.iter().sorted().enumerate() {
+            if *packet == first_divider || *packet == second_divider {
+                result *= index + 1;
             }
-
-            result
-        }
-        Ok((remaining, _)) => {
-            println!("remaining unparsed \"{}\"", remaining);
-            0
-        }
-        Err(e) => {
-            println!("error parsing \"{}\"", e);
-            0
         }
+
+        result.into()
     }
 }
 
@@ -162,7 +143,9 @@ fn part2_input_parser(input: &str) -> IResult<&str, Vec<Packet>> {
 
 #[cfg(test)]
 mod day13_tests {
+    use crate::day13::Day13;
     use crate::day13::Packet;
+    use aoc_common::solution::{solve, Answer};
 
     #[test]
     fn test_value_ordering() {
@@ -203,4 +186,37 @@ mod day13_tests {
                 ])])
         );
     }
+
+    const EXAMPLE: &str = "[1,1,3,1,1]
+[1,1,5,1,1]
+
+[[1],[2,3,4]]
+[[1],4]
+
+[9]
+[[8,7,6]]
+
+[[4,4],4,4]
+[[4,4],4,4,4]
+
+[7,7,7,7]
+[7,7,7]
+
+[]
+[3]
+
+[[[]]]
+[[]]
+
+[1,[2,[3,[4,[5,6,7]]]],8,9]
+[1,[2,[3,[4,[5,6,0]]]],8,9]
+";
+
+    #[test]
+    fn example() {
+        assert_eq!(
+            solve::<Day13>(EXAMPLE, &[1, 2], &()).unwrap(),
+            [Answer::Integer(13), Answer::Integer(140)]
+        );
+    }
 }
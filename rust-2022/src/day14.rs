@@ -1,20 +1,15 @@
-use itertools::Itertools;
-use nom::{
-    bytes::complete::tag,
-    character::complete::anychar,
-    combinator::{map_res, opt},
-    multi::separated_list0,
-    multi::{many1, separated_list1},
-    IResult,
-};
-use std::collections::BinaryHeap;
-use std::collections::{HashMap, HashSet};
-use std::{cmp::Ordering, hash::Hash};
+use aoc_common::grid::{Grid, Position};
+use aoc_common::parse::{parse_all, ParseError};
+use aoc_common::solution::{Answer, Solution};
+use nom::{bytes::complete::tag, combinator::map_res, multi::separated_list1, IResult};
 
 type Input = Vec<Formation>;
 
 type Formation = Vec<(isize, isize)>;
 
+// `None` is air
+type Cave = Grid<Option<Spot>>;
+
 #[derive(Debug, Copy, Clone, Eq, PartialEq)]
 enum Spot {
     Sand,
@@ -27,64 +22,42 @@ enum SandResult {
     Abyss,
 }
 
-pub fn part1() -> usize {
-    let input = include_str!("../../data/2022/14.txt");
-
-    match input_parser(input) {
-        Ok((remaining_input, formations)) if remaining_input.is_empty() => {
-            println!("parsed entire input");
-
-            let mut map = HashMap::new();
+pub struct Day14;
 
-            fill_map(&formations, &mut map);
+impl Solution for Day14 {
+    type Input<'a> = Input;
 
-            let deepest = map.iter().max_by(|x, y| x.0 .1.cmp(&y.0 .1)).unwrap().0 .1;
+    type Params = ();
 
-            while simulate_sand_part1(&mut map, deepest + 2) == SandResult::Settled {}
-
-            map.iter().filter(|(_, &spot)| spot == Spot::Sand).count()
-        }
-        Ok((remaining, _)) => {
-            println!("remaining unparsed \"{}\"", remaining);
-            0
-        }
-        Err(e) => {
-            println!("error parsing \"{}\"", e);
-            0
-        }
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse_all(input, input_parser)
     }
-}
 
-pub fn part2() -> usize {
-    let input = include_str!("../../data/2022/14.txt");
+    fn part1(formations: &Self::Input<'_>, _params: &Self::Params) -> Answer {
+        let (mut map, source, deepest) = build_cave(formations);
 
-    match input_parser(input) {
-        Ok((remaining_input, formations)) if remaining_input.is_empty() => {
-            println!("parsed entire input");
+        while simulate_sand_part1(&mut map, source, deepest + 2) == SandResult::Settled {}
 
-            let mut map = HashMap::new();
-
-            fill_map(&formations, &mut map);
+        count_sand(&map).into()
+    }
 
-            let deepest = map.iter().max_by(|x, y| x.0 .1.cmp(&y.0 .1)).unwrap().0 .1;
+    fn part2(formations: &Self::Input<'_>, _params: &Self::Params) -> Answer {
+        let (mut map, source, deepest) = build_cave(formations);
 
-            while simulate_sand_part2(&mut map, deepest + 1) == SandResult::Settled {}
+        while simulate_sand_part2(&mut map, source, deepest + 1) == SandResult::Settled {}
 
-            map.iter().filter(|(_, &spot)| spot == Spot::Sand).count()
-        }
-        Ok((remaining, _)) => {
-            println!("remaining unparsed \"{}\"", remaining);
-            0
-        }
-        Err(e) => {
-            println!("error parsing \"{}\"", e);
-            0
-        }
+        count_sand(&map).into()
     }
 }
 
-fn simulate_sand_part1(map: &mut HashMap<(isize, isize), Spot>, abyss_start: isize) -> SandResult {
-    let mut sand_position: (isize, isize) = (500, 0);
+fn count_sand(map: &Cave) -> usize {
+    map.cells()
+        .filter(|&&spot| spot == Some(Spot::Sand))
+        .count()
+}
+
+fn simulate_sand_part1(map: &mut Cave, source: Position, abyss_start: isize) -> SandResult {
+    let mut sand_position = source;
 
     while let Some(position) = find_next_position(map, sand_position) {
         sand_position = position;
@@ -93,94 +66,73 @@ fn simulate_sand_part1(map: &mut HashMap<(isize, isize), Spot>, abyss_start: isi
         }
     }
 
-    map.insert(sand_position, Spot::Sand);
+    map[sand_position] = Some(Spot::Sand);
 
     SandResult::Settled
 }
 
-fn simulate_sand_part2(map: &mut HashMap<(isize, isize), Spot>, floor_start: isize) -> SandResult {
-    let mut sand_position: (isize, isize) = (500, 0);
+fn simulate_sand_part2(map: &mut Cave, source: Position, floor_start: isize) -> SandResult {
+    let mut sand_position = source;
 
     while let Some(position) = find_next_position_part2(map, sand_position, floor_start) {
         sand_position = position;
     }
 
-    map.insert(sand_position, Spot::Sand);
+    map[sand_position] = Some(Spot::Sand);
 
     //not really accurate since no abyss, but 🤷‍♂️
-    if sand_position.1 == 0 {
+    if sand_position == source {
         SandResult::Abyss
     } else {
         SandResult::Settled
     }
 }
 
-fn find_next_position(
-    map: &HashMap<(isize, isize), Spot>,
-    sand_position: (isize, isize),
-) -> Option<(isize, isize)> {
+fn falling_to(sand_position: Position) -> [Position; 3] {
     let down = (sand_position.0, sand_position.1 + 1);
     let diagonal_left = (sand_position.0 - 1, sand_position.1 + 1);
     let diagonal_right = (sand_position.0 + 1, sand_position.1 + 1);
 
-    match map.get(&down) {
-        Some(_) => {}
-        None => return Some(down),
-    }
-
-    match map.get(&diagonal_left) {
-        Some(_) => {}
-        None => return Some(diagonal_left),
-    }
-
-    match map.get(&diagonal_right) {
-        Some(_) => {}
-        None => return Some(diagonal_right),
-    }
+    [down, diagonal_left, diagonal_right]
+}
 
-    None
+fn find_next_position(map: &Cave, sand_position: Position) -> Option<Position> {
+    falling_to(sand_position)
+        .into_iter()
+        .find(|&position| map.get(position) == Some(&None))
 }
 
 fn find_next_position_part2(
-    map: &HashMap<(isize, isize), Spot>,
-    sand_position: (isize, isize),
+    map: &Cave,
+    sand_position: Position,
     floor_start: isize,
-) -> Option<(isize, isize)> {
-    let down = (sand_position.0, sand_position.1 + 1);
-    let diagonal_left = (sand_position.0 - 1, sand_position.1 + 1);
-    let diagonal_right = (sand_position.0 + 1, sand_position.1 + 1);
-
-    match map.get(&down) {
-        Some(_) => {}
-        None => {
-            if down.1 <= floor_start {
-                return Some(down);
-            }
-        }
-    }
-
-    match map.get(&diagonal_left) {
-        Some(_) => {}
-        None => {
-            if diagonal_left.1 <= floor_start {
-                return Some(diagonal_left);
-            }
-        }
-    }
-
-    match map.get(&diagonal_right) {
-        Some(_) => {}
-        None => {
-            if diagonal_right.1 <= floor_start {
-                return Some(diagonal_right);
-            }
-        }
-    }
+) -> Option<Position> {
+    falling_to(sand_position)
+        .into_iter()
+        .find(|&position| position.1 <= floor_start && map.get(position) == Some(&None))
+}
 
-    return None;
+// sand can't spread further sideways than it falls, so the cave only has to reach that far
+// either side of the source. returns the cave, where the source ended up and the deepest rock
+fn build_cave(formations: &[Formation]) -> (Cave, Position, isize) {
+    let points = formations.iter().flatten();
+    let deepest = points.clone().map(|&(_, y)| y).max().unwrap();
+    let floor = deepest + 2;
+    let left = points
+        .clone()
+        .map(|&(x, _)| x)
+        .min()
+        .unwrap()
+        .min(500 - floor - 1);
+    let right = points.map(|&(x, _)| x).max().unwrap().max(500 + floor + 1);
+
+    let mut map = Grid::new((right - left + 1) as usize, (floor + 1) as usize, None);
+    fill_map(formations, left, &mut map);
+
+    (map, (500 - left, 0), deepest)
 }
 
-fn fill_map(formations: &[Formation], map: &mut HashMap<(isize, isize), Spot>) {
+fn fill_map(formations: &[Formation], x_offset: isize, map: &mut Cave) {
     for formation in formations {
         for line in formation.windows(2) {
             let end_1 = line[0];
@@ -195,7 +147,7 @@ fn fill_map(formations: &[Formation], map: &mut HashMap<(isize, isize), Spot>) {
                 };
 
                 for y in start.1..(end.1 + 1) {
-                    map.insert((end_1.0, y), Spot::Rock);
+                    map[(end_1.0 - x_offset, y)] = Some(Spot::Rock);
                 }
             } else {
                 //horizontal line
@@ -206,7 +158,7 @@ fn fill_map(formations: &[Formation], map: &mut HashMap<(isize, isize), Spot>) {
                 };
 
                 for x in start.0..(end.0 + 1) {
-                    map.insert((x, end_1.1), Spot::Rock);
+                    map[(x - x_offset, end_1.1)] = Some(Spot::Rock);
                 }
             }
         }
@@ -237,3 +189,21 @@ fn input_parser(input: &str) -> IResult<&str, Input> {
 
     Ok((input, formations))
 }
+
+#[cfg(test)]
+mod day14_tests {
+    use crate::day14::Day14;
+    use aoc_common::solution::{solve, Answer};
+
+    const EXAMPLE: &str = "498,4 -> 498,6 -> 496,6
+503,4 -> 502,4 -> 502,9 -> 494,9
+";
+
+    #[test]
+    fn example() {
+        assert_eq!(
+            solve::<Day14>(EXAMPLE, &[1, 2], &()).unwrap(),
+            [Answer::Integer(24), Answer::Integer(93)]
+        );
+    }
+}
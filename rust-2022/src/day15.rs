@@ -1,244 +1,119 @@
-use itertools::Itertools;
-use nom::{
-    bytes::complete::tag,
-    character::complete::anychar,
-    combinator::{map_res, opt},
-    multi::separated_list0,
-    multi::{many1, separated_list1},
-    IResult,
-};
-use std::collections::BinaryHeap;
-use std::collections::{HashMap, HashSet};
-use std::{cmp::Ordering, hash::Hash};
+use aoc_common::debug;
+use aoc_common::geometry::manhattan_distance;
+use aoc_common::params;
+use aoc_common::parse::{parse_all, parse_number, ParseError};
+use aoc_common::solution::{Answer, Solution};
+use aoc_common::{trace, warn};
+use nom::{bytes::complete::tag, multi::separated_list1, IResult};
+use std::collections::HashSet;
 
 type Input = Vec<SensorPlacement>;
 
 #[derive(Debug, Copy, Clone, Eq, PartialEq)]
-struct SensorPlacement {
+pub struct SensorPlacement {
     location: (isize, isize),
     closest_beacon: (isize, isize),
 }
 
-pub fn part1() -> isize {
-    // let input = include_str!("../../data/2022/15-example.txt");
-    // let row_to_check: isize = 10;
-    let input = include_str!("../../data/2022/15.txt");
-    let row_to_check: isize = 2000000;
-
-    match input_parser(input) {
-        Ok((remaining_input, sensors)) if remaining_input.is_empty() => {
-            println!("parsed entire input");
-
-            // let mut sum: isize = 0;
-
-            // for (index, sensor) in sensors
-            //     .iter()
-            //     .sorted_by(|a, b| a.location.0.cmp(&b.location.0))
-            //     .enumerate()
-            // {
-            //     let count = count_on_line(sensor, row_to_check) as isize;
-
-            //     if count == 0 {
-            //         continue;
-            //     }
-
-            //     println!("on the line {}", count);
-
-            //     sum += count;
-
-            //     //if the beacon is on the line, it doesn't count
-            //     if sensor.closest_beacon.1 == row_to_check {
-            //         println!("B on the line {:?}", sensor.closest_beacon);
-            //         sum -= 1;
-            //     }
-
-            //     for other_sensor in sensors.iter().skip(index + 1) {
-            //         if other_sensor.location == sensor.location {
-            //             continue;
-            //         }
-
-            //         // let radius = manhattan_distance(sensor.location, sensor.closest_beacon);
-            //         // let other_radius =
-            //         //     manhattan_distance(other_sensor.location, other_sensor.closest_beacon);
-            //         // let distance_between =
-            //         //     manhattan_distance(sensor.location, other_sensor.location);
-
-            //         let amount_overlapping =
-            //             amount_overlapping_on_line(sensor, other_sensor, row_to_check);
-            //         sum -= amount_overlapping as isize;
-
-            //         if amount_overlapping > 0 {
-            //             println!("overlapping {}", amount_overlapping);
-            //         }
-            //     }
-
-            //     //if double covered, subtract how much
-            //     // sum -= count_on_line(&sensor, row_to_check);
-            // }
-
-            // sum as usize
-
-            let mut projections = vec![];
-            let mut beacons: HashSet<(isize, isize)> = HashSet::new();
-            for sensor in sensors.iter() {
-                if let Some(projection) = project_on_line(sensor, row_to_check) {
-                    println!("projected {:?} on line {:?}", sensor, projection);
-                    add_projection(&mut projections, projection);
-                    println!("after adding {:?}", projections);
-                }
-
-                beacons.insert(sensor.closest_beacon);
-            }
-
-            let mut sum = 0;
-            for projection in projections {
-                sum += 1 + projection.1 - projection.0;
-            }
-
-            //remove spots taken by beacons
-            let beacons_on_line = beacons.iter().filter(|b| b.1 == row_to_check).count();
-
-            sum - beacons_on_line as isize
-        }
-        Ok((remaining, _)) => {
-            println!("remaining unparsed \"{}\"", remaining);
-            0
-        }
-        Err(e) => {
-            println!("error parsing \"{}\"", e);
-            0
-        }
+params! {
+    pub struct Day15Params {
+        row: isize = (example: 10, real: 2_000_000),
+        search_size: isize = (example: 20, real: 4_000_000),
     }
 }
 
-pub fn part2() -> isize {
-    // let input = include_str!("../../data/2022/15-example.txt");
-    // let min_y = 0;
-    // let max_y = 20;
-    let input = include_str!("../../data/2022/15.txt");
-    let min_y = 0;
-    let max_y = 4000000;
-
-    match input_parser(input) {
-        Ok((remaining_input, sensors)) if remaining_input.is_empty() => {
-            println!("parsed entire input");
-
-            for row_to_check in min_y..(max_y + 1) {
-                let mut projections = vec![];
-                let mut beacons: HashSet<(isize, isize)> = HashSet::new();
-                for sensor in sensors.iter() {
-                    if let Some(projection) = project_on_line(sensor, row_to_check) {
-                        // println!("projected {:?} on line {:?}", sensor, projection);
-                        add_projection(&mut projections, projection);
-                        // println!("after adding {:?}", projections);
-                    }
-
-                    beacons.insert(sensor.closest_beacon);
-                }
+pub struct Day15;
 
-                let mut last_projections_len = projections.len();
-                projections.sort_by(|a, b| a.0.cmp(&b.0));
-                let mut joined_projections = projections.to_owned();
-                loop {
-                    joined_projections = join_sorted_projections(&joined_projections);
-                    if joined_projections.len() == last_projections_len {
-                        break;
-                    }
+impl Solution for Day15 {
+    type Input<'a> = Input;
 
-                    last_projections_len = joined_projections.len();
-                }
-
-                if joined_projections.len() == 1 {
-                    println!("no space in row {}", row_to_check);
-                } else {
-                    println!("chunks: {:?}", joined_projections);
-                    let y = row_to_check;
-                    let x = joined_projections[0].1 + 1;
-
-                    if x >= 0 || x <= 4000000 {
-                        println!("found: {:?}", (x, y));
-                        return x * 4000000 + y;
-                    }
-                }
+    type Params = Day15Params;
 
-                // let mut sum = 0;
-                // for projection in projections {
-                //     sum += 1 + projection.1 - projection.0;
-                // }
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse_all(input, input_parser)
+    }
 
-                //remove spots taken by beacons
-                // let beacons_on_line = beacons.iter().filter(|b| b.1 == row_to_check).count();
+    fn part1(sensors: &Self::Input<'_>, params: &Self::Params) -> Answer {
+        let row_to_check = params.row;
 
-                // println!("spots unavailable {}", sum - beacons_on_line as isize);
+        let mut projections = vec![];
+        let mut beacons: HashSet<(isize, isize)> = HashSet::new();
+        for sensor in sensors.iter() {
+            if let Some(projection) = project_on_line(sensor, row_to_check) {
+                trace!("projected {:?} on line {:?}", sensor, projection);
+                add_projection(&mut projections, projection);
+                trace!("after adding {:?}", projections);
             }
 
-            0
-        }
-        Ok((remaining, _)) => {
-            println!("remaining unparsed \"{}\"", remaining);
-            0
-        }
-        Err(e) => {
-            println!("error parsing \"{}\"", e);
-            0
+            beacons.insert(sensor.closest_beacon);
         }
-    }
-}
 
-fn add_coordinates_inside_range(
-    start: (isize, isize),
-    range: usize,
-    coordinates: &mut HashSet<(isize, isize)>,
-) {
-    for x_change in 0..(range + 1) {
-        for y_change in 0..(range - x_change + 1) {
-            coordinates.insert((start.0 + (x_change as isize), start.1 + (y_change as isize)));
-            coordinates.insert((start.0 - (x_change as isize), start.1 + (y_change as isize)));
-            coordinates.insert((start.0 + (x_change as isize), start.1 - (y_change as isize)));
-            coordinates.insert((start.0 - (x_change as isize), start.1 - (y_change as isize)));
+        let mut sum = 0;
+        for projection in projections {
+            sum += 1 + projection.1 - projection.0;
         }
+
+        //remove spots taken by beacons
+        let beacons_on_line = beacons.iter().filter(|b| b.1 == row_to_check).count();
+
+        (sum - beacons_on_line as isize).into()
     }
-}
 
-fn count_on_line(sensor: &SensorPlacement, y_axis: isize) -> usize {
-    let radius = manhattan_distance(sensor.location, sensor.closest_beacon);
+    fn part2(sensors: &Self::Input<'_>, params: &Self::Params) -> Answer {
+        let min_y = 0;
+        let max_y = params.search_size;
 
-    let closest_point_on_line = (sensor.location.0, y_axis);
+        for row_to_check in min_y..(max_y + 1) {
+            let mut projections = vec![];
+            let mut beacons: HashSet<(isize, isize)> = HashSet::new();
+            for sensor in sensors.iter() {
+                if let Some(projection) = project_on_line(sensor, row_to_check) {
+                    // trace!("projected {:?} on line {:?}", sensor, projection);
+                    add_projection(&mut projections, projection);
+                    // trace!("after adding {:?}", projections);
+                }
 
-    let line_distance_from_sensor = manhattan_distance(sensor.location, closest_point_on_line);
+                beacons.insert(sensor.closest_beacon);
+            }
 
-    if line_distance_from_sensor > radius {
-        0
-    } else {
-        1 + (radius - line_distance_from_sensor) * 2
-    }
-}
+            let mut last_projections_len = projections.len();
+            projections.sort_by_key(|a| a.0);
+            let mut joined_projections = projections.to_owned();
+            loop {
+                joined_projections = join_sorted_projections(&joined_projections);
+                if joined_projections.len() == last_projections_len {
+                    break;
+                }
 
-fn amount_overlapping_on_line(a: &SensorPlacement, b: &SensorPlacement, y_axis: isize) -> usize {
-    let radius_a = manhattan_distance(a.location, a.closest_beacon);
-    let radius_b = manhattan_distance(b.location, b.closest_beacon);
-    // let distance_between = manhattan_distance(a.location, b.location);
+                last_projections_len = joined_projections.len();
+            }
 
-    let middle_point_on_line_a = (a.location.0, y_axis);
-    let middle_point_on_line_b = (b.location.0, y_axis);
+            if joined_projections.len() == 1 {
+                trace!("no space in row {}", row_to_check);
+            } else {
+                debug!("chunks: {:?}", joined_projections);
+                let y = row_to_check;
+                let x = joined_projections[0].1 + 1;
 
-    let line_distance_from_a = manhattan_distance(a.location, middle_point_on_line_a);
-    let line_distance_from_b = manhattan_distance(b.location, middle_point_on_line_b);
+                if x >= 0 && x <= params.search_size {
+                    debug!("found: {:?}", (x, y));
+                    return (x * 4000000 + y).into();
+                }
+            }
 
-    if line_distance_from_a > radius_a || line_distance_from_b > radius_b {
-        return 0;
-    }
+            // let mut sum = 0;
+            // for projection in projections {
+            //     sum += 1 + projection.1 - projection.0;
+            // }
 
-    let width_at_line_a = 1 + (radius_a - line_distance_from_a); //2
-    let width_at_line_b = 1 + (radius_b - line_distance_from_b); //2
+            //remove spots taken by beacons
+            // let beacons_on_line = beacons.iter().filter(|b| b.1 == row_to_check).count();
 
-    let overlap = width_at_line_a as isize + width_at_line_b as isize
-        - (a.location.0.abs_diff(b.location.0) + 1) as isize;
+            // println!("spots unavailable {}", sum - beacons_on_line as isize);
+        }
 
-    if overlap < 0 {
-        0
-    } else {
-        overlap as usize
+        warn!("no spot in the search area is out of every sensor's range");
+        Answer::Unsolved
     }
 }
 
@@ -338,29 +213,13 @@ fn join_sorted_projections(projections: &[(isize, isize)]) -> Vec<(isize, isize)
     results
 }
 
-fn manhattan_distance(a: (isize, isize), b: (isize, isize)) -> usize {
-    a.0.abs_diff(b.0) + a.1.abs_diff(b.1)
-}
-
 fn coordinate_parser(input: &str) -> IResult<&str, (isize, isize)> {
     let (input, _) = tag("x=")(input)?;
-    let (input, x_negative_sign) = opt(nom::character::complete::char('-'))(input)?;
-    let (input, x) = map_res(nom::character::complete::digit1, |s: &str| {
-        s.parse::<isize>()
-    })(input)?;
+    let (input, x) = parse_number(input)?;
     let (input, _) = tag(", y=")(input)?;
-    let (input, y_negative_sign) = opt(nom::character::complete::char('-'))(input)?;
-    let (input, y) = map_res(nom::character::complete::digit1, |s: &str| {
-        s.parse::<isize>()
-    })(input)?;
+    let (input, y) = parse_number(input)?;
 
-    Ok((
-        input,
-        (
-            if x_negative_sign.is_some() { -x } else { x },
-            if y_negative_sign.is_some() { -y } else { y },
-        ),
-    ))
+    Ok((input, (x, y)))
 }
 
 fn sensor_placement_parser(input: &str) -> IResult<&str, SensorPlacement> {
@@ -387,115 +246,10 @@ fn input_parser(input: &str) -> IResult<&str, Input> {
 
 #[cfg(test)]
 mod day15_tests {
-    use crate::day15::add_coordinates_inside_range;
-    use crate::day15::{
-        amount_overlapping_on_line, count_on_line, join_sorted_projections, split_at_overlap,
-        SensorPlacement,
-    };
-    use std::collections::HashSet;
-
-    #[test]
-    fn test_coordinates_inside_range() {
-        let mut set = HashSet::new();
-        add_coordinates_inside_range((0, 0), 2, &mut set);
-        assert_eq!(set.len(), 13);
-    }
-
-    #[test]
-    fn test_count_on_line_when_on_tip() {
-        assert_eq!(
-            count_on_line(
-                &SensorPlacement {
-                    location: (0, 0),
-                    closest_beacon: (1, 1),
-                },
-                2
-            ),
-            1
-        );
-    }
-
-    #[test]
-    fn test_count_on_line_when_half_way() {
-        assert_eq!(
-            count_on_line(
-                &SensorPlacement {
-                    location: (0, 0),
-                    closest_beacon: (1, 1),
-                },
-                1
-            ),
-            3
-        );
-    }
-
-    #[test]
-    fn test_count_on_line_when_on_center() {
-        assert_eq!(
-            count_on_line(
-                &SensorPlacement {
-                    location: (0, 0),
-                    closest_beacon: (1, 1),
-                },
-                0
-            ),
-            5
-        );
-    }
-
-    #[test]
-    fn test_amount_overlapping_on_line_1() {
-        assert_eq!(
-            amount_overlapping_on_line(
-                &SensorPlacement {
-                    location: (0, 0),
-                    closest_beacon: (1, 0),
-                },
-                &SensorPlacement {
-                    location: (2, 1),
-                    closest_beacon: (1, 0),
-                },
-                0
-            ),
-            1
-        );
-    }
-
-    #[test]
-    fn test_amount_overlapping_on_line_2() {
-        assert_eq!(
-            amount_overlapping_on_line(
-                &SensorPlacement {
-                    location: (8, 7),
-                    closest_beacon: (2, 10),
-                },
-                &SensorPlacement {
-                    location: (2, 0),
-                    closest_beacon: (2, 10),
-                },
-                10
-            ),
-            1
-        );
-    }
-
-    #[test]
-    fn test_amount_overlapping_on_line_3() {
-        assert_eq!(
-            amount_overlapping_on_line(
-                &SensorPlacement {
-                    location: (8, 7),
-                    closest_beacon: (2, 10),
-                },
-                &SensorPlacement {
-                    location: (0, 11),
-                    closest_beacon: (2, 10),
-                },
-                10
-            ),
-            1
-        );
-    }
+    use crate::day15::{join_sorted_projections, split_at_overlap};
+    use crate::day15::{Day15, Day15Params};
+    use aoc_common::params::Params;
+    use aoc_common::solution::{solve, Answer};
 
     #[test]
     fn test_split_at_overlap() {
@@ -517,4 +271,36 @@ mod day15_tests {
             vec![(-8, 14), (15, 26)]
         );
     }
+
+    const EXAMPLE: &str = "Sensor at x=2, y=18: closest beacon is at x=-2, y=15
+Sensor at x=9, y=16: closest beacon is at x=10, y=16
+Sensor at x=13, y=2: closest beacon is at x=15, y=3
+Sensor at x=12, y=14: closest beacon is at x=10, y=16
+Sensor at x=10, y=20: closest beacon is at x=10, y=16
+Sensor at x=14, y=17: closest beacon is at x=10, y=16
+Sensor at x=8, y=7: closest beacon is at x=2, y=10
+Sensor at x=2, y=0: closest beacon is at x=2, y=10
+Sensor at x=0, y=11: closest beacon is at x=2, y=10
+Sensor at x=20, y=14: closest beacon is at x=25, y=17
+Sensor at x=17, y=20: closest beacon is at x=21, y=22
+Sensor at x=16, y=7: closest beacon is at x=15, y=3
+Sensor at x=14, y=3: closest beacon is at x=15, y=3
+Sensor at x=20, y=1: closest beacon is at x=15, y=3
+";
+
+    #[test]
+    fn example_part1() {
+        assert_eq!(
+            solve::<Day15>(EXAMPLE, &[1], &Day15Params::example()).unwrap(),
+            [Answer::Integer(26)]
+        );
+    }
+
+    #[test]
+    fn example_part2() {
+        assert_eq!(
+            solve::<Day15>(EXAMPLE, &[2], &Day15Params::example()).unwrap(),
+            [Answer::Integer(56000011)]
+        );
+    }
 }
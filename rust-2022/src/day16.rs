@@ -1,225 +1,199 @@
-use itertools::Itertools;
+use aoc_common::params;
+use aoc_common::parse::{parse_all, ParseError};
+use aoc_common::solution::{Answer, Solution};
+use aoc_common::{debug, warn};
 use nom::{
-    branch::alt,
-    bytes::complete::tag,
-    character::complete::anychar,
-    combinator::{map_res, opt},
-    multi::separated_list0,
-    multi::{many1, separated_list1},
-    IResult,
+    branch::alt, bytes::complete::tag, combinator::map_res, multi::separated_list1, IResult,
 };
-use std::collections::{BinaryHeap, VecDeque};
-use std::collections::{HashMap, HashSet};
-use std::{cmp::Ordering, hash::Hash};
 
 type Input = Vec<ValveDescription>;
 
 type Location = (char, char);
 
+const START: Location = ('A', 'A');
+
+params! {
+    // part 2 has elephants helping out, once we've spent some of the time teaching them how
+    pub struct Day16Params {
+        minutes: usize = (example: 30, real: 30),
+        elephants: usize = (example: 1, real: 1),
+        teaching_minutes: usize = (example: 4, real: 4),
+    }
+}
+
 #[derive(Debug, Clone, Eq, PartialEq)]
-struct ValveDescription {
+pub struct ValveDescription {
     label: Location,
     flow_rate: usize,
     connections: Vec<Location>,
 }
 
-#[derive(Debug, Copy, Clone, Eq, PartialEq)]
-enum Step {
-    Move(Location),
-    OpenValve(Location),
+// just the valves worth opening and how long it takes to walk between them, with where everyone
+// starts added on the end, even when it's also one of the valves. valve `i` is bit `i` in a set of
+// valves
+pub struct Network {
+    labels: Vec<Location>,
+    flow_rates: Vec<usize>,
+    distances: Vec<Vec<usize>>,
 }
 
-struct Opportunity {
-    moves: VecDeque<Step>,
-    value: usize,
-}
-
-pub fn part1() -> usize {
-    let input = include_str!("../../data/2022/16-example.txt");
-
-    match input_parser(input) {
-        Ok((remaining_input, valves)) if remaining_input.is_empty() => {
-            println!("parsed entire input");
-            let mut time_remaining = 30;
-
-            let mut location = ('A', 'A');
-            let mut pressure_relieved_per_minute = 0;
-            let mut total_pressure_released = 0;
-            let mut open_valves = HashSet::new();
-            let mut next_steps: VecDeque<Step> = VecDeque::new();
-            while time_remaining > 0 {
-                total_pressure_released += pressure_relieved_per_minute;
-                print!("releasing {} pressure. ", pressure_relieved_per_minute);
-
-                if next_steps.is_empty() {
-                    next_steps = find_next_steps(&valves, &open_valves, location, time_remaining);
-                    println!("plan length: {}", next_steps.len());
-                }
+pub struct Day16;
 
-                match next_steps.pop_front().unwrap() {
-                    Step::Move(new_location) => {
-                        location = new_location;
-                        println!("Moving to {:?}", new_location);
-                    }
-                    Step::OpenValve(room_label) => {
-                        let flow_rate = valves
-                            .iter()
-                            .find(|r| r.label == room_label)
-                            .unwrap()
-                            .flow_rate;
-                        pressure_relieved_per_minute += flow_rate;
-                        open_valves.insert(location);
-                        println!("Opening valve worth {}", flow_rate);
-                    }
-                }
+impl Solution for Day16 {
+    type Input<'a> = Input;
 
-                time_remaining -= 1;
-            }
+    type Params = Day16Params;
 
-            total_pressure_released
-        }
-        Ok((remaining, _)) => {
-            println!("remaining unparsed \"{}\"", remaining);
-            0
-        }
-        Err(e) => {
-            println!("error parsing \"{}\"", e);
-            0
-        }
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse_all(input, input_parser)
     }
-}
-
-pub fn part2() -> usize {
-    let input = include_str!("../../data/2022/16-example.txt");
 
-    match input_parser(input) {
-        Ok((remaining_input, valves)) if remaining_input.is_empty() => {
-            println!("parsed entire input");
+    fn part1(valves: &Self::Input<'_>, params: &Self::Params) -> Answer {
+        let Some(network) = Network::compress(valves, START) else {
+            warn!("there's no valve {}{} to start from", START.0, START.1);
+            return Answer::Unsolved;
+        };
 
-            0
-        }
-        Ok((remaining, _)) => {
-            println!("remaining unparsed \"{}\"", remaining);
-            0
-        }
-        Err(e) => {
-            println!("error parsing \"{}\"", e);
-            0
-        }
+        network.most_pressure(params.minutes, 1).into()
     }
-}
 
-fn find_next_steps(
-    valves: &[ValveDescription],
-    open_valves: &HashSet<Location>,
-    current_location: Location,
-    time_remaining: usize,
-) -> VecDeque<Step> {
-    let opportunities =
-        // greedy_build_opportunities(valves, open_valves, current_location, time_remaining);
-        build_opportunities(valves, open_valves, current_location, time_remaining, 17);
-    let best_move = opportunities
-        .iter()
-        .max_by(|a, b| {
-            a.value
-                .cmp(&b.value)
-                .then_with(|| b.moves.len().cmp(&a.moves.len()))
-        })
-        .unwrap();
+    fn part2(valves: &Self::Input<'_>, params: &Self::Params) -> Answer {
+        let Some(network) = Network::compress(valves, START) else {
+            warn!("there's no valve {}{} to start from", START.0, START.1);
+            return Answer::Unsolved;
+        };
 
-    best_move.moves.to_owned()
+        let minutes = params.minutes.saturating_sub(params.teaching_minutes);
+        network.most_pressure(minutes, 1 + params.elephants).into()
+    }
 }
 
-fn greedy_build_opportunities(
-    valves: &[ValveDescription],
-    open_valves: &HashSet<Location>,
-    current_location: Location,
-    time_remaining: usize,
-) -> Vec<Opportunity> {
-    let mut visited: HashSet<Location> = HashSet::new();
-    let mut to_visit: Vec<(Location, Vec<Location>)> = vec![(current_location, vec![])];
-
-    let mut opportunities = vec![];
-    while let Some((room_label, path)) = to_visit.pop() {
-        if path.len() > time_remaining {
-            continue;
+impl Network {
+    // floyd-warshall over every valve, then keeping the rows and columns for the ones that matter
+    pub fn compress(valves: &[ValveDescription], start: Location) -> Option<Network> {
+        let index_of = |label: Location| valves.iter().position(|v| v.label == label);
+        let start = index_of(start)?;
+
+        let mut distances = vec![vec![usize::MAX; valves.len()]; valves.len()];
+        for (from, valve) in valves.iter().enumerate() {
+            distances[from][from] = 0;
+            for &connection in &valve.connections {
+                if let Some(to) = index_of(connection) {
+                    distances[from][to] = 1;
+                }
+            }
+        }
+        for via in 0..valves.len() {
+            for from in 0..valves.len() {
+                for to in 0..valves.len() {
+                    let through = distances[from][via].saturating_add(distances[via][to]);
+                    if through < distances[from][to] {
+                        distances[from][to] = through;
+                    }
+                }
+            }
         }
 
-        let room = valves.iter().find(|r| r.label == room_label).unwrap();
-        let value = if open_valves.contains(&room_label) {
-            0
-        } else {
-            (time_remaining - path.len()) * room.flow_rate
-        };
+        // the start is only a valve like the rest when it has any flow, but it's always on the end
+        let kept: Vec<usize> = (0..valves.len())
+            .filter(|&index| valves[index].flow_rate > 0)
+            .chain([start])
+            .collect();
+        debug!(
+            "{} of {} valves are worth opening",
+            kept.len() - 1,
+            valves.len()
+        );
+
+        Some(Network {
+            labels: kept.iter().map(|&index| valves[index].label).collect(),
+            flow_rates: kept.iter().map(|&index| valves[index].flow_rate).collect(),
+            distances: kept
+                .iter()
+                .map(|&from| kept.iter().map(|&to| distances[from][to]).collect())
+                .collect(),
+        })
+    }
 
-        let mut moves = VecDeque::from_iter(path.iter().map(|&to_room| Step::Move(to_room)));
-        moves.push_back(Step::OpenValve(room_label));
+    fn valve_count(&self) -> usize {
+        self.labels.len() - 1
+    }
 
-        opportunities.push(Opportunity { value, moves });
+    // the most pressure one person can let out in the time by opening each set of valves, and
+    // nothing else. sets nobody can get round in time are left at 0
+    fn best_by_valves(&self, minutes: usize) -> Vec<usize> {
+        let mut best = vec![0; 1 << self.valve_count()];
+        self.explore(self.valve_count(), minutes, 0, 0, &mut best);
 
-        for connection in room.connections.iter() {
-            if !visited.contains(connection) {
-                visited.insert(*connection);
-                let mut this_path = path.clone();
-                this_path.push(*connection);
-                to_visit.push((*connection, this_path))
-            }
-        }
+        best
     }
 
-    opportunities
-}
+    fn explore(
+        &self,
+        at: usize,
+        minutes_left: usize,
+        opened: usize,
+        pressure: usize,
+        best: &mut [usize],
+    ) {
+        best[opened] = best[opened].max(pressure);
+
+        for next in 0..self.valve_count() {
+            let bit = 1 << next;
+            // the walk there and a minute to open it, which is only worth it with time to spare
+            let cost = self.distances[at][next].saturating_add(1);
+            if opened & bit != 0 || cost >= minutes_left {
+                continue;
+            }
 
-fn build_opportunities(
-    valves: &[ValveDescription],
-    open_valves: &HashSet<Location>,
-    current_location: Location,
-    time_remaining: usize,
-    max_size: usize,
-) -> Vec<Opportunity> {
-    let mut to_visit: Vec<(usize, Location, VecDeque<Step>)> =
-        vec![(0, current_location, VecDeque::new())];
-
-    let mut opportunities = vec![];
-    while let Some((current_value, room_label, moves)) = to_visit.pop() {
-        // println!(
-        //     "oppoertunities: {} to_visit: {}",
-        //     opportunities.len(),
-        //     to_visit.len()
-        // );
-        if moves.len() > time_remaining || moves.len() == max_size {
-            opportunities.push(Opportunity {
-                value: current_value,
-                moves,
-            });
-            continue;
+            let minutes_left = minutes_left - cost;
+            let pressure = pressure + minutes_left * self.flow_rates[next];
+            self.explore(next, minutes_left, opened | bit, pressure, best);
         }
+    }
 
-        let room = valves.iter().find(|r| r.label == room_label).unwrap();
-        let room_value = if open_valves.contains(&room_label) {
-            0
-        } else {
-            (time_remaining - moves.len()) * room.flow_rate
-        };
-
-        //create possible moves
+    // the most pressure `agents` people can let out between them. they each open a separate set of
+    // valves, so it's the best way of splitting the valves up among them
+    pub fn most_pressure(&self, minutes: usize, agents: usize) -> usize {
+        if agents == 0 {
+            return 0;
+        }
 
-        //generate open
-        if !open_valves.contains(&room_label) && !moves.contains(&Step::OpenValve(room_label)) {
-            let mut next_moves = moves.clone();
-            next_moves.push_back(Step::OpenValve(room_label));
-            to_visit.push((current_value + room_value, room_label, next_moves))
+        // what one person gets from the valves in a set, not necessarily opening all of them
+        let mut alone = self.best_by_valves(minutes);
+        for bit in 0..self.valve_count() {
+            for valves in 0..alone.len() {
+                if valves & 1 << bit != 0 {
+                    alone[valves] = alone[valves].max(alone[valves ^ 1 << bit]);
+                }
+            }
         }
 
-        //generate keep moving
-        for connection in room.connections.iter() {
-            let mut next_moves = moves.clone();
-            next_moves.push_back(Step::Move(*connection));
-            to_visit.push((current_value, *connection, next_moves))
+        // each extra person takes some of the valves and leaves the rest to the team so far
+        let mut team = alone.clone();
+        for _ in 1..agents {
+            team = (0..team.len())
+                .map(|valves| {
+                    subsets(valves)
+                        .map(|taken| alone[taken] + team[valves ^ taken])
+                        .max()
+                        .unwrap()
+                })
+                .collect();
         }
+
+        team[team.len() - 1]
     }
+}
 
-    opportunities
+// every subset of a set of bits, including the whole thing and the empty set
+fn subsets(set: usize) -> impl Iterator<Item = usize> {
+    let mut next = Some(set);
+    std::iter::from_fn(move || {
+        let subset = next?;
+        next = subset.checked_sub(1).map(|below| below & set);
+        Some(subset)
+    })
 }
 
 fn location_name_parser(input: &str) -> IResult<&str, Location> {
@@ -257,3 +231,83 @@ fn input_parser(input: &str) -> IResult<&str, Input> {
 
     Ok((input, formations))
 }
+
+#[cfg(test)]
+mod day16_tests {
+    use crate::day16::{Day16, Day16Params, Network, START};
+    use aoc_common::params::Params;
+    use aoc_common::solution::{solve, Answer, Solution};
+
+    const EXAMPLE: &str = "Valve AA has flow rate=0; tunnels lead to valves DD, II, BB
+Valve BB has flow rate=13; tunnels lead to valves CC, AA
+Valve CC has flow rate=2; tunnels lead to valves DD, BB
+Valve DD has flow rate=20; tunnels lead to valves CC, AA, EE
+Valve EE has flow rate=3; tunnels lead to valves FF, DD
+Valve FF has flow rate=0; tunnels lead to valves EE, GG
+Valve GG has flow rate=0; tunnels lead to valves FF, HH
+Valve HH has flow rate=22; tunnel leads to valve GG
+Valve II has flow rate=0; tunnels lead to valves AA, JJ
+Valve JJ has flow rate=21; tunnel leads to valve II
+";
+
+    #[test]
+    fn example() {
+        assert_eq!(
+            solve::<Day16>(EXAMPLE, &[1, 2], &Day16Params::example()).unwrap(),
+            [Answer::Integer(1651), Answer::Integer(1707)]
+        );
+    }
+
+    #[test]
+    fn compresses_to_the_valves_worth_opening() {
+        let network = Network::compress(&Day16::parse(EXAMPLE).unwrap(), START).unwrap();
+
+        let labels: Vec<String> = network
+            .labels
+            .iter()
+            .map(|&(a, b)| format!("{a}{b}"))
+            .collect();
+        assert_eq!(labels, ["BB", "CC", "DD", "EE", "HH", "JJ", "AA"]);
+        assert_eq!(network.distances[6], [1, 2, 1, 2, 5, 2, 0]);
+        assert_eq!(network.distances[4][5], 7);
+    }
+
+    #[test]
+    fn more_agents() {
+        let network = Network::compress(&Day16::parse(EXAMPLE).unwrap(), START).unwrap();
+
+        assert_eq!(network.most_pressure(26, 0), 0);
+        assert_eq!(network.most_pressure(26, 2), 1707);
+        // with someone for every valve, each goes straight to theirs
+        let straight_there = 24 * 13 + 23 * 2 + 24 * 20 + 23 * 3 + 20 * 22 + 23 * 21;
+        assert_eq!(network.most_pressure(26, 6), straight_there);
+        assert_eq!(network.most_pressure(26, 9), straight_there);
+
+        let three = network.most_pressure(26, 3);
+        assert!(three > 1707 && three < straight_there);
+    }
+
+    #[test]
+    fn opening_the_start() {
+        let valves = Day16::parse(
+            "Valve AA has flow rate=10; tunnel leads to valve BB
+Valve BB has flow rate=1; tunnel leads to valve AA
+",
+        )
+        .unwrap();
+        let network = Network::compress(&valves, START).unwrap();
+        assert_eq!(network.distances[0], [0, 1, 0]);
+
+        assert_eq!(network.most_pressure(30, 1), 29 * 10 + 27);
+        assert_eq!(network.most_pressure(26, 2), 25 * 10 + 24);
+    }
+
+    #[test]
+    fn no_time() {
+        let network = Network::compress(&Day16::parse(EXAMPLE).unwrap(), START).unwrap();
+
+        assert_eq!(network.most_pressure(0, 1), 0);
+        assert_eq!(network.most_pressure(2, 1), 0);
+        assert_eq!(network.most_pressure(3, 1), 20);
+    }
+}
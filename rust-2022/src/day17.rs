@@ -1,23 +1,20 @@
+use aoc_common::cycle::find_cycle;
+use aoc_common::frame;
+use aoc_common::grid::Grid;
+use aoc_common::params;
+use aoc_common::parse::{parse_all, ParseError};
+use aoc_common::render::{Canvas, Cell};
+use aoc_common::solution::{Answer, Solution};
 use itertools::Itertools;
-use nom::{
-    branch::alt,
-    bytes::complete::tag,
-    character::complete::anychar,
-    combinator::{map_res, opt},
-    multi::separated_list0,
-    multi::{many1, separated_list1},
-    IResult,
-};
-use std::collections::BinaryHeap;
-use std::collections::{HashMap, HashSet};
-use std::{cmp::Ordering, hash::Hash};
+use nom::{branch::alt, multi::many1, IResult};
+use std::collections::HashSet;
 
 type Input = Vec<Direction>;
 
 type Formation = Vec<(isize, isize)>;
 
 #[derive(Debug, Copy, Clone, Eq, PartialEq)]
-enum Direction {
+pub enum Direction {
     Left,
     Right,
 }
@@ -37,134 +34,121 @@ fn get_formations() -> Vec<Formation> {
     vec![minus, plus, corner, pipe, square]
 }
 
-pub fn part1() -> isize {
-    let input = include_str!("../../data/2022/17.txt");
-
-    match input_parser(input) {
-        Ok((remaining_input, shifts)) if remaining_input.is_empty() => {
-            println!("parsed entire input");
-
-            let formations = get_formations();
-            let mut grid: HashSet<(isize, isize)> = HashSet::new();
-            let mut rock = next_rock(&grid, &(formations[0]));
-            let mut round = 0;
-            let mut total_rock_count = 1;
-            while total_rock_count <= 2022 {
-                let shift = shifts[round % shifts.len()];
-                match step(&grid, &rock, &shift) {
-                    StepResult::Settled(new_position) => {
-                        grid.extend(new_position.iter());
-                        total_rock_count += 1;
-                        rock = next_rock(&grid, &(formations[(total_rock_count - 1) % 5]));
-                    }
-                    StepResult::Moved(new_position) => rock = new_position,
-                }
+params! {
+    pub struct Day17Params {
+        part1_rocks: u64 = (example: 2022, real: 2022),
+        part2_rocks: u64 = (example: 1_000_000_000_000, real: 1_000_000_000_000),
+    }
+}
 
-                // print_grid(&grid, Some(&rock));
-                // println!("\n\n");
-                round += 1;
-            }
+pub struct Day17;
 
-            // print_grid(&grid, None);
+impl Solution for Day17 {
+    type Input<'a> = Input;
 
-            max_height(&grid).unwrap_or(0) + 1
-        }
-        Ok((remaining, _)) => {
-            println!("remaining unparsed \"{}\"", remaining);
-            0
-        }
-        Err(e) => {
-            println!("error parsing \"{}\"", e);
-            0
-        }
+    type Params = Day17Params;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse_all(input, input_parser)
     }
+
+    fn part1(shifts: &Self::Input<'_>, params: &Self::Params) -> Answer {
+        tower_height(shifts, params.part1_rocks).into()
+    }
+
+    fn part2(shifts: &Self::Input<'_>, params: &Self::Params) -> Answer {
+        tower_height(shifts, params.part2_rocks).into()
+    }
+}
+
+// rows no falling rock can reach any more are cut off and only counted in `floor_height`
+#[derive(Debug, Clone)]
+struct Chamber {
+    grid: HashSet<(isize, isize)>,
+    floor_height: isize,
+    next_shift: usize,
+    next_formation: usize,
 }
 
-pub fn part2() -> isize {
-    let input = include_str!("../../data/2022/17.txt");
-
-    match input_parser(input) {
-        Ok((remaining_input, shifts)) if remaining_input.is_empty() => {
-            println!("parsed entire input");
-
-            let formations = get_formations();
-            let mut grid: HashSet<(isize, isize)> = HashSet::new();
-            let mut total_rock_count: u64 = 1;
-            let mut floor_height = 0;
-            let mut memory: HashMap<(usize, usize), (u64, isize)> = HashMap::new();
-
-            let mut shift_infinite_iter = shifts.iter().enumerate().cycle();
-            let mut formation_infinite_iter = formations.iter().enumerate().cycle();
-
-            let mut rock = next_rock(&grid, formation_infinite_iter.next().unwrap().1);
-            let goal_rocks = 1_000_000_000_000;
-            // let goal_rocks = 2022;
-            while total_rock_count <= goal_rocks {
-                // if round % total_shifts == 0 && (total_rock_count - 1) % total_formations == 0 {
-                //     // println!("{}%", total_rock_count * 100 / 1_000_000_000_000);
-                //     println!(
-                //         "\n\n\ndone with round. height: {}\n\n\n",
-                //         max_height(&grid).unwrap_or(0) + floor_height
-                //     );
-                // }
-
-                let (shift_index, shift) = shift_infinite_iter.next().unwrap();
-                match step(&grid, &rock, shift) {
-                    StepResult::Settled(new_position) => {
-                        grid.extend(new_position.iter());
-                        total_rock_count += 1;
-                        let (rock_index, next_formation) = formation_infinite_iter.next().unwrap();
-                        if let Some(new_floor) = find_new_floor(&grid, &new_position) {
-                            // println!("found new floor {}", new_floor);
-                            grid = shift_grid(&grid, new_floor);
-                            floor_height += new_floor;
-
-                            if let Some(&previous) = memory.get(&(shift_index, rock_index)) {
-                                println!("found a repeat! {:?}", previous);
-                                let rock_diff = total_rock_count - previous.0;
-                                let floor_diff = floor_height - previous.1;
-                                let remaining_rocks_to_try = goal_rocks - total_rock_count;
-                                let multiplier = remaining_rocks_to_try / rock_diff;
-                                total_rock_count += rock_diff * multiplier;
-                                floor_height += floor_diff * multiplier as isize;
-                                memory.clear();
-                            }
-
-                            memory.insert(
-                                (shift_index, rock_index),
-                                (total_rock_count, floor_height),
-                            );
-                        }
-
-                        rock = next_rock(&grid, next_formation);
-                    }
-                    StepResult::Moved(new_position) => rock = new_position,
+impl Chamber {
+    fn height(&self) -> isize {
+        self.floor_height + max_height(&self.grid).map_or(0, |height| height + 1)
+    }
+
+    // what's left of the grid along with where the jets and rocks are up to is everything that
+    // decides how the following rocks land
+    fn fingerprint(&self) -> (usize, usize, Vec<(isize, isize)>) {
+        (
+            self.next_shift,
+            self.next_formation,
+            self.grid.iter().copied().sorted().collect(),
+        )
+    }
+}
+
+fn tower_height(shifts: &[Direction], goal_rocks: u64) -> i64 {
+    let formations = get_formations();
+    let chamber = Chamber {
+        grid: HashSet::new(),
+        floor_height: 0,
+        next_shift: 0,
+        next_formation: 0,
+    };
+
+    let cycle = find_cycle(
+        chamber,
+        |chamber| drop_rock(chamber, shifts, &formations),
+        Chamber::fingerprint,
+    );
+
+    cycle.extrapolate(goal_rocks, |chamber| chamber.height() as i64)
+}
+
+fn drop_rock(chamber: &Chamber, shifts: &[Direction], formations: &[Formation]) -> Chamber {
+    let mut chamber = chamber.clone();
+    let mut rock = next_rock(&chamber.grid, &formations[chamber.next_formation]);
+    chamber.next_formation = (chamber.next_formation + 1) % formations.len();
+
+    loop {
+        let shift = shifts[chamber.next_shift];
+        chamber.next_shift = (chamber.next_shift + 1) % shifts.len();
+        match step(&chamber.grid, &rock, &shift) {
+            StepResult::Settled(new_position) => {
+                chamber.grid.extend(new_position.iter());
+                if let Some(new_floor) = find_new_floor(&chamber.grid) {
+                    chamber.grid = shift_grid(&chamber.grid, new_floor);
+                    chamber.floor_height += new_floor;
                 }
-            }
 
-            max_height(&grid).unwrap_or(0) + floor_height + 1
-        }
-        Ok((remaining, _)) => {
-            println!("remaining unparsed \"{}\"", remaining);
-            0
-        }
-        Err(e) => {
-            println!("error parsing \"{}\"", e);
-            0
+                frame!(draw_chamber(&chamber.grid));
+
+                return chamber;
+            }
+            StepResult::Moved(new_position) => rock = new_position,
         }
     }
 }
 
-fn find_new_floor(grid: &HashSet<(isize, isize)>, latest_rock: &[(isize, isize)]) -> Option<isize> {
-    let bottom_of_rock = latest_rock.iter().min_by(|a, b| a.1.cmp(&b.1)).unwrap().1;
-    let top_of_rock = latest_rock.iter().max_by(|a, b| a.1.cmp(&b.1)).unwrap().1;
-    for y in bottom_of_rock..(top_of_rock + 1) {
-        if (0..7).all(|x| grid.contains(&(x, y))) {
-            return Some(y);
+// rocks only ever move left, right and down, so they can only reach the air that can be reached that
+// way from above the tower. anything below the lowest of that air is out of reach for good
+fn find_new_floor(grid: &HashSet<(isize, isize)>) -> Option<isize> {
+    let top = max_height(grid)? + 1;
+    let mut to_visit = (0..7).map(|x| (x, top)).collect_vec();
+    let mut reachable: HashSet<(isize, isize)> = to_visit.iter().copied().collect();
+    while let Some((x, y)) = to_visit.pop() {
+        for next in [(x - 1, y), (x + 1, y), (x, y - 1)] {
+            if (0..7).contains(&next.0)
+                && next.1 >= 0
+                && !grid.contains(&next)
+                && reachable.insert(next)
+            {
+                to_visit.push(next);
+            }
         }
     }
 
-    None
+    let lowest = reachable.iter().map(|&(_, y)| y).min().unwrap();
+    (lowest > 0).then_some(lowest)
 }
 
 fn shift_grid(grid: &HashSet<(isize, isize)>, new_floor: isize) -> HashSet<(isize, isize)> {
@@ -181,18 +165,17 @@ fn shift_grid(grid: &HashSet<(isize, isize)>, new_floor: isize) -> HashSet<(isiz
     shifted_grid
 }
 
-fn print_grid(grid: &HashSet<(isize, isize)>, rock: Option<&Formation>) {
-    let formation = rock.map_or(HashSet::new(), |v| HashSet::from_iter(v.iter()));
-    for y in (0..20).rev() {
-        for x in 0..7 {
-            if grid.contains(&(x, y)) || formation.contains(&(x, y)) {
-                print!("{}", "#");
-            } else {
-                print!("{}", ".");
-            }
+// what's left of the tower, top side up
+fn draw_chamber(grid: &HashSet<(isize, isize)>) -> Canvas {
+    let top = max_height(grid).unwrap_or(0);
+
+    Grid::from_fn(7, top as usize + 1, |(x, y)| {
+        if grid.contains(&(x, top - y)) {
+            Cell::from('#')
+        } else {
+            Cell::from('.')
         }
-        println!();
-    }
+    })
 }
 
 //this doesn't have to be a calculated value
@@ -305,3 +288,21 @@ fn input_parser(input: &str) -> IResult<&str, Input> {
 
     Ok((input, moves))
 }
+
+#[cfg(test)]
+mod day17_tests {
+    use crate::day17::{Day17, Day17Params};
+    use aoc_common::params::Params;
+    use aoc_common::solution::{solve, Answer};
+
+    const EXAMPLE: &str = ">>><<><>><<<>><>>><<<>>><<<><<<>><>><<>>
+";
+
+    #[test]
+    fn example() {
+        assert_eq!(
+            solve::<Day17>(EXAMPLE, &[1, 2], &Day17Params::example()).unwrap(),
+            [Answer::Integer(3068), Answer::Integer(1514285714288)]
+        );
+    }
+}
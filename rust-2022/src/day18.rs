@@ -1,73 +1,48 @@
+use aoc_common::parse::{parse_all, ParseError};
+use aoc_common::solution::{Answer, Solution};
 use itertools::Itertools;
-use nom::{
-    bytes::complete::tag,
-    character::complete::anychar,
-    combinator::{map_res, opt},
-    multi::separated_list0,
-    multi::{many1, separated_list1},
-    IResult,
-};
-use std::collections::BinaryHeap;
-use std::collections::{HashMap, HashSet};
-use std::{cmp::Ordering, hash::Hash};
+use nom::{combinator::map_res, multi::separated_list1, IResult};
+use std::collections::HashSet;
 
 type Coordinate = (isize, isize, isize);
 
 type Input = HashSet<Coordinate>;
 
-pub fn part1() -> usize {
-    let input = include_str!("../../data/2022/18.txt");
+pub struct Day18;
 
-    match input_parser(input) {
-        Ok((remaining_input, blocks)) if remaining_input.is_empty() => {
-            println!("parsed entire input");
+impl Solution for Day18 {
+    type Input<'a> = Input;
 
-            blocks
-                .iter()
-                .map(|&b| count_exposed_sides(&blocks, b))
-                .sum1()
-                .unwrap()
-        }
-        Ok((remaining, _)) => {
-            println!("remaining unparsed \"{}\"", remaining);
-            0
-        }
-        Err(e) => {
-            println!("error parsing \"{}\"", e);
-            0
-        }
-    }
-}
+    type Params = ();
 
-pub fn part2() -> usize {
-    let input = include_str!("../../data/2022/18.txt");
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse_all(input, input_parser)
+    }
 
-    match input_parser(input) {
-        Ok((remaining_input, reference)) if remaining_input.is_empty() => {
-            println!("parsed entire input");
+    fn part1(blocks: &Self::Input<'_>, _params: &Self::Params) -> Answer {
+        blocks
+            .iter()
+            .map(|&b| count_exposed_sides(blocks, b))
+            .sum1::<usize>()
+            .unwrap()
+            .into()
+    }
 
-            let (mut carved, starting_point) = create_bounding_box(&reference);
+    fn part2(reference: &Self::Input<'_>, _params: &Self::Params) -> Answer {
+        let (mut carved, starting_point) = create_bounding_box(reference);
 
-            let mut to_carve = vec![starting_point];
-            while let Some(point) = to_carve.pop() {
-                let carve_next = carve_from_point(&mut carved, &reference, point);
-                to_carve.extend(carve_next);
-            }
-
-            carved
-                .iter()
-                .map(|&b| count_exposed_sides(&carved, b))
-                .sum1()
-                .unwrap()
-        }
-        Ok((remaining, _)) => {
-            println!("remaining unparsed \"{}\"", remaining);
-            0
-        }
-        Err(e) => {
-            println!("error parsing \"{}\"", e);
-            0
+        let mut to_carve = vec![starting_point];
+        while let Some(point) = to_carve.pop() {
+            let carve_next = carve_from_point(&mut carved, reference, point);
+            to_carve.extend(carve_next);
         }
+
+        carved
+            .iter()
+            .map(|&b| count_exposed_sides(&carved, b))
+            .sum1::<usize>()
+            .unwrap()
+            .into()
     }
 }
 
@@ -174,3 +149,32 @@ fn input_parser(input: &str) -> IResult<&str, Input> {
 
     Ok((input, HashSet::from_iter(formations)))
 }
+
+#[cfg(test)]
+mod day18_tests {
+    use crate::day18::Day18;
+    use aoc_common::solution::{solve, Answer};
+
+    const EXAMPLE: &str = "2,2,2
+1,2,2
+3,2,2
+2,1,2
+2,3,2
+2,2,1
+2,2,3
+2,2,4
+2,2,6
+1,2,5
+3,2,5
+2,1,5
+2,3,5
+";
+
+    #[test]
+    fn example() {
+        assert_eq!(
+            solve::<Day18>(EXAMPLE, &[1, 2], &()).unwrap(),
+            [Answer::Integer(64), Answer::Integer(58)]
+        );
+    }
+}
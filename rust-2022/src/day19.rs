@@ -1,20 +1,14 @@
-use itertools::Itertools;
-use nom::{
-    bytes::complete::tag,
-    character::complete::anychar,
-    combinator::{map_res, opt},
-    multi::separated_list0,
-    multi::{many1, separated_list1},
-    IResult,
-};
-use std::collections::{HashMap, HashSet};
-use std::fmt;
-use std::{cmp::Ordering, hash::Hash};
-use std::{collections::BinaryHeap, time::SystemTime};
+use aoc_common::debug;
+use aoc_common::info;
+use aoc_common::parse::{parse_all, ParseError};
+use aoc_common::solution::{Answer, Solution};
+use nom::{bytes::complete::tag, combinator::map_res, multi::separated_list1, IResult};
+use std::hash::Hash;
+use std::time::Instant;
 
 type Input = Vec<Blueprint>;
 
-struct Blueprint {
+pub struct Blueprint {
     ore_robot_cost: Cost,
     clay_robot_cost: Cost,
     obsidian_robot_cost: Cost,
@@ -50,75 +44,41 @@ enum FactoryUse {
     BuildingGeodeRobot,
 }
 
-pub fn part1() -> usize {
-    let input = include_str!("../../data/2022/19.txt");
+pub struct Day19;
 
-    match input_parser(input) {
-        Ok((remaining_input, blueprints)) if remaining_input.is_empty() => {
-            println!("parsed entire input");
+impl Solution for Day19 {
+    type Input<'a> = Input;
 
-            let mut sum = 0;
+    type Params = ();
 
-            // for (index, blueprint) in blueprints.iter().enumerate() {
-            //     let start = SystemTime::now();
-            //     let max_geodes = blueprint.max_geodes(24);
-            //     let end = SystemTime::now();
-            //     println!(
-            //         "ID {}: {} in {} seconds",
-            //         index + 1,
-            //         max_geodes,
-            //         end.duration_since(start).unwrap().as_secs()
-            //     );
-            //     sum += (index + 1) * max_geodes;
-            // }
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse_all(input, input_parser)
+    }
 
-            println!("part 1 result: {sum}");
+    fn part1(blueprints: &Self::Input<'_>, _params: &Self::Params) -> Answer {
+        let mut sum = 0;
 
-            sum
-        }
-        Ok((remaining, _)) => {
-            println!("remaining unparsed \"{remaining}\"");
-            0
-        }
-        Err(e) => {
-            println!("error parsing \"{e}\"");
-            0
+        for (index, blueprint) in blueprints.iter().enumerate() {
+            let start = Instant::now();
+            let max_geodes = blueprint.max_geodes(24);
+            info!("ID {}: {} in {:?}", index + 1, max_geodes, start.elapsed());
+            sum += (index + 1) * max_geodes;
         }
+
+        sum.into()
     }
-}
 
-pub fn part2() -> usize {
-    let input = include_str!("../../data/2022/19.txt");
-
-    match input_parser(input) {
-        Ok((remaining_input, blueprints)) if remaining_input.is_empty() => {
-            println!("parsed entire input");
-
-            let mut result = 1;
-
-            for (index, blueprint) in blueprints.iter().take(1).enumerate() {
-                let start = SystemTime::now();
-                let max_geodes = blueprint.max_geodes(32);
-                let end = SystemTime::now();
-                println!(
-                    "ID {}: {} in {} seconds",
-                    index + 1,
-                    max_geodes,
-                    end.duration_since(start).unwrap().as_secs()
-                );
-                result *= max_geodes;
-            }
+    fn part2(blueprints: &Self::Input<'_>, _params: &Self::Params) -> Answer {
+        let mut result = 1;
 
-            result
-        }
-        Ok((remaining, _)) => {
-            println!("remaining unparsed \"{remaining}\"");
-            0
-        }
-        Err(e) => {
-            println!("error parsing \"{e}\"");
-            0
+        for (index, blueprint) in blueprints.iter().take(3).enumerate() {
+            let start = Instant::now();
+            let max_geodes = blueprint.max_geodes(32);
+            info!("ID {}: {} in {:?}", index + 1, max_geodes, start.elapsed());
+            result *= max_geodes;
         }
+
+        result.into()
     }
 }
 
@@ -176,62 +136,29 @@ fn input_parser(input: &str) -> IResult<&str, Input> {
 
 impl Blueprint {
     fn max_geodes(&self, minutes: usize) -> usize {
-        let mut states = HashSet::new();
-        let mut best_state = State::new();
-        let mut count_final_states = 0;
-        states.insert(State::new());
-
-        // let mut min = states.iter().next().unwrap().geodes;
-        // let mut max = states.iter().next().unwrap().geodes;
-        // let mut cutoff = 0;
-
-        while !states.is_empty() {
-            // println!("time remaining: {}", time_remaining);
-            println!("{} states to evaluate", states.len());
-            println!("{} final states", count_final_states);
-            println!("{} best so far", best_state.geodes);
-            let mut next_states = HashSet::new();
-
-            for state in states {
-                // if time_remaining < 10 && state.geodes <= cutoff {
-                //     continue;
-                // }
-
-                // if state.is_building_too_much_supply(self) {
-                //     continue;
-                // }
-
-                // min = min.min(state.geodes);
-                // max = max.max(state.geodes);
-
-                // next_states.extend(self.generate_possible_states(&state));
-                for next_state in self.generate_possible_states(&state, minutes) {
-                    if next_state.time < minutes {
-                        next_states.insert(next_state);
-                    } else if next_state.geodes > best_state.geodes {
-                        best_state = next_state;
-                        count_final_states += 1;
-                    } else {
-                        count_final_states += 1;
-                    }
-                }
-            }
+        let mut best = 0;
+        self.explore(State::new(), minutes, &mut best);
+        debug!("found {best}");
 
-            // println!(
-            //     "{:?}",
-            //     next_states
-            //         .iter()
-            //         .max_by(|a, b| a.geodes.cmp(&b.geodes))
-            //         .unwrap_or(&State::new()),
-            // );
+        best
+    }
 
-            // cutoff = max - ((max - min) / 2);
-            states = next_states;
+    // depth first over which robot to build next, giving up on a branch once even a new geode
+    // robot every minute left couldn't beat the best so far
+    fn explore(&self, state: State, minutes: usize, best: &mut usize) {
+        let left = minutes - state.time;
+        if left == 0 {
+            *best = (*best).max(state.geodes);
+            return;
         }
 
-        println!("found {best_state:?}");
+        if state.geodes + state.geode_robots * left + left * (left - 1) / 2 <= *best {
+            return;
+        }
 
-        best_state.geodes
+        for next_state in self.generate_possible_states(&state, minutes) {
+            self.explore(next_state, minutes, best);
+        }
     }
 
     fn generate_possible_states(&self, current_state: &State, minutes: usize) -> Vec<State> {
@@ -243,8 +170,12 @@ impl Blueprint {
         //build one geode robot if possible
         let mut possibilities = vec![];
 
+        // the factory only builds one robot a minute, so there's no point making more of a resource
+        // each minute than the dearest robot needs
         if let Some(time_to_afford_ore) = current_state.time_to_afford(&self.ore_robot_cost) {
-            if current_state.time + time_to_afford_ore < minutes {
+            if current_state.time + time_to_afford_ore < minutes
+                && current_state.ore_robots < self.max_ore_cost()
+            {
                 let new_state = current_state
                     .advance_time(time_to_afford_ore + 1)
                     .pay(&self.ore_robot_cost)
@@ -255,7 +186,9 @@ impl Blueprint {
         }
 
         if let Some(time_to_afford_clay) = current_state.time_to_afford(&self.clay_robot_cost) {
-            if current_state.time + time_to_afford_clay < minutes {
+            if current_state.time + time_to_afford_clay < minutes
+                && current_state.clay_robots < self.obsidian_robot_cost.clay_cost()
+            {
                 let new_state = current_state
                     .advance_time(time_to_afford_clay + 1)
                     .pay(&self.clay_robot_cost)
@@ -268,7 +201,9 @@ impl Blueprint {
         if let Some(time_to_afford_obsidian) =
             current_state.time_to_afford(&self.obsidian_robot_cost)
         {
-            if current_state.time + time_to_afford_obsidian < minutes {
+            if current_state.time + time_to_afford_obsidian < minutes
+                && current_state.obsidian_robots < self.geode_robot_cost.obsidian_cost()
+            {
                 let new_state = (*current_state)
                     .advance_time(time_to_afford_obsidian + 1)
                     .pay(&self.obsidian_robot_cost)
@@ -403,12 +338,10 @@ impl State {
                         Some(0)
                     } else {
                         Some(
-                            (*ore as isize - self.ore as isize)
-                                .div_ceil(self.ore_robots as isize)
-                                .max(
-                                    (*clay as isize - self.clay as isize)
-                                        .div_ceil(self.clay_robots as isize),
-                                ) as usize,
+                            (*ore)
+                                .saturating_sub(self.ore)
+                                .div_ceil(self.ore_robots)
+                                .max((*clay).saturating_sub(self.clay).div_ceil(self.clay_robots)),
                         )
                     }
                 } else {
@@ -421,12 +354,14 @@ impl State {
                         Some(0)
                     } else {
                         Some(
-                            (*ore as isize - self.ore as isize)
-                                .div_ceil(self.ore_robots as isize)
+                            (*ore)
+                                .saturating_sub(self.ore)
+                                .div_ceil(self.ore_robots)
                                 .max(
-                                    (*obsidian as isize - self.obsidian as isize)
-                                        .div_ceil(self.obsidian_robots as isize),
-                                ) as usize,
+                                    (*obsidian)
+                                        .saturating_sub(self.obsidian)
+                                        .div_ceil(self.obsidian_robots),
+                                ),
                         )
                     }
                 } else {
@@ -452,3 +387,29 @@ impl State {
         self
     }
 }
+
+#[cfg(test)]
+mod day19_tests {
+    use crate::day19::Day19;
+    use aoc_common::solution::{solve, Answer};
+
+    const EXAMPLE: &str = "Blueprint 1: Each ore robot costs 4 ore. Each clay robot costs 2 ore. Each obsidian robot costs 3 ore and 14 clay. Each geode robot costs 2 ore and 7 obsidian.
+Blueprint 2: Each ore robot costs 2 ore. Each clay robot costs 3 ore. Each obsidian robot costs 3 ore and 8 clay. Each geode robot costs 3 ore and 12 obsidian.
+";
+
+    #[test]
+    fn example_part1() {
+        assert_eq!(
+            solve::<Day19>(EXAMPLE, &[1], &()).unwrap(),
+            [Answer::Integer(33)]
+        );
+    }
+
+    #[test]
+    fn example_part2() {
+        assert_eq!(
+            solve::<Day19>(EXAMPLE, &[2], &()).unwrap(),
+            [Answer::Integer(3472)]
+        );
+    }
+}
@@ -1,185 +1,254 @@
-use itertools::Itertools;
-use nom::{
-    bytes::complete::tag,
-    character::complete::anychar,
-    combinator::{map_res, opt},
-    multi::separated_list0,
-    multi::{many1, separated_list1},
-    IResult,
-};
-use std::fmt;
-use std::{cmp::Ordering, hash::Hash};
-use std::{collections::BinaryHeap, time::SystemTime};
-use std::{
-    collections::{HashMap, HashSet},
-    ptr,
-};
+use aoc_common::debug;
+use aoc_common::params;
+use aoc_common::parse::{parse_all, parse_number, ParseError};
+use aoc_common::solution::{Answer, Solution};
+use nom::{multi::separated_list1, IResult};
 
 type Input = Vec<isize>;
 
-pub fn part1() -> isize {
-    let input = include_str!("../../data/2022/20.txt");
-    // let input = include_str!("../../data/2022/20-example.txt");
+params! {
+    // what part 2 multiplies every number by before mixing, and how many times it mixes
+    pub struct Day20Params {
+        decryption_key: isize = (example: 811589153, real: 811589153),
+        rounds: usize = (example: 10, real: 10),
+    }
+}
 
-    match input_parser(input) {
-        Ok((remaining_input, numbers)) if remaining_input.is_empty() => {
-            println!("parsed entire input");
+pub struct Day20;
 
-            let mut mixed = numbers.iter().collect_vec();
-            // println!("{mixed:?}");
-            let length = numbers.len();
-            for number in numbers.iter() {
-                shift_number(number, &mut mixed, length);
-                // println!("{mixed:?}");
-            }
+impl Solution for Day20 {
+    type Input<'a> = Input;
 
-            let index_of_0 = mixed.iter().position(|&n| *n == 0).unwrap();
-            let first_index = (index_of_0 + 1000) % length;
-            let second_index = (index_of_0 + 2000) % length;
-            let third_index = (index_of_0 + 3000) % length;
+    type Params = Day20Params;
 
-            println!(
-                "{}, {}, {}",
-                *mixed[first_index], *mixed[second_index], *mixed[third_index]
-            );
-            *mixed[first_index] + *mixed[second_index] + *mixed[third_index]
-        }
-        Ok((remaining, _)) => {
-            println!("remaining unparsed \"{remaining}\"");
-            0
-        }
-        Err(e) => {
-            println!("error parsing \"{e}\"");
-            0
-        }
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse_all(input, input_parser)
+    }
+
+    fn part1(numbers: &Self::Input<'_>, _params: &Self::Params) -> Answer {
+        decrypt(numbers, 1, 1).into()
+    }
+
+    fn part2(encrypted_numbers: &Self::Input<'_>, params: &Self::Params) -> Answer {
+        decrypt(encrypted_numbers, params.decryption_key, params.rounds).into()
     }
 }
 
-pub fn part2() -> isize {
-    let input = include_str!("../../data/2022/20.txt");
+fn decrypt(numbers: &[isize], decryption_key: isize, rounds: usize) -> isize {
+    let mut mixer = Mixer::new(numbers.iter().map(|n| n * decryption_key).collect());
+    for _ in 0..rounds {
+        mixer.round();
+    }
 
-    match input_parser(input) {
-        Ok((remaining_input, encrypted_numbers)) if remaining_input.is_empty() => {
-            println!("parsed entire input");
+    mixer.grove_coordinates()
+}
 
-            let decryption_key = 811589153;
-            let numbers = encrypted_numbers
-                .iter()
-                .map(|&n| n * decryption_key)
-                .collect_vec();
+// the numbers' original indexes in their mixed order, kept in blocks of about √n of them so that
+// finding, removing and inserting one all take O(√n)
+struct MixingList {
+    blocks: Vec<Vec<usize>>,
+    block_of: Vec<usize>,
+    block_size: usize,
+}
 
-            let mut mixed = numbers.iter().collect_vec();
-            let length = numbers.len();
+impl MixingList {
+    fn new(length: usize) -> Self {
+        let block_size = (length as f64).sqrt().ceil().max(1.0) as usize;
+        let mut list = MixingList {
+            blocks: vec![],
+            block_of: vec![0; length],
+            block_size,
+        };
+        list.rebuild((0..length).collect());
+        list
+    }
 
-            for _ in 0..10 {
-                for number in numbers.iter() {
-                    shift_number(number, &mut mixed, length);
-                }
+    fn rebuild(&mut self, order: Vec<usize>) {
+        self.blocks = order
+            .chunks(self.block_size)
+            .map(<[usize]>::to_vec)
+            .collect();
+        for (block, indexes) in self.blocks.iter().enumerate() {
+            for &index in indexes {
+                self.block_of[index] = block;
             }
+        }
+    }
 
-            let index_of_0 = mixed.iter().position(|&n| *n == 0).unwrap();
-            let first_index = (index_of_0 + 1000) % length;
-            let second_index = (index_of_0 + 2000) % length;
-            let third_index = (index_of_0 + 3000) % length;
+    fn order(&self) -> Vec<usize> {
+        self.blocks.concat()
+    }
 
-            println!(
-                "{}, {}, {}",
-                *mixed[first_index], *mixed[second_index], *mixed[third_index]
-            );
-            *mixed[first_index] + *mixed[second_index] + *mixed[third_index]
-        }
-        Ok((remaining, _)) => {
-            println!("remaining unparsed \"{remaining}\"");
-            0
+    // takes `index` out of the list, giving back where it was
+    fn remove(&mut self, index: usize) -> usize {
+        let block = self.block_of[index];
+        let offset = self.blocks[block]
+            .iter()
+            .position(|&other| other == index)
+            .unwrap();
+        self.blocks[block].remove(offset);
+
+        self.blocks[..block].iter().map(Vec::len).sum::<usize>() + offset
+    }
+
+    fn insert(&mut self, position: usize, index: usize) {
+        let mut remaining = position;
+        let mut block = 0;
+        while block + 1 < self.blocks.len() && remaining > self.blocks[block].len() {
+            remaining -= self.blocks[block].len();
+            block += 1;
         }
-        Err(e) => {
-            println!("error parsing \"{e}\"");
-            0
+
+        self.blocks[block].insert(remaining, index);
+        self.block_of[index] = block;
+        if self.blocks[block].len() > 2 * self.block_size {
+            self.rebuild(self.order());
         }
     }
 }
 
-fn shift_number<'a>(number: &'a isize, mixed: &mut Vec<&'a isize>, length: usize) {
-    let index_in_mixed = mixed.iter().position(|&n| ptr::eq(n, number)).unwrap();
-    mixed.remove(index_in_mixed);
-    let new_index_unadjusted = index_in_mixed as isize + *number;
+pub struct Mixer {
+    numbers: Vec<isize>,
+    list: MixingList,
+}
+
+impl Mixer {
+    pub fn new(numbers: Vec<isize>) -> Self {
+        let list = MixingList::new(numbers.len());
+        Mixer { numbers, list }
+    }
+
+    // moves the number that started at `index` as far as its value. one that would land at the
+    // very start goes to the end instead, which is the same place in the circle
+    fn move_number(&mut self, index: usize) {
+        let others = self.numbers.len() as isize - 1;
+        if others == 0 {
+            return;
+        }
 
-    let new_index = if new_index_unadjusted <= 0 {
-        ((new_index_unadjusted) % (length - 1) as isize + (length - 1) as isize) as usize
-    } else if new_index_unadjusted > (length - 1) as isize {
-        (new_index_unadjusted as usize) % (length - 1)
-    } else {
-        new_index_unadjusted as usize
-    };
+        let position = self.list.remove(index) as isize;
+        let new_position = match (position + self.numbers[index]).rem_euclid(others) {
+            0 => others,
+            new_position => new_position,
+        };
+        self.list.insert(new_position as usize, index);
+    }
 
-    mixed.insert(new_index, number);
-}
+    pub fn round(&mut self) {
+        for index in 0..self.numbers.len() {
+            self.move_number(index);
+        }
+    }
+
+    pub fn sequence(&self) -> Vec<isize> {
+        self.list
+            .order()
+            .into_iter()
+            .map(|index| self.numbers[index])
+            .collect()
+    }
 
-fn number_parser(input: &str) -> IResult<&str, isize> {
-    let (input, negative_sign) = opt(tag("-"))(input)?;
-    let (input, num) = map_res(nom::character::complete::digit1, |s: &str| {
-        s.parse::<isize>()
-    })(input)?;
+    // the 1000th, 2000th and 3000th numbers after the 0, added up
+    pub fn grove_coordinates(&self) -> isize {
+        let sequence = self.sequence();
+        let zero = sequence.iter().position(|&n| n == 0).unwrap();
+        let coordinates =
+            [1000, 2000, 3000].map(|offset| sequence[(zero + offset) % sequence.len()]);
 
-    Ok((input, if negative_sign.is_some() { -num } else { num }))
+        debug!("coordinates: {:?}", coordinates);
+        coordinates.iter().sum()
+    }
 }
 
 fn input_parser(input: &str) -> IResult<&str, Input> {
     let (input, formations) =
-        separated_list1(nom::character::complete::newline, number_parser)(input)?;
+        separated_list1(nom::character::complete::newline, parse_number)(input)?;
 
     Ok((input, formations))
 }
 
 #[cfg(test)]
 mod day20_tests {
-    use std::ptr;
+    use crate::day20::Day20;
+    use crate::day20::Day20Params;
+    use crate::day20::Mixer;
+    use aoc_common::params::Params;
+    use aoc_common::solution::{solve, Answer};
 
-    use crate::day20::shift_number;
+    #[test]
+    fn mixes_like_the_example() {
+        let mut mixer = Mixer::new(vec![1, 2, -3, 3, -2, 0, 4]);
+        let expected = [
+            [2, 1, -3, 3, -2, 0, 4],
+            [1, -3, 2, 3, -2, 0, 4],
+            [1, 2, 3, -2, -3, 0, 4],
+            [1, 2, -2, -3, 0, 3, 4],
+            [1, 2, -3, 0, 3, 4, -2],
+            [1, 2, -3, 0, 3, 4, -2],
+            [1, 2, -3, 4, 0, 3, -2],
+        ];
+
+        for (index, sequence) in expected.iter().enumerate() {
+            mixer.move_number(index);
+            assert_eq!(mixer.sequence(), sequence);
+        }
+        assert_eq!(mixer.grove_coordinates(), 3);
+    }
 
     #[test]
-    fn test_pointer_equality() {
-        let x = 4;
-        let y = 4;
-
-        let x_1 = &x;
-        let x_2 = &x;
-        let y_1 = &y;
-
-        assert!(ptr::eq(x_1, x_2));
-        assert!(!ptr::eq(x_1, y_1));
-    }
-
-    // #[test]
-    // fn test_shift_number() {
-    //     let mut numbers = vec![1, -2, 3, 4];
-    //     let len = numbers.len();
-    //     shift_number(-2, &mut numbers, len);
-    //     assert_eq!(numbers, vec![1, 3, -2, 4]);
-
-    //     numbers = vec![1, 2, 3, 4];
-    //     let len = numbers.len();
-    //     shift_number(2, &mut numbers, len);
-    //     assert_eq!(numbers, vec![1, 3, 4, 2]);
-
-    //     numbers = vec![1, -1, 3, 4];
-    //     let len = numbers.len();
-    //     shift_number(-1, &mut numbers, len);
-    //     assert_eq!(numbers, vec![1, 3, 4, -1]);
-
-    //     numbers = vec![1, 8, 3, 4];
-    //     let len = numbers.len();
-    //     shift_number(8, &mut numbers, len);
-    //     assert_eq!(numbers, vec![8, 1, 3, 4]);
-
-    //     numbers = vec![1, 2, -2, -3, 0, 3, 4];
-    //     let len = numbers.len();
-    //     shift_number(-2, &mut numbers, len);
-    //     assert_eq!(numbers, vec![1, 2, -3, 0, 3, 4, -2]);
-
-    //     numbers = vec![4, -2, 5, 6, 7, 8, 9];
-    //     let len = numbers.len();
-    //     shift_number(-2, &mut numbers, len);
-    //     assert_eq!(numbers, vec![4, 5, 6, 7, 8, -2, 9]);
-    // }
+    fn mixes_longer_lists_in_blocks() {
+        // a few hundred numbers spread over many blocks, checked against plain vector mixing
+        let numbers: Vec<isize> = (0..300).map(|n| (n * 7919 % 601) - 300).collect();
+        let mut expected: Vec<usize> = (0..numbers.len()).collect();
+        for _ in 0..3 {
+            for index in 0..numbers.len() {
+                let position = expected.iter().position(|&other| other == index).unwrap();
+                expected.remove(position);
+                let others = numbers.len() as isize - 1;
+                let new_position = match (position as isize + numbers[index]).rem_euclid(others) {
+                    0 => others,
+                    new_position => new_position,
+                };
+                expected.insert(new_position as usize, index);
+            }
+        }
+
+        let mut mixer = Mixer::new(numbers.clone());
+        for _ in 0..3 {
+            mixer.round();
+        }
+        let expected: Vec<isize> = expected.into_iter().map(|index| numbers[index]).collect();
+        assert_eq!(mixer.sequence(), expected);
+    }
+
+    const EXAMPLE: &str = "1
+2
+-3
+3
+-2
+0
+4
+";
+
+    #[test]
+    fn example() {
+        assert_eq!(
+            solve::<Day20>(EXAMPLE, &[1, 2], &Day20Params::example()).unwrap(),
+            [Answer::Integer(3), Answer::Integer(1623178306)]
+        );
+
+        // the example lists the order after the first round, 0, -3, 4, -2, 3, 2, 1 times the key
+        let mut params = Day20Params::example();
+        params.rounds = 1;
+        assert_eq!(
+            solve::<Day20>(EXAMPLE, &[2], &params).unwrap(),
+            [Answer::Integer(811589153 * 6)]
+        );
+
+        params.decryption_key = 1;
+        assert_eq!(
+            solve::<Day20>(EXAMPLE, &[2], &params).unwrap(),
+            [Answer::Integer(3)]
+        );
+    }
 }
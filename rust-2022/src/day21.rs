@@ -1,24 +1,12 @@
-use itertools::Itertools;
-use nom::{
-    bytes::complete::tag,
-    character::complete::anychar,
-    combinator::{map_res, opt},
-    multi::separated_list0,
-    multi::{many1, separated_list1},
-    IResult,
-};
-use std::{cmp::Ordering, hash::Hash};
-use std::{collections::BinaryHeap, time::SystemTime};
-use std::{
-    collections::{HashMap, HashSet},
-    ptr,
-};
-use std::{fmt, ops};
+use aoc_common::parse::{parse_all, ParseError};
+use aoc_common::solution::{Answer, Solution};
+use nom::{bytes::complete::tag, combinator::map_res, multi::separated_list1, IResult};
+use std::collections::HashMap;
 
 type Input = Vec<(String, MonkeyOutput)>;
 
 #[derive(Clone)]
-enum MonkeyOutput {
+pub enum MonkeyOutput {
     Literal(isize),
     Addition(String, String),
     Subtraction(String, String),
@@ -26,78 +14,56 @@ enum MonkeyOutput {
     Division(String, String),
 }
 
-pub fn part1() -> isize {
-    // let input = include_str!("../../data/2022/21-example.txt");
-    let input = include_str!("../../data/2022/21.txt");
+pub struct Day21;
 
-    match input_parser(input) {
-        Ok((remaining_input, monkeys)) if remaining_input.is_empty() => {
-            println!("parsed entire input");
+impl Solution for Day21 {
+    type Input<'a> = Input;
 
-            let mut monkey_lookup = HashMap::new();
+    type Params = ();
 
-            for monkey in monkeys.iter() {
-                monkey_lookup.insert(monkey.0.clone(), monkey.1.clone());
-            }
-
-            resolve("root", &monkey_lookup)
-        }
-        Ok((remaining, _)) => {
-            println!("remaining unparsed \"{remaining}\"");
-            0
-        }
-        Err(e) => {
-            println!("error parsing \"{e}\"");
-            0
-        }
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse_all(input, input_parser)
     }
-}
-
-pub fn part2() -> isize {
-    let input = include_str!("../../data/2022/21.txt");
-    // let input = include_str!("../../data/2022/21-example.txt");
-
-    match input_parser(input) {
-        Ok((remaining_input, monkeys)) if remaining_input.is_empty() => {
-            println!("parsed entire input");
 
-            let mut monkey_lookup = HashMap::new();
+    fn part1(monkeys: &Self::Input<'_>, _params: &Self::Params) -> Answer {
+        let mut monkey_lookup = HashMap::new();
 
-            for monkey in monkeys.iter() {
-                monkey_lookup.insert(monkey.0.clone(), monkey.1.clone());
-            }
+        for monkey in monkeys.iter() {
+            monkey_lookup.insert(monkey.0.clone(), monkey.1.clone());
+        }
 
-            let root = monkey_lookup.get("root").unwrap();
+        resolve("root", &monkey_lookup).into()
+    }
 
-            let (left, right) = match root {
-                MonkeyOutput::Literal(_) => {
-                    panic!("It doesn't make sense for the root to be literal")
-                }
-                MonkeyOutput::Addition(left, right) => (left, right),
-                MonkeyOutput::Subtraction(left, right) => (left, right),
-                MonkeyOutput::Multiplication(left, right) => (left, right),
-                MonkeyOutput::Division(left, right) => (left, right),
-            };
+    fn part2(monkeys: &Self::Input<'_>, _params: &Self::Params) -> Answer {
+        let mut monkey_lookup = HashMap::new();
 
-            let left_value = maybe_resolve(left, &monkey_lookup);
-            let right_value = maybe_resolve(right, &monkey_lookup);
+        for monkey in monkeys.iter() {
+            monkey_lookup.insert(monkey.0.clone(), monkey.1.clone());
+        }
 
-            let (comparison, hole_side) = if let Some(value) = left_value {
-                (value, right)
-            } else {
-                (right_value.unwrap(), left)
-            };
+        let root = monkey_lookup.get("root").unwrap();
 
-            reduce_to_answer(comparison, hole_side, &monkey_lookup)
-        }
-        Ok((remaining, _)) => {
-            println!("remaining unparsed \"{remaining}\"");
-            0
-        }
-        Err(e) => {
-            println!("error parsing \"{e}\"");
-            0
-        }
+        let (left, right) = match root {
+            MonkeyOutput::Literal(_) => {
+                panic!("It doesn't make sense for the root to be literal")
+            }
+            MonkeyOutput::Addition(left, right) => (left, right),
+            MonkeyOutput::Subtraction(left, right) => (left, right),
+            MonkeyOutput::Multiplication(left, right) => (left, right),
+            MonkeyOutput::Division(left, right) => (left, right),
+        };
+
+        let left_value = maybe_resolve(left, &monkey_lookup);
+        let right_value = maybe_resolve(right, &monkey_lookup);
+
+        let (comparison, hole_side) = if let Some(value) = left_value {
+            (value, right)
+        } else {
+            (right_value.unwrap(), left)
+        };
+
+        reduce_to_answer(comparison, hole_side, &monkey_lookup).into()
     }
 }
 
@@ -192,40 +158,16 @@ fn maybe_resolve(monkey_name: &str, lookup: &HashMap<String, MonkeyOutput>) -> O
     match monkey_output {
         MonkeyOutput::Literal(value) => Some(*value),
         MonkeyOutput::Addition(left, right) => {
-            let left_value = maybe_resolve(left, lookup);
-            let right_value = maybe_resolve(right, lookup);
-            if left_value.is_none() || right_value.is_none() {
-                None
-            } else {
-                Some(left_value.unwrap() + right_value.unwrap())
-            }
+            Some(maybe_resolve(left, lookup)? + maybe_resolve(right, lookup)?)
         }
         MonkeyOutput::Subtraction(left, right) => {
-            let left_value = maybe_resolve(left, lookup);
-            let right_value = maybe_resolve(right, lookup);
-            if left_value.is_none() || right_value.is_none() {
-                None
-            } else {
-                Some(left_value.unwrap() - right_value.unwrap())
-            }
+            Some(maybe_resolve(left, lookup)? - maybe_resolve(right, lookup)?)
         }
         MonkeyOutput::Multiplication(left, right) => {
-            let left_value = maybe_resolve(left, lookup);
-            let right_value = maybe_resolve(right, lookup);
-            if left_value.is_none() || right_value.is_none() {
-                None
-            } else {
-                Some(left_value.unwrap() * right_value.unwrap())
-            }
+            Some(maybe_resolve(left, lookup)? * maybe_resolve(right, lookup)?)
         }
         MonkeyOutput::Division(left, right) => {
-            let left_value = maybe_resolve(left, lookup);
-            let right_value = maybe_resolve(right, lookup);
-            if left_value.is_none() || right_value.is_none() {
-                None
-            } else {
-                Some(left_value.unwrap() / right_value.unwrap())
-            }
+            Some(maybe_resolve(left, lookup)? / maybe_resolve(right, lookup)?)
         }
     }
 }
@@ -308,14 +250,33 @@ fn input_parser(input: &str) -> IResult<&str, Input> {
     Ok((input, formations))
 }
 
-// #[cfg(test)]
-// mod day21_tests {
-//     use std::ptr;
-
-//     use crate::day21;
-
-//     #[test]
-//     fn test_() {
-
-//     }
-// }
+#[cfg(test)]
+mod day21_tests {
+    use crate::day21::Day21;
+    use aoc_common::solution::{solve, Answer};
+
+    const EXAMPLE: &str = "root: pppw + sjmn
+dbpl: 5
+cczh: sllz + lgvd
+zczc: 2
+ptdq: humn - dvpt
+dvpt: 3
+lfqf: 4
+humn: 5
+ljgn: 2
+sjmn: drzm * dbpl
+sllz: 4
+pppw: cczh / lfqf
+lgvd: ljgn * ptdq
+drzm: hmdt - zczc
+hmdt: 32
+";
+
+    #[test]
+    fn example() {
+        assert_eq!(
+            solve::<Day21>(EXAMPLE, &[1, 2], &()).unwrap(),
+            [Answer::Integer(152), Answer::Integer(301)]
+        );
+    }
+}
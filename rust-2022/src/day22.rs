@@ -1,69 +1,31 @@
-use itertools::Itertools;
+use aoc_common::frame;
+use aoc_common::grid::Grid;
+use aoc_common::parse::{parse_all, ParseError};
+use aoc_common::render::{Canvas, Cell, Colour};
+use aoc_common::solution::{Answer, Solution};
+use aoc_common::{trace, warn};
 use nom::{
     bytes::complete::tag,
-    character::complete::anychar,
-    combinator::{map_res, opt},
-    multi::separated_list0,
+    combinator::map_res,
     multi::{many1, separated_list1},
     IResult,
 };
-use std::{cmp::Ordering, hash::Hash};
-use std::{collections::BinaryHeap, time::SystemTime};
-use std::{
-    collections::{HashMap, HashSet},
-    ptr,
-};
-use std::{fmt, ops};
 
-type Input = (HashMap<(isize, isize), Spot>, Vec<Direction>);
+type Input = (Board, Vec<Direction>);
 
-type CubeLayout = HashMap<CubeFace, CubeFaceBoundary>;
+// `None` is off the edge of the map
+type Board = Grid<Option<Spot>>;
 
-#[derive(Copy, Clone, Debug)]
-struct Position {
-    x: isize,
-    y: isize,
-    facing: Orientation,
-}
-
-#[derive(Copy, Clone, Debug)]
-struct CubePosition {
-    x: isize,
-    y: isize,
-    // face: CubeFace,
-    facing: Orientation,
-}
+// a direction in space once the map is folded up
+type Vector = [isize; 3];
 
-#[derive(Copy, Clone, Debug)]
-struct CubeFacePosition {
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+struct Position {
     x: isize,
     y: isize,
-    face: CubeFace,
     facing: Orientation,
 }
 
-#[derive(Copy, Clone, Debug)]
-struct CubeFaceBoundary {
-    top_left: (isize, isize),
-    bottom_right: (isize, isize),
-}
-
-#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
-enum CubeFace {
-    A,
-    B,
-    C,
-    D,
-    E,
-    F,
-}
-
-struct Cube {
-    map: HashMap<(isize, isize), Spot>,
-    face_boundaries: Vec<CubeFaceBoundary>,
-    size: usize,
-}
-
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
 enum Orientation {
     Up,
@@ -73,170 +35,137 @@ enum Orientation {
 }
 
 #[derive(Copy, Clone, Debug)]
-enum Direction {
+pub enum Direction {
     Move(isize),
     TurnLeft,
     TurnRight,
 }
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
-enum Spot {
+pub enum Spot {
     Open,
     Wall,
 }
 
-pub fn part1() -> isize {
-    // let input = include_str!("../../data/2022/22-example.txt");
-    let input = include_str!("../../data/2022/22.txt");
+// one of the six squares of the map, with where it ends up once folded: `normal` points out of the
+// cube, and `right` and `down` are where going right and down on the map take you
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+struct Face {
+    corner: (isize, isize),
+    normal: Vector,
+    right: Vector,
+    down: Vector,
+}
+
+// walking off `face` over one of its sides brings you onto `side` of another, which you then walk
+// away from. `flipped` is when the two sides count along the shared edge from opposite ends
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+struct Glue {
+    face: usize,
+    side: Orientation,
+    flipped: bool,
+}
 
-    match input_parser(input) {
-        Ok((remaining_input, (map, directions))) if remaining_input.is_empty() => {
-            println!("parsed entire input");
+// the map folded up, whatever shape its net is. `glues` has each face's sides in the order of
+// `Orientation::ALL`
+#[derive(Clone, Debug)]
+struct Cube {
+    size: isize,
+    faces: Vec<Face>,
+    glues: Vec<[Glue; 4]>,
+}
 
-            let &(starting_x, starting_y) = map
-                .iter()
-                .min_by(|&(a, _), &(b, _)| a.1.cmp(&b.1).then(a.0.cmp(&b.0)))
-                .unwrap()
-                .0;
+pub struct Day22;
 
-            let mut position = Position {
-                x: starting_x,
-                y: starting_y,
-                facing: Orientation::Right,
-            };
+impl Solution for Day22 {
+    type Input<'a> = Input;
 
-            for direction in directions {
-                position = follow_direction(position, direction, &map);
-            }
+    type Params = ();
 
-            position.score()
-        }
-        Ok((remaining, _)) => {
-            println!("remaining unparsed \"{remaining}\"");
-            0
-        }
-        Err(e) => {
-            println!("error parsing \"{e}\"");
-            0
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse_all(input, input_parser)
+    }
+
+    fn part1((map, directions): &Self::Input<'_>, _params: &Self::Params) -> Answer {
+        let ((starting_x, starting_y), _) = tiles(map).next().unwrap();
+
+        let mut position = Position {
+            x: starting_x,
+            y: starting_y,
+            facing: Orientation::Right,
+        };
+
+        for &direction in directions {
+            position = follow_direction(position, direction, map);
+            frame!(draw_board(map, (position.x, position.y), position.facing));
         }
+
+        position.score().into()
     }
-}
 
-pub fn part2() -> isize {
-    let input = include_str!("../../data/2022/22.txt");
-    let size = 50;
-    // let input = include_str!("../../data/2022/22-example.txt");
-    // let size = 4;
-
-    match input_parser(input) {
-        Ok((remaining_input, (map, directions))) if remaining_input.is_empty() => {
-            println!("parsed entire input");
-
-            let &(starting_x, starting_y) = map
-                .iter()
-                .min_by(|&(a, _), &(b, _)| a.1.cmp(&b.1).then(a.0.cmp(&b.0)))
-                .unwrap()
-                .0;
-
-            let mut position = CubePosition {
-                x: starting_x,
-                y: starting_y,
-                facing: Orientation::Right,
-            };
+    fn part2((map, directions): &Self::Input<'_>, _params: &Self::Params) -> Answer {
+        let Some(cube) = Cube::fold(map) else {
+            warn!("the map isn't the net of a cube");
+            return Answer::Unsolved;
+        };
 
-            let cube_layout = map_to_cube(&map, size);
+        let ((starting_x, starting_y), _) = tiles(map).next().unwrap();
 
-            // println!("{:?}", position);
-            for direction in directions {
-                position =
-                    follow_direction_with_cube(position, direction, &map, &cube_layout, size);
-                // println!("{:?}", position);
-                print_position(&map, position);
-                println!();
-                println!();
-            }
+        let mut position = Position {
+            x: starting_x,
+            y: starting_y,
+            facing: Orientation::Right,
+        };
 
-            position.score()
-        }
-        Ok((remaining, _)) => {
-            println!("remaining unparsed \"{remaining}\"");
-            0
-        }
-        Err(e) => {
-            println!("error parsing \"{e}\"");
-            0
+        for &direction in directions {
+            position = follow_direction_with_cube(position, direction, map, &cube);
+            frame!(draw_board(map, (position.x, position.y), position.facing));
         }
+
+        position.score().into()
     }
 }
 
-fn print_position(map: &HashMap<(isize, isize), Spot>, position: CubePosition) {
-    for y in 0..16 {
-        for x in 0..16 {
-            if position.x == x && position.y == y {
-                match position.facing {
-                    Orientation::Up => print!("^"),
-                    Orientation::Down => print!("v"),
-                    Orientation::Left => print!("<"),
-                    Orientation::Right => print!(">"),
-                }
-                continue;
-            }
-            match map.get(&(x, y)) {
-                None => {
-                    print!(" ");
-                }
-                Some(Spot::Wall) => {
-                    print!("#");
-                }
-                Some(Spot::Open) => {
-                    print!(".");
-                }
-            }
+// the whole map with an arrow where we're standing
+fn draw_board(map: &Board, (x, y): (isize, isize), facing: Orientation) -> Canvas {
+    Grid::from_fn(map.width(), map.height(), |position| {
+        if position == (x, y) {
+            let arrow = match facing {
+                Orientation::Up => '^',
+                Orientation::Down => 'v',
+                Orientation::Left => '<',
+                Orientation::Right => '>',
+            };
+            return Cell::coloured(arrow, Colour::RED);
         }
-        println!();
-    }
+
+        match spot_at(map, position) {
+            None => ' '.into(),
+            Some(Spot::Wall) => '#'.into(),
+            Some(Spot::Open) => Cell::coloured('.', Colour::grey(90)),
+        }
+    })
 }
 
-fn follow_direction(
-    position: Position,
-    direction: Direction,
-    map: &HashMap<(isize, isize), Spot>,
-) -> Position {
-    match direction {
-        Direction::Move(n) => move_along_line(position, n, map),
-        Direction::TurnLeft => position.rotate_left(),
-        Direction::TurnRight => position.rotate_right(),
-    }
+fn spot_at(map: &Board, position: (isize, isize)) -> Option<Spot> {
+    map.get(position).copied().flatten()
 }
 
-fn follow_direction_with_cube(
-    position: CubePosition,
-    direction: Direction,
-    map: &HashMap<(isize, isize), Spot>,
-    cube_layout: &CubeLayout,
-    size: isize,
-) -> CubePosition {
-    match direction {
-        Direction::Move(n) => {
-            println!("moving {n}");
-            let mut result = position;
-            for _ in 0..n {
-                result = match result.facing {
-                    Orientation::Up => result.move_up(map, cube_layout, size),
-                    Orientation::Down => result.move_down(map, cube_layout, size),
-                    Orientation::Left => result.move_left(map, cube_layout, size),
-                    Orientation::Right => result.move_right(map, cube_layout, size),
-                };
-            }
+// every spot on the map in reading order, leaving out the gaps
+fn tiles(map: &Board) -> impl Iterator<Item = ((isize, isize), Spot)> + '_ {
+    map.iter()
+        .filter_map(|(position, spot)| spot.map(|spot| (position, spot)))
+}
 
-            result
-        }
+fn follow_direction(position: Position, direction: Direction, map: &Board) -> Position {
+    match direction {
+        Direction::Move(n) => move_along_line(position, n, map),
         Direction::TurnLeft => position.rotate_left(),
         Direction::TurnRight => position.rotate_right(),
     }
 }
 
-fn move_along_line(position: Position, n: isize, map: &HashMap<(isize, isize), Spot>) -> Position {
+fn move_along_line(position: Position, n: isize, map: &Board) -> Position {
     if n <= 0 {
         return position;
     }
@@ -244,13 +173,12 @@ fn move_along_line(position: Position, n: isize, map: &HashMap<(isize, isize), S
     let (next_coordinate, next_spot) = match position.facing {
         Orientation::Up => {
             let next = (position.x, position.y - 1);
-            if let Some(spot) = map.get(&next) {
+            if let Some(spot) = spot_at(map, next) {
                 (next, spot)
             } else {
                 //wrap to bottom (max y)
-                let (&coordinate, spot) = map
-                    .iter()
-                    .filter(|&((x, _y), _)| *x == position.x)
+                let (coordinate, spot) = tiles(map)
+                    .filter(|&((x, _y), _)| x == position.x)
                     .max_by(|(a, _), (b, _)| a.1.cmp(&b.1))
                     .unwrap();
 
@@ -259,13 +187,12 @@ fn move_along_line(position: Position, n: isize, map: &HashMap<(isize, isize), S
         }
         Orientation::Down => {
             let next = (position.x, position.y + 1);
-            if let Some(spot) = map.get(&next) {
+            if let Some(spot) = spot_at(map, next) {
                 (next, spot)
             } else {
                 //wrap to top (min y)
-                let (&coordinate, spot) = map
-                    .iter()
-                    .filter(|&((x, _y), _)| *x == position.x)
+                let (coordinate, spot) = tiles(map)
+                    .filter(|&((x, _y), _)| x == position.x)
                     .min_by(|(a, _), (b, _)| a.1.cmp(&b.1))
                     .unwrap();
 
@@ -274,13 +201,12 @@ fn move_along_line(position: Position, n: isize, map: &HashMap<(isize, isize), S
         }
         Orientation::Left => {
             let next = (position.x - 1, position.y);
-            if let Some(spot) = map.get(&next) {
+            if let Some(spot) = spot_at(map, next) {
                 (next, spot)
             } else {
                 //wrap to top (min y)
-                let (&coordinate, spot) = map
-                    .iter()
-                    .filter(|&((_x, y), _)| *y == position.y)
+                let (coordinate, spot) = tiles(map)
+                    .filter(|&((_x, y), _)| y == position.y)
                     .max_by(|(a, _), (b, _)| a.0.cmp(&b.0))
                     .unwrap();
 
@@ -289,13 +215,12 @@ fn move_along_line(position: Position, n: isize, map: &HashMap<(isize, isize), S
         }
         Orientation::Right => {
             let next = (position.x + 1, position.y);
-            if let Some(spot) = map.get(&next) {
+            if let Some(spot) = spot_at(map, next) {
                 (next, spot)
             } else {
                 //wrap to top (min y)
-                let (&coordinate, spot) = map
-                    .iter()
-                    .filter(|&((_x, y), _)| *y == position.y)
+                let (coordinate, spot) = tiles(map)
+                    .filter(|&((_x, y), _)| y == position.y)
                     .min_by(|(a, _), (b, _)| a.0.cmp(&b.0))
                     .unwrap();
 
@@ -351,59 +276,24 @@ fn input_parser(input: &str) -> IResult<&str, Input> {
     let (input, _) = nom::character::complete::newline(input)?;
     let (input, directions) = many1(direction_parser)(input)?;
 
-    let mut map = HashMap::new();
-    for (y, row) in lines.iter().enumerate() {
-        for (x, c) in row.iter().enumerate() {
-            match c {
-                '.' => {
-                    map.insert((x as isize, y as isize), Spot::Open);
-                }
-                '#' => {
-                    map.insert((x as isize, y as isize), Spot::Wall);
-                }
-                _ => {}
-            }
-        }
-    }
+    let width = lines.iter().map(|row| row.len()).max().unwrap_or(0);
+    let rows = lines.iter().map(|row| {
+        let mut row: Vec<Option<Spot>> = row
+            .iter()
+            .map(|c| match c {
+                '.' => Some(Spot::Open),
+                '#' => Some(Spot::Wall),
+                _ => None,
+            })
+            .collect();
+        row.resize(width, None);
+        row
+    });
+    let map = Grid::from_rows(rows).unwrap();
 
     Ok((input, (map, directions)))
 }
 
-fn map_to_cube(map: &HashMap<(isize, isize), Spot>, size: isize) -> CubeLayout {
-    //we don't know the layout for sure, so see where they are
-    //read left to right, top to bottom
-    let mut faces = HashMap::new();
-    let mut face_names = vec![
-        CubeFace::F,
-        CubeFace::E,
-        CubeFace::D,
-        CubeFace::C,
-        CubeFace::B,
-        CubeFace::A,
-    ];
-    for y in 0..6 {
-        for x in 0..6 {
-            let starting_position = (size * x, size * y);
-            if map.contains_key(&starting_position) {
-                //this is one of the faces
-                faces.insert(
-                    face_names.pop().unwrap(),
-                    CubeFaceBoundary {
-                        top_left: starting_position,
-                        bottom_right: (x * size + size - 1, y * size + size - 1),
-                    },
-                );
-            }
-        }
-    }
-
-    // for (name, boundary) in faces.iter() {
-    //     println!("{:?}: {:?}", name, boundary);
-    // }
-
-    faces
-}
-
 impl Position {
     fn score(&self) -> isize {
         let facing_score = match self.facing {
@@ -441,502 +331,204 @@ impl Position {
     }
 }
 
-impl CubePosition {
-    fn rotate_left(&self) -> Self {
-        Self {
-            x: self.x,
-            y: self.y,
-            facing: self.facing.rotate_left(),
+fn follow_direction_with_cube(
+    position: Position,
+    direction: Direction,
+    map: &Board,
+    cube: &Cube,
+) -> Position {
+    match direction {
+        Direction::Move(n) => {
+            trace!("moving {n}");
+            let mut result = position;
+            for _ in 0..n {
+                let next = cube.step(map, result);
+                if spot_at(map, (next.x, next.y)) == Some(Spot::Wall) {
+                    break;
+                }
+                result = next;
+            }
+
+            result
         }
+        Direction::TurnLeft => position.rotate_left(),
+        Direction::TurnRight => position.rotate_right(),
     }
+}
 
-    fn rotate_right(&self) -> Self {
-        Self {
-            x: self.x,
-            y: self.y,
-            facing: self.facing.rotate_right(),
+fn negate([x, y, z]: Vector) -> Vector {
+    [-x, -y, -z]
+}
+
+impl Face {
+    // which way a side of the face points once folded
+    fn outwards(&self, side: Orientation) -> Vector {
+        match side {
+            Orientation::Up => negate(self.down),
+            Orientation::Down => self.down,
+            Orientation::Left => negate(self.right),
+            Orientation::Right => self.right,
         }
     }
 
-    fn score(&self) -> isize {
-        let facing_score = match self.facing {
-            Orientation::Right => 0,
-            Orientation::Down => 1,
-            Orientation::Left => 2,
-            Orientation::Up => 3,
+    // which way a side runs, from where it starts counting
+    fn along(&self, side: Orientation) -> Vector {
+        match side {
+            Orientation::Up | Orientation::Down => self.right,
+            Orientation::Left | Orientation::Right => self.down,
+        }
+    }
+
+    // the face that's next to this one on the map, folded down over the side between them
+    fn roll(&self, side: Orientation, corner: (isize, isize)) -> Face {
+        let (normal, right, down) = match side {
+            Orientation::Up => (negate(self.down), self.right, self.normal),
+            Orientation::Down => (self.down, self.right, negate(self.normal)),
+            Orientation::Left => (negate(self.right), self.normal, self.down),
+            Orientation::Right => (self.right, negate(self.normal), self.down),
         };
 
-        (self.x + 1) * 4 + (self.y + 1) * 1000 + facing_score
+        Face {
+            corner,
+            normal,
+            right,
+            down,
+        }
     }
+}
 
-    fn move_up(
-        &self,
-        map: &HashMap<(isize, isize), Spot>,
-        cube_layout: &CubeLayout,
-        size: isize,
-    ) -> CubePosition {
-        let next = (self.x, self.y - 1);
-        match map.get(&next) {
-            Some(Spot::Open) => Self {
-                x: next.0,
-                y: next.1,
-                facing: self.facing,
-            },
-            Some(Spot::Wall) => *self,
-            None => {
-                let relative_in_current_cube =
-                    CubeFacePosition::from_global_position(self, cube_layout);
-
-                let relative_in_new_cube =
-                    relative_in_current_cube.real_move_in_direction(Orientation::Up, size);
-
-                let global_position = relative_in_new_cube.to_global_position(cube_layout);
-
-                let spot = map.get(&(global_position.x, global_position.y));
-                if spot.is_none() || spot == Some(&Spot::Wall) {
-                    *self
-                } else {
-                    global_position
-                }
-            }
+impl Cube {
+    // works out the faces from the map and folds them up by rolling out from the first one, so
+    // any of the 11 nets will do. `None` when the map isn't six equal squares that make a cube
+    fn fold(map: &Board) -> Option<Cube> {
+        let tiles = tiles(map).count();
+        let size = ((tiles / 6) as f64).sqrt().round() as usize;
+        if size == 0 || size * size * 6 != tiles {
+            return None;
         }
-    }
 
-    fn move_down(
-        &self,
-        map: &HashMap<(isize, isize), Spot>,
-        cube_layout: &CubeLayout,
-        size: isize,
-    ) -> CubePosition {
-        let next = (self.x, self.y + 1);
-        match map.get(&next) {
-            Some(Spot::Open) => Self {
-                x: next.0,
-                y: next.1,
-                facing: self.facing,
-            },
-            Some(Spot::Wall) => *self,
-            None => {
-                let relative_in_current_cube =
-                    CubeFacePosition::from_global_position(self, cube_layout);
-
-                let relative_in_new_cube =
-                    relative_in_current_cube.real_move_in_direction(Orientation::Down, size);
-
-                let global_position = relative_in_new_cube.to_global_position(cube_layout);
-
-                let spot = map.get(&(global_position.x, global_position.y));
-                if spot.is_none() || spot == Some(&Spot::Wall) {
-                    *self
-                } else {
-                    global_position
-                }
-            }
+        let corners: Vec<(isize, isize)> = (0..map.height())
+            .step_by(size)
+            .flat_map(|y| (0..map.width()).step_by(size).map(move |x| (x, y)))
+            .map(|(x, y)| (x as isize, y as isize))
+            .filter(|&corner| spot_at(map, corner).is_some())
+            .collect();
+        let size = size as isize;
+        let whole_squares = corners.iter().all(|&(left, top)| {
+            (top..top + size).all(|y| (left..left + size).all(|x| spot_at(map, (x, y)).is_some()))
+        });
+        if corners.len() != 6 || !whole_squares {
+            return None;
         }
-    }
 
-    fn move_left(
-        &self,
-        map: &HashMap<(isize, isize), Spot>,
-        cube_layout: &CubeLayout,
-        size: isize,
-    ) -> CubePosition {
-        let next = (self.x - 1, self.y);
-        match map.get(&next) {
-            Some(Spot::Open) => Self {
-                x: next.0,
-                y: next.1,
-                facing: self.facing,
-            },
-            Some(Spot::Wall) => *self,
-            None => {
-                let relative_in_current_cube =
-                    CubeFacePosition::from_global_position(self, cube_layout);
-
-                let relative_in_new_cube =
-                    relative_in_current_cube.real_move_in_direction(Orientation::Left, size);
-
-                let global_position = relative_in_new_cube.to_global_position(cube_layout);
-
-                let spot = map.get(&(global_position.x, global_position.y));
-                if spot.is_none() || spot == Some(&Spot::Wall) {
-                    *self
-                } else {
-                    global_position
+        let mut faces: Vec<Option<Face>> = vec![None; 6];
+        faces[0] = Some(Face {
+            corner: corners[0],
+            normal: [0, 0, -1],
+            right: [1, 0, 0],
+            down: [0, 1, 0],
+        });
+        let mut queue = vec![0];
+        while let Some(index) = queue.pop() {
+            let face = faces[index].unwrap();
+            for side in Orientation::ALL {
+                let (dx, dy) = side.step();
+                let corner = (face.corner.0 + dx * size, face.corner.1 + dy * size);
+                if let Some(next) = corners.iter().position(|&c| c == corner) {
+                    if faces[next].is_none() {
+                        faces[next] = Some(face.roll(side, corner));
+                        queue.push(next);
+                    }
                 }
             }
         }
-    }
 
-    fn move_right(
-        &self,
-        map: &HashMap<(isize, isize), Spot>,
-        cube_layout: &CubeLayout,
-        size: isize,
-    ) -> CubePosition {
-        let next = (self.x + 1, self.y);
-        match map.get(&next) {
-            Some(Spot::Open) => Self {
-                x: next.0,
-                y: next.1,
-                facing: self.facing,
-            },
-            Some(Spot::Wall) => *self,
-            None => {
-                let relative_in_current_cube =
-                    CubeFacePosition::from_global_position(self, cube_layout);
-
-                let relative_in_new_cube =
-                    relative_in_current_cube.real_move_in_direction(Orientation::Right, size);
-
-                let global_position = relative_in_new_cube.to_global_position(cube_layout);
-
-                let spot = map.get(&(global_position.x, global_position.y));
-                if spot.is_none() || spot == Some(&Spot::Wall) {
-                    *self
-                } else {
-                    global_position
-                }
-            }
+        // the squares must all be connected and each end up on a different side of the cube
+        let faces: Vec<Face> = faces.into_iter().collect::<Option<_>>()?;
+        let face_towards = |normal: Vector| faces.iter().position(|face| face.normal == normal);
+        if (0..6).any(|index| face_towards(faces[index].normal) != Some(index)) {
+            return None;
         }
-    }
 
-    fn in_boundary(&self, boundary: &CubeFaceBoundary) -> bool {
-        self.x >= boundary.top_left.0
-            && self.x <= boundary.bottom_right.0
-            && self.y >= boundary.top_left.1
-            && self.y <= boundary.bottom_right.1
+        let glues = faces
+            .iter()
+            .map(|face| {
+                Orientation::ALL.map(|side| {
+                    let index = face_towards(face.outwards(side)).unwrap();
+                    let other = &faces[index];
+                    let other_side = Orientation::ALL
+                        .into_iter()
+                        .find(|&s| other.outwards(s) == face.normal)
+                        .unwrap();
+
+                    Glue {
+                        face: index,
+                        side: other_side,
+                        flipped: face.along(side) != other.along(other_side),
+                    }
+                })
+            })
+            .collect();
+
+        Some(Cube { size, faces, glues })
     }
-}
-
-impl CubeFacePosition {
-    fn from_global_position(position: &CubePosition, cube_layout: &CubeLayout) -> CubeFacePosition {
-        // println!("translating x: {}, y: {}", position.x, position.y);
-        // let cube_face_a = cube_layout.get(&CubeFace::A).unwrap();
-        // println!("boundary: {:?}", cube_face_a);
 
-        let (cube_face, boundary) = cube_layout
+    fn face_of(&self, (x, y): (isize, isize)) -> usize {
+        self.faces
             .iter()
-            .find(|&(_cube_face, boundary)| position.in_boundary(boundary))
-            .unwrap();
-
-        Self {
-            x: position.x - boundary.top_left.0,
-            y: position.y - boundary.top_left.1,
-            face: *cube_face,
-            facing: position.facing,
-        }
+            .position(|face| {
+                let (left, top) = face.corner;
+                (left..left + self.size).contains(&x) && (top..top + self.size).contains(&y)
+            })
+            .unwrap()
     }
 
-    fn example_move_in_direction(&self, direction: Orientation, size: isize) -> Self {
-        match (self.face, direction) {
-            (CubeFace::A, Orientation::Up) => Self {
-                x: size - self.x - 1,
-                y: 0,
-                face: CubeFace::B,
-                facing: Orientation::Down,
-            },
-            (CubeFace::A, Orientation::Down) => Self {
-                x: self.x,
-                y: 0,
-                face: CubeFace::D,
-                facing: Orientation::Down,
-            },
-            (CubeFace::A, Orientation::Left) => Self {
-                x: self.y,
-                y: 0,
-                face: CubeFace::C,
-                facing: Orientation::Down,
-            },
-            (CubeFace::A, Orientation::Right) => Self {
-                x: size - 1,
-                y: size - self.y - 1,
-                face: CubeFace::F,
-                facing: Orientation::Left,
-            },
-            (CubeFace::B, Orientation::Up) => Self {
-                x: size - self.x - 1,
-                y: 0,
-                face: CubeFace::A,
-                facing: Orientation::Down,
-            },
-            (CubeFace::B, Orientation::Down) => Self {
-                x: size - self.x - 1,
-                y: size - 1,
-                face: CubeFace::E,
-                facing: Orientation::Up,
-            },
-            (CubeFace::B, Orientation::Left) => Self {
-                x: size - self.y - 1,
-                y: size - 1,
-                face: CubeFace::F,
-                facing: Orientation::Up,
-            },
-            (CubeFace::B, Orientation::Right) => Self {
-                x: 0,
-                y: self.y,
-                face: CubeFace::C,
-                facing: Orientation::Right,
-            },
-            (CubeFace::C, Orientation::Up) => Self {
-                x: 0,
-                y: self.x,
-                face: CubeFace::A,
-                facing: Orientation::Right,
-            },
-            (CubeFace::C, Orientation::Down) => Self {
-                x: 0,
-                y: self.x,
-                face: CubeFace::E,
-                facing: Orientation::Right,
-            },
-            (CubeFace::C, Orientation::Left) => Self {
-                x: size - 1,
-                y: self.y,
-                face: CubeFace::B,
-                facing: Orientation::Left,
-            },
-            (CubeFace::C, Orientation::Right) => Self {
-                x: 0,
-                y: self.y,
-                face: CubeFace::D,
-                facing: Orientation::Right,
-            },
-            (CubeFace::D, Orientation::Up) => Self {
-                x: self.x,
-                y: size - 1,
-                face: CubeFace::A,
-                facing: Orientation::Up,
-            },
-            (CubeFace::D, Orientation::Down) => Self {
-                x: self.x,
-                y: 0,
-                face: CubeFace::E,
-                facing: Orientation::Down,
-            },
-            (CubeFace::D, Orientation::Left) => Self {
-                x: size - 1,
-                y: self.y,
-                face: CubeFace::C,
-                facing: Orientation::Left,
-            },
-            (CubeFace::D, Orientation::Right) => Self {
-                x: size - self.y - 1,
-                y: 0,
-                face: CubeFace::F,
-                facing: Orientation::Up,
-            },
-            (CubeFace::E, Orientation::Up) => Self {
-                x: self.x,
-                y: size - 1,
-                face: CubeFace::D,
-                facing: Orientation::Up,
-            },
-            (CubeFace::E, Orientation::Down) => Self {
-                x: size - self.x - 1,
-                y: size - 1,
-                face: CubeFace::B,
-                facing: Orientation::Up,
-            },
-            (CubeFace::E, Orientation::Left) => Self {
-                x: size - self.y - 1,
-                y: size - 1,
-                face: CubeFace::C,
-                facing: Orientation::Up,
-            },
-            (CubeFace::E, Orientation::Right) => Self {
-                x: 0,
-                y: self.y,
-                face: CubeFace::F,
-                facing: Orientation::Right,
-            },
-            (CubeFace::F, Orientation::Up) => Self {
-                x: size - 1,
-                y: size - self.x - 1,
-                face: CubeFace::D,
-                facing: Orientation::Left,
-            },
-            (CubeFace::F, Orientation::Down) => Self {
-                x: 0,
-                y: size - self.x - 1,
-                face: CubeFace::B,
-                facing: Orientation::Right,
-            },
-            (CubeFace::F, Orientation::Left) => Self {
-                x: size - 1,
-                y: self.y,
-                face: CubeFace::E,
-                facing: Orientation::Left,
-            },
-            (CubeFace::F, Orientation::Right) => Self {
-                x: size - 1,
-                y: size - self.y - 1,
-                face: CubeFace::E,
-                facing: Orientation::Left,
-            },
+    // one step ahead, going round onto another face where the map runs out. walls aren't checked
+    fn step(&self, map: &Board, position: Position) -> Position {
+        let (dx, dy) = position.facing.step();
+        let next = (position.x + dx, position.y + dy);
+        if spot_at(map, next).is_some() {
+            return position.set_coordinate(next);
         }
-    }
 
-    fn real_move_in_direction(&self, direction: Orientation, size: isize) -> Self {
-        match (self.face, direction) {
-            (CubeFace::A, Orientation::Up) => Self {
-                x: 0,
-                y: self.x,
-                face: CubeFace::F,
-                facing: Orientation::Right,
-            },
-            (CubeFace::A, Orientation::Down) => Self {
-                x: self.x,
-                y: 0,
-                face: CubeFace::C,
-                facing: Orientation::Down,
-            },
-            (CubeFace::A, Orientation::Left) => Self {
-                x: 0,
-                y: size - self.y - 1,
-                face: CubeFace::D,
-                facing: Orientation::Right,
-            },
-            (CubeFace::A, Orientation::Right) => Self {
-                x: 0,
-                y: self.y,
-                face: CubeFace::B,
-                facing: Orientation::Right,
-            },
-            (CubeFace::B, Orientation::Up) => Self {
-                x: self.x,
-                y: size - 1,
-                face: CubeFace::F,
-                facing: Orientation::Up,
-            },
-            (CubeFace::B, Orientation::Down) => Self {
-                x: size - 1,
-                y: self.x,
-                face: CubeFace::C,
-                facing: Orientation::Left,
-            },
-            (CubeFace::B, Orientation::Left) => Self {
-                x: size - 1,
-                y: self.y,
-                face: CubeFace::A,
-                facing: Orientation::Left,
-            },
-            (CubeFace::B, Orientation::Right) => Self {
-                x: size - 1,
-                y: size - self.y - 1,
-                face: CubeFace::E,
-                facing: Orientation::Left,
-            },
-            (CubeFace::C, Orientation::Up) => Self {
-                x: self.x,
-                y: size - 1,
-                face: CubeFace::A,
-                facing: Orientation::Up,
-            },
-            (CubeFace::C, Orientation::Down) => Self {
-                x: self.x,
-                y: 0,
-                face: CubeFace::E,
-                facing: Orientation::Down,
-            },
-            (CubeFace::C, Orientation::Left) => Self {
-                x: self.y,
-                y: 0,
-                face: CubeFace::D,
-                facing: Orientation::Down,
-            },
-            (CubeFace::C, Orientation::Right) => Self {
-                x: self.y,
-                y: size - 1,
-                face: CubeFace::B,
-                facing: Orientation::Up,
-            },
-            (CubeFace::D, Orientation::Up) => Self {
-                x: 0,
-                y: self.x,
-                face: CubeFace::C,
-                facing: Orientation::Right,
-            },
-            (CubeFace::D, Orientation::Down) => Self {
-                x: self.x,
-                y: 0,
-                face: CubeFace::F,
-                facing: Orientation::Down,
-            },
-            (CubeFace::D, Orientation::Left) => Self {
-                x: 0,
-                y: size - self.y - 1,
-                face: CubeFace::A,
-                facing: Orientation::Right,
-            },
-            (CubeFace::D, Orientation::Right) => Self {
-                x: 0,
-                y: self.y,
-                face: CubeFace::E,
-                facing: Orientation::Right,
-            },
-            (CubeFace::E, Orientation::Up) => Self {
-                x: self.x,
-                y: size - 1,
-                face: CubeFace::C,
-                facing: Orientation::Up,
-            },
-            (CubeFace::E, Orientation::Down) => Self {
-                x: size - 1,
-                y: self.x,
-                face: CubeFace::F,
-                facing: Orientation::Left,
-            },
-            (CubeFace::E, Orientation::Left) => Self {
-                x: size - 1,
-                y: self.y,
-                face: CubeFace::D,
-                facing: Orientation::Left,
-            },
-            (CubeFace::E, Orientation::Right) => Self {
-                x: size - 1,
-                y: size - self.y - 1,
-                face: CubeFace::B,
-                facing: Orientation::Left,
-            },
-            (CubeFace::F, Orientation::Up) => Self {
-                x: self.x,
-                y: size - 1,
-                face: CubeFace::D,
-                facing: Orientation::Up,
-            },
-            (CubeFace::F, Orientation::Down) => Self {
-                x: self.x,
-                y: 0,
-                face: CubeFace::B,
-                facing: Orientation::Down,
-            },
-            (CubeFace::F, Orientation::Left) => Self {
-                x: self.y,
-                y: 0,
-                face: CubeFace::A,
-                facing: Orientation::Down,
-            },
-            (CubeFace::F, Orientation::Right) => Self {
-                x: self.y,
-                y: size - 1,
-                face: CubeFace::E,
-                facing: Orientation::Up,
-            },
-        }
-    }
+        let index = self.face_of((position.x, position.y));
+        let face = &self.faces[index];
+        let (x, y) = (position.x - face.corner.0, position.y - face.corner.1);
+        let glue = self.glues[index][position.facing.index()];
 
-    fn to_global_position(self, cube_layout: &CubeLayout) -> CubePosition {
-        let cube_boundary = cube_layout.get(&self.face).unwrap();
-        CubePosition {
-            x: cube_boundary.top_left.0 + self.x,
-            y: cube_boundary.top_left.1 + self.y,
-            facing: self.facing,
+        let along = match position.facing {
+            Orientation::Up | Orientation::Down => x,
+            Orientation::Left | Orientation::Right => y,
+        };
+        let along = if glue.flipped {
+            self.size - 1 - along
+        } else {
+            along
+        };
+        let (x, y) = match glue.side {
+            Orientation::Up => (along, 0),
+            Orientation::Down => (along, self.size - 1),
+            Orientation::Left => (0, along),
+            Orientation::Right => (self.size - 1, along),
+        };
+
+        let (left, top) = self.faces[glue.face].corner;
+        Position {
+            x: left + x,
+            y: top + y,
+            facing: glue.side.opposite(),
         }
     }
 }
 
 impl Orientation {
+    const ALL: [Orientation; 4] = [
+        Orientation::Up,
+        Orientation::Down,
+        Orientation::Left,
+        Orientation::Right,
+    ];
+
     fn rotate_right(&self) -> Self {
         match self {
             Orientation::Up => Orientation::Right,
@@ -954,65 +546,171 @@ impl Orientation {
             Orientation::Right => Orientation::Up,
         }
     }
+
+    fn opposite(&self) -> Self {
+        self.rotate_left().rotate_left()
+    }
+
+    fn step(&self) -> (isize, isize) {
+        match self {
+            Orientation::Up => (0, -1),
+            Orientation::Down => (0, 1),
+            Orientation::Left => (-1, 0),
+            Orientation::Right => (1, 0),
+        }
+    }
+
+    // where it is in `ALL`
+    fn index(&self) -> usize {
+        match self {
+            Orientation::Up => 0,
+            Orientation::Down => 1,
+            Orientation::Left => 2,
+            Orientation::Right => 3,
+        }
+    }
 }
 
 #[cfg(test)]
 mod day22_tests {
-    use crate::day22::{self, CubeFacePosition};
+    use crate::day22::{input_parser, tiles, Board, Cube, Day22, Orientation, Position, Spot};
+    use aoc_common::grid::Grid;
+    use aoc_common::solution::{solve, Answer};
+
+    const EXAMPLE: &str = "        ...#
+        .#..
+        #...
+        ....
+...#.......#
+........#...
+..#....#....
+..........#.
+        ...#....
+        .....#..
+        .#......
+        ......#.
+
+10R5L5R10L4R5L5
+";
+
+    // every way six squares can be laid out to fold into a cube, give or take turning it over
+    const NETS: [&str; 11] = [
+        "#...\n####\n#...",
+        "#...\n####\n.#..",
+        "#...\n####\n..#.",
+        "#...\n####\n...#",
+        ".#..\n####\n.#..",
+        ".#..\n####\n..#.",
+        "##..\n.###\n.#..",
+        "##..\n.###\n..#.",
+        "##..\n.###\n...#",
+        "##..\n.##.\n..##",
+        "###..\n..###",
+    ];
 
-    use super::{input_parser, CubePosition};
+    // a net with every face `size` open tiles wide
+    fn open_net(net: &str, size: usize) -> Board {
+        let rows: Vec<&str> = net.lines().collect();
+        let width = rows.iter().map(|row| row.len()).max().unwrap();
+        Grid::from_fn(width * size, rows.len() * size, |(x, y)| {
+            let row = rows[y as usize / size].as_bytes();
+            match row.get(x as usize / size) {
+                Some(b'#') => Some(Spot::Open),
+                _ => None,
+            }
+        })
+    }
 
     #[test]
-    fn test_1() {
-        let input = include_str!("../../data/2022/22-example.txt");
-        let size = 4;
+    fn walking_off_the_example() {
+        let (_, (map, _directions)) = input_parser(EXAMPLE).unwrap();
+        let cube = Cube::fold(&map).unwrap();
+        assert_eq!(cube.size, 4);
 
-        let (_, (map, _directions)) = input_parser(input).unwrap();
-
-        let position = CubePosition {
+        // the puzzle's A to B
+        let position = Position {
             x: 11,
             y: 5,
-            facing: day22::Orientation::Right,
+            facing: Orientation::Right,
         };
+        assert_eq!(
+            cube.step(&map, position),
+            Position {
+                x: 14,
+                y: 8,
+                facing: Orientation::Down
+            }
+        );
 
-        let cube_layout = day22::map_to_cube(&map, size);
-
-        let relative_in_current_cube =
-            CubeFacePosition::from_global_position(&position, &cube_layout);
-
-        assert_eq!(relative_in_current_cube.face, day22::CubeFace::D);
-
-        let result = position.move_right(&map, &cube_layout, size);
-
-        assert_eq!(result.x, 14);
-        assert_eq!(result.y, 8);
-        assert_eq!(result.facing, day22::Orientation::Up);
-    }
-
-    #[test]
-    fn test_2() {
-        let input = include_str!("../../data/2022/22-example.txt");
-        let size = 4;
-
-        let (_, (map, _directions)) = input_parser(input).unwrap();
-
-        let position = CubePosition {
+        let position = Position {
             x: 10,
             y: 11,
-            facing: day22::Orientation::Down,
+            facing: Orientation::Down,
         };
+        assert_eq!(
+            cube.step(&map, position),
+            Position {
+                x: 1,
+                y: 7,
+                facing: Orientation::Up
+            }
+        );
+    }
 
-        let cube_layout = day22::map_to_cube(&map, size);
+    #[test]
+    fn every_net_folds() {
+        for net in NETS {
+            for size in [1, 3] {
+                let map = open_net(net, size);
+                let cube = Cube::fold(&map).unwrap_or_else(|| panic!("{net} doesn't fold"));
+
+                for (index, glues) in cube.glues.iter().enumerate() {
+                    for (side, glue) in Orientation::ALL.iter().zip(glues) {
+                        let back = cube.glues[glue.face][glue.side.index()];
+                        assert_eq!((back.face, back.side), (index, *side), "{net}");
+                        assert_eq!(back.flipped, glue.flipped, "{net}");
+                    }
+                }
 
-        let relative_in_current_cube =
-            CubeFacePosition::from_global_position(&position, &cube_layout);
+                // going straight on round a cube brings you back where you started
+                for ((x, y), _) in tiles(&map) {
+                    for facing in Orientation::ALL {
+                        let start = Position { x, y, facing };
+                        let end =
+                            (0..4 * size).fold(start, |position, _| cube.step(&map, position));
+                        assert_eq!(end, start, "{net}");
+                    }
+                }
+            }
+        }
+    }
 
-        assert_eq!(relative_in_current_cube.face, day22::CubeFace::E);
+    #[test]
+    fn not_a_cube() {
+        for net in [
+            "####\n####",
+            "##\n##\n##",
+            "#####\n.#...",
+            "####..\n...##",
+            "#",
+        ] {
+            assert!(Cube::fold(&open_net(net, 2)).is_none(), "{net}");
+        }
+    }
 
-        let result = position.move_down(&map, &cube_layout, size);
+    #[test]
+    fn example_part1() {
+        assert_eq!(
+            solve::<Day22>(EXAMPLE, &[1], &()).unwrap(),
+            [Answer::Integer(6032)]
+        );
+    }
 
-        assert_eq!(result.x, 1);
-        assert_eq!(result.y, 7);
-        assert_eq!(result.facing, day22::Orientation::Up);
+    #[test]
+    fn example_part2() {
+        assert_eq!(
+            solve::<Day22>(EXAMPLE, &[2], &()).unwrap(),
+            [Answer::Integer(5031)]
+        );
     }
 }
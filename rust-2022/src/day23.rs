@@ -1,276 +1,401 @@
-use itertools::Itertools;
+use aoc_common::frame;
+use aoc_common::grid::Position;
+use aoc_common::params;
+use aoc_common::parse::{parse_all, ParseError};
+use aoc_common::render::{self, Canvas};
+use aoc_common::solution::{Answer, Solution};
 use nom::{
-    bytes::complete::tag,
-    character::complete::anychar,
-    combinator::{map_res, opt},
-    multi::separated_list0,
     multi::{many1, separated_list1},
     IResult,
 };
-use std::{cmp::Ordering, hash::Hash};
-use std::{collections::BinaryHeap, time::SystemTime};
-use std::{
-    collections::{HashMap, HashSet},
-    ptr,
-};
-use std::{fmt, ops};
+use std::str::FromStr;
 
-type Input = HashSet<ElfSpot>;
+type Input = ElfGrid;
 
-#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
-struct ElfSpot {
-    x: isize,
-    y: isize,
+params! {
+    // the directions elves consider, in order for the first round, and how many rounds part 1 runs
+    pub struct Day23Params {
+        rules: RuleOrder = (example: RuleOrder::default(), real: RuleOrder::default()),
+        rounds: usize = (example: 10, real: 10),
+    }
 }
 
-struct BoundingBox {
-    min_x: isize,
-    min_y: isize,
-    max_x: isize,
-    max_y: isize,
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    North,
+    South,
+    West,
+    East,
 }
 
-pub fn part1() -> isize {
-    let input = include_str!("../../data/2022/23.txt");
-    // let input = include_str!("../../data/2022/23-example.txt");
-
-    match input_parser(input) {
-        Ok((remaining_input, mut map)) if remaining_input.is_empty() => {
-            println!("parsed entire input");
-
-            for round in 0..10 {
-                let mut proposed_moves = vec![];
-                for elf in map.iter() {
-                    if elf.has_neighbors(&map) {
-                        if let Some(proposed_spot) = elf.propose_new_spot(&map, round) {
-                            proposed_moves.push((*elf, proposed_spot));
-                        }
-                    }
-                }
+impl Direction {
+    fn step(self) -> Position {
+        match self {
+            Direction::North => (0, -1),
+            Direction::South => (0, 1),
+            Direction::West => (-1, 0),
+            Direction::East => (1, 0),
+        }
+    }
 
-                for (new_spot, mut proposals_to_spot) in proposed_moves
-                    .iter()
-                    .sorted_by(|a, b| a.1.cmp(&b.1))
-                    .group_by(|(_, spot)| spot)
-                    .into_iter()
-                {
-                    let elf_proposal = proposals_to_spot.next().unwrap();
-                    if proposals_to_spot.count() == 0 {
-                        //then move
-                        map.remove(&elf_proposal.0);
-                        map.insert(*new_spot);
-                    }
-                }
-            }
+    // the spot in this direction and the two diagonals either side of it, all of which have to be
+    // free for an elf to go this way
+    fn looks(self) -> [Position; 3] {
+        let (x, y) = self.step();
+        if x == 0 {
+            [(-1, y), (0, y), (1, y)]
+        } else {
+            [(x, -1), (x, 0), (x, 1)]
+        }
+    }
+}
 
-            let smallest_boundary = bounding_box(&map);
-            let mut empty_counter: isize = 0;
-            for y in smallest_boundary.min_y..(smallest_boundary.max_y + 1) {
-                for x in smallest_boundary.min_x..(smallest_boundary.max_x + 1) {
-                    if !map.contains(&ElfSpot { x, y }) {
-                        empty_counter += 1;
-                        // print!(".");
-                    } else {
-                        // print!("#");
-                    }
-                }
-                // println!();
-            }
+// the puzzle tries north, south, west then east, starting one further down the list every round
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RuleOrder(pub Vec<Direction>);
+
+impl Default for RuleOrder {
+    fn default() -> Self {
+        RuleOrder(vec![
+            Direction::North,
+            Direction::South,
+            Direction::West,
+            Direction::East,
+        ])
+    }
+}
 
-            empty_counter
-        }
-        Ok((remaining, _)) => {
-            println!("remaining unparsed \"{remaining}\"");
-            0
+// written as the directions' initials, "NSWE" being the default
+impl FromStr for RuleOrder {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut directions = vec![];
+        for (index, c) in s.char_indices() {
+            let direction = match c {
+                'N' => Direction::North,
+                'S' => Direction::South,
+                'W' => Direction::West,
+                'E' => Direction::East,
+                _ => return Err(ParseError::at(s, &s[index..], "one of N, S, W or E")),
+            };
+            if directions.contains(&direction) {
+                return Err(ParseError::at(s, &s[index..], "a direction not yet listed"));
+            }
+            directions.push(direction);
         }
-        Err(e) => {
-            println!("error parsing \"{e}\"");
-            0
+
+        if directions.is_empty() {
+            return Err(ParseError::at(s, s, "at least one direction"));
         }
+
+        Ok(RuleOrder(directions))
     }
 }
 
-pub fn part2() -> usize {
-    let input = include_str!("../../data/2022/23.txt");
-    // let input = include_str!("../../data/2022/23-example.txt");
+// a bit per spot over a rectangle that grows as the elves spread out. `origin` is where the
+// rectangle's top left corner is, rows are padded to whole words
+#[derive(Debug, Clone)]
+pub struct ElfGrid {
+    origin: Position,
+    width: usize,
+    height: usize,
+    words_per_row: usize,
+    bits: Vec<u64>,
+    len: usize,
+}
+
+// how much room is added around the elves whenever they reach the edge
+const GROWTH: usize = 8;
+
+impl ElfGrid {
+    pub fn new(origin: Position, width: usize, height: usize) -> Self {
+        let words_per_row = width.div_ceil(64);
+        ElfGrid {
+            origin,
+            width,
+            height,
+            words_per_row,
+            bits: vec![0; words_per_row * height],
+            len: 0,
+        }
+    }
 
-    match input_parser(input) {
-        Ok((remaining_input, mut map)) if remaining_input.is_empty() => {
-            println!("parsed entire input");
+    pub fn len(&self) -> usize {
+        self.len
+    }
 
-            let mut round: usize = 0;
-            loop {
-                let mut moves_occurred = false;
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
 
-                let mut proposed_moves = vec![];
-                for elf in map.iter() {
-                    if elf.has_neighbors(&map) {
-                        if let Some(proposed_spot) = elf.propose_new_spot(&map, round) {
-                            proposed_moves.push((*elf, proposed_spot));
-                        }
-                    }
-                }
+    // the spot's number within the rectangle, counting along the rows
+    fn cell(&self, (x, y): Position) -> Option<usize> {
+        let x = usize::try_from(x - self.origin.0).ok()?;
+        let y = usize::try_from(y - self.origin.1).ok()?;
+        (x < self.width && y < self.height).then_some(y * self.width + x)
+    }
 
-                for (new_spot, mut proposals_to_spot) in proposed_moves
-                    .iter()
-                    .sorted_by(|a, b| a.1.cmp(&b.1))
-                    .group_by(|(_, spot)| spot)
-                    .into_iter()
-                {
-                    let elf_proposal = proposals_to_spot.next().unwrap();
-                    if proposals_to_spot.count() == 0 {
-                        //then move
-                        map.remove(&elf_proposal.0);
-                        map.insert(*new_spot);
-                        moves_occurred = true;
-                    }
-                }
+    fn bit(&self, position: Position) -> Option<(usize, u64)> {
+        let cell = self.cell(position)?;
+        let (x, y) = (cell % self.width, cell / self.width);
+        Some((y * self.words_per_row + x / 64, 1 << (x % 64)))
+    }
 
-                if !moves_occurred {
-                    return round + 1;
-                }
+    pub fn contains(&self, position: Position) -> bool {
+        self.bit(position)
+            .is_some_and(|(word, mask)| self.bits[word] & mask != 0)
+    }
 
-                round += 1;
-            }
+    pub fn insert(&mut self, position: Position) {
+        if self.bit(position).is_none() {
+            self.grow_to(position, position);
         }
-        Ok((remaining, _)) => {
-            println!("remaining unparsed \"{remaining}\"");
-            0
+
+        let (word, mask) = self.bit(position).unwrap();
+        if self.bits[word] & mask == 0 {
+            self.bits[word] |= mask;
+            self.len += 1;
         }
-        Err(e) => {
-            println!("error parsing \"{e}\"");
-            0
+    }
+
+    pub fn remove(&mut self, position: Position) {
+        if let Some((word, mask)) = self.bit(position) {
+            if self.bits[word] & mask != 0 {
+                self.bits[word] &= !mask;
+                self.len -= 1;
+            }
         }
     }
-}
 
-fn bounding_box(elves: &HashSet<ElfSpot>) -> BoundingBox {
-    BoundingBox {
-        min_x: elves.iter().min_by(|a, b| a.x.cmp(&b.x)).unwrap().x,
-        min_y: elves.iter().min_by(|a, b| a.y.cmp(&b.y)).unwrap().y,
-        max_x: elves.iter().max_by(|a, b| a.x.cmp(&b.x)).unwrap().x,
-        max_y: elves.iter().max_by(|a, b| a.y.cmp(&b.y)).unwrap().y,
+    pub fn elves(&self) -> impl Iterator<Item = Position> + '_ {
+        self.bits
+            .iter()
+            .enumerate()
+            .flat_map(move |(index, &word)| {
+                let row = index / self.words_per_row;
+                let first = (index % self.words_per_row) * 64;
+                let mut rest = word;
+                std::iter::from_fn(move || {
+                    if rest == 0 {
+                        return None;
+                    }
+                    let x = first + rest.trailing_zeros() as usize;
+                    rest &= rest - 1;
+                    Some((self.origin.0 + x as isize, self.origin.1 + row as isize))
+                })
+            })
     }
-}
 
-impl ElfSpot {
-    fn has_neighbors(&self, elves: &HashSet<ElfSpot>) -> bool {
-        for y_diff in -1..2 {
-            for x_diff in -1..2 {
-                if x_diff == 0 && y_diff == 0 {
-                    continue;
-                }
-
-                if elves.contains(&ElfSpot {
-                    x: self.x + x_diff,
-                    y: self.y + y_diff,
-                }) {
-                    return true;
-                }
+    // the top left and bottom right elves' corners
+    pub fn bounding_box(&self) -> Option<(Position, Position)> {
+        self.elves().fold(None, |bounds, (x, y)| match bounds {
+            None => Some(((x, y), (x, y))),
+            Some(((min_x, min_y), (max_x, max_y))) => {
+                Some(((min_x.min(x), min_y.min(y)), (max_x.max(x), max_y.max(y))))
             }
-        }
+        })
+    }
 
-        false
-    }
-
-    fn propose_new_spot(&self, elves: &HashSet<ElfSpot>, round_number: usize) -> Option<Self> {
-        // If there is no Elf in the N, NE, or NW adjacent positions, the Elf proposes moving north one step.
-        // If there is no Elf in the S, SE, or SW adjacent positions, the Elf proposes moving south one step.
-        // If there is no Elf in the W, NW, or SW adjacent positions, the Elf proposes moving west one step.
-        // If there is no Elf in the E, NE, or SE adjacent positions, the Elf proposes moving east one step.
-        for i in round_number..(round_number + 4) {
-            if i % 4 == 0 && self.is_north_open(elves) {
-                return Some(self.to_north());
-            } else if i % 4 == 1 && self.is_south_open(elves) {
-                return Some(self.to_south());
-            } else if i % 4 == 2 && self.is_west_open(elves) {
-                return Some(self.to_west());
-            } else if i % 4 == 3 && self.is_east_open(elves) {
-                return Some(self.to_east());
-            }
+    // makes sure there's at least `margin` free spots between every elf and the edge
+    fn keep_margin(&mut self, margin: usize) {
+        let Some(((min_x, min_y), (max_x, max_y))) = self.bounding_box() else {
+            return;
+        };
+        let margin = margin as isize;
+        if self.cell((min_x - margin, min_y - margin)).is_none()
+            || self.cell((max_x + margin, max_y + margin)).is_none()
+        {
+            self.grow_to(
+                (min_x - margin, min_y - margin),
+                (max_x + margin, max_y + margin),
+            );
         }
-
-        None
     }
 
-    fn is_north_open(&self, elves: &HashSet<ElfSpot>) -> bool {
-        for x_diff in -1..2 {
-            if elves.contains(&ElfSpot {
-                x: self.x + x_diff,
-                y: self.y - 1,
-            }) {
-                return false;
-            }
+    // rebuilds the rectangle to cover what it did and the corners given, with room to spare on
+    // the sides that had to move
+    fn grow_to(&mut self, (min_x, min_y): Position, (max_x, max_y): Position) {
+        let growth = GROWTH as isize;
+        let (old_left, old_top) = self.origin;
+        let old_right = old_left + self.width as isize - 1;
+        let old_bottom = old_top + self.height as isize - 1;
+
+        let left = if min_x < old_left {
+            min_x - growth
+        } else {
+            old_left
+        };
+        let top = if min_y < old_top {
+            min_y - growth
+        } else {
+            old_top
+        };
+        let right = if max_x > old_right {
+            max_x + growth
+        } else {
+            old_right
+        };
+        let bottom = if max_y > old_bottom {
+            max_y + growth
+        } else {
+            old_bottom
+        };
+
+        let mut grown = ElfGrid::new(
+            (left, top),
+            (right - left + 1) as usize,
+            (bottom - top + 1) as usize,
+        );
+        for elf in self.elves() {
+            grown.insert(elf);
         }
 
-        true
+        *self = grown;
     }
 
-    fn is_south_open(&self, elves: &HashSet<ElfSpot>) -> bool {
-        for x_diff in -1..2 {
-            if elves.contains(&ElfSpot {
-                x: self.x + x_diff,
-                y: self.y + 1,
-            }) {
-                return false;
-            }
+    fn has_neighbors(&self, (x, y): Position) -> bool {
+        (-1..=1)
+            .flat_map(|dy| (-1..=1).map(move |dx| (dx, dy)))
+            .any(|(dx, dy)| (dx, dy) != (0, 0) && self.contains((x + dx, y + dy)))
+    }
+}
+
+// the elves and the rules they move by. the rules are tried in order starting from the round
+// number, wrapping around the list
+pub struct Diffusion {
+    elves: ElfGrid,
+    rules: RuleOrder,
+    rounds: usize,
+    // how many elves want each spot this round, numbered like the grid's cells
+    proposals: Vec<u8>,
+}
+
+impl Diffusion {
+    pub fn new(elves: ElfGrid, rules: RuleOrder) -> Self {
+        Diffusion {
+            elves,
+            rules,
+            rounds: 0,
+            proposals: vec![],
         }
+    }
 
-        true
+    pub fn elves(&self) -> &ElfGrid {
+        &self.elves
     }
 
-    fn is_east_open(&self, elves: &HashSet<ElfSpot>) -> bool {
-        for y_diff in -1..2 {
-            if elves.contains(&ElfSpot {
-                x: self.x + 1,
-                y: self.y + y_diff,
-            }) {
-                return false;
-            }
+    pub fn rounds(&self) -> usize {
+        self.rounds
+    }
+
+    fn proposal(&self, elf: Position) -> Option<Position> {
+        if !self.elves.has_neighbors(elf) {
+            return None;
         }
 
-        true
+        let rules = &self.rules.0;
+        (0..rules.len())
+            .map(|offset| rules[(self.rounds + offset) % rules.len()])
+            .find(|direction| {
+                direction
+                    .looks()
+                    .iter()
+                    .all(|(dx, dy)| !self.elves.contains((elf.0 + dx, elf.1 + dy)))
+            })
+            .map(|direction| {
+                let (dx, dy) = direction.step();
+                (elf.0 + dx, elf.1 + dy)
+            })
     }
 
-    fn is_west_open(&self, elves: &HashSet<ElfSpot>) -> bool {
-        for y_diff in -1..2 {
-            if elves.contains(&ElfSpot {
-                x: self.x - 1,
-                y: self.y + y_diff,
-            }) {
-                return false;
+    // plays one round and says how many elves moved
+    pub fn round(&mut self) -> usize {
+        // nobody moves more than one spot, so that much room means every proposal has a cell
+        self.elves.keep_margin(1);
+        self.proposals.clear();
+        self.proposals
+            .resize(self.elves.width * self.elves.height, 0);
+
+        let moves: Vec<(Position, Position, usize)> = self
+            .elves
+            .elves()
+            .filter_map(|elf| {
+                let to = self.proposal(elf)?;
+                Some((elf, to, self.elves.cell(to).unwrap()))
+            })
+            .collect();
+        for &(_, _, cell) in &moves {
+            self.proposals[cell] = self.proposals[cell].saturating_add(1);
+        }
+
+        let mut moved = 0;
+        for (from, to, cell) in moves {
+            if self.proposals[cell] == 1 {
+                self.elves.remove(from);
+                self.elves.insert(to);
+                moved += 1;
             }
         }
 
-        true
+        self.rounds += 1;
+        moved
     }
 
-    fn to_north(&self) -> Self {
-        Self {
-            x: self.x,
-            y: self.y - 1,
+    // `snapshot` sees the elves after every round along with how many rounds have been played
+    pub fn run(&mut self, rounds: usize, mut snapshot: impl FnMut(usize, &ElfGrid)) {
+        for _ in 0..rounds {
+            self.round();
+            snapshot(self.rounds, &self.elves);
         }
     }
 
-    fn to_south(&self) -> Self {
-        Self {
-            x: self.x,
-            y: self.y + 1,
+    // plays until a round where no elf moves and gives that round's number, counting from 1
+    pub fn run_until_stable(&mut self, mut snapshot: impl FnMut(usize, &ElfGrid)) -> usize {
+        loop {
+            let moved = self.round();
+            snapshot(self.rounds, &self.elves);
+            if moved == 0 {
+                return self.rounds;
+            }
         }
     }
+}
 
-    fn to_east(&self) -> Self {
-        Self {
-            x: self.x + 1,
-            y: self.y,
-        }
+pub fn draw_elves(elves: &ElfGrid) -> Canvas {
+    render::points(elves.elves().map(|elf| (elf, '#'.into())), '.'.into())
+}
+
+pub struct Day23;
+
+impl Solution for Day23 {
+    type Input<'a> = Input;
+
+    type Params = Day23Params;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse_all(input, input_parser)
     }
 
-    fn to_west(&self) -> Self {
-        Self {
-            x: self.x - 1,
-            y: self.y,
-        }
+    fn part1(elves: &Self::Input<'_>, params: &Self::Params) -> Answer {
+        let mut diffusion = Diffusion::new(elves.clone(), params.rules.clone());
+        diffusion.run(params.rounds, |_, elves| frame!(draw_elves(elves)));
+
+        let elves = diffusion.elves();
+        let Some(((min_x, min_y), (max_x, max_y))) = elves.bounding_box() else {
+            return 0.into();
+        };
+        let area = (max_x - min_x + 1) * (max_y - min_y + 1);
+
+        (area - elves.len() as isize).into()
+    }
+
+    fn part2(elves: &Self::Input<'_>, params: &Self::Params) -> Answer {
+        let mut diffusion = Diffusion::new(elves.clone(), params.rules.clone());
+
+        diffusion
+            .run_until_stable(|_, elves| frame!(draw_elves(elves)))
+            .into()
     }
 }
 
@@ -284,25 +409,117 @@ fn input_parser(input: &str) -> IResult<&str, Input> {
     let (input, lines) =
         separated_list1(nom::character::complete::newline, map_line_parser)(input)?;
 
-    let mut map = HashSet::new();
+    let width = lines.iter().map(|row| row.len()).max().unwrap_or(0);
+    let mut elves = ElfGrid::new((0, 0), width, lines.len());
     for (y, row) in lines.iter().enumerate() {
         for (x, c) in row.iter().enumerate() {
             if *c == '#' {
-                map.insert(ElfSpot {
-                    x: x as isize,
-                    y: y as isize,
-                });
+                elves.insert((x as isize, y as isize));
             }
         }
     }
 
-    Ok((input, map))
+    Ok((input, elves))
 }
 
-// #[cfg(test)]
-// mod day23_tests {
-//     use crate::day23;
+#[cfg(test)]
+mod day23_tests {
+    use crate::day23::{draw_elves, Day23, Day23Params, Diffusion, Direction, ElfGrid, RuleOrder};
+    use aoc_common::params::Params;
+    use aoc_common::render;
+    use aoc_common::solution::{solve, Answer, Solution};
+
+    const EXAMPLE: &str = "....#..
+..###.#
+#...#.#
+.#...##
+#.###..
+##.#.##
+.#..#..
+";
+
+    const SMALL_EXAMPLE: &str = ".....
+..##.
+..#..
+.....
+..##.
+.....
+";
+
+    #[test]
+    fn example() {
+        assert_eq!(
+            solve::<Day23>(EXAMPLE, &[1, 2], &Day23Params::example()).unwrap(),
+            [Answer::Integer(110), Answer::Integer(20)]
+        );
+    }
 
-//     #[test]
-//     fn test_1() {}
-// }
+    #[test]
+    fn small_example() {
+        let elves = Day23::parse(SMALL_EXAMPLE).unwrap();
+        let mut diffusion = Diffusion::new(elves, RuleOrder::default());
+
+        let mut pictures = vec![];
+        let rounds = diffusion.run_until_stable(|round, elves| {
+            pictures.push((round, render::text(&draw_elves(elves))))
+        });
+
+        assert_eq!(rounds, 4);
+        assert_eq!(pictures.len(), 4);
+        assert_eq!(pictures[0], (1, "##\n..\n#.\n.#\n#.".to_owned()));
+        assert_eq!(
+            pictures[2],
+            (3, "..#..\n....#\n#....\n....#\n.....\n..#..".to_owned())
+        );
+        assert_eq!(pictures[3].1, pictures[2].1);
+    }
+
+    #[test]
+    fn rule_orders() {
+        assert_eq!("NSWE".parse(), Ok(RuleOrder::default()));
+        assert_eq!(
+            "EW".parse(),
+            Ok(RuleOrder(vec![Direction::East, Direction::West]))
+        );
+        assert!("NSWN".parse::<RuleOrder>().is_err());
+        assert!("NSXE".parse::<RuleOrder>().is_err());
+        assert!("".parse::<RuleOrder>().is_err());
+
+        let mut pair = ElfGrid::new((0, 0), 2, 1);
+        pair.insert((0, 0));
+        pair.insert((1, 0));
+
+        let mut diffusion = Diffusion::new(pair.clone(), RuleOrder::default());
+        assert_eq!(diffusion.round(), 2);
+        assert_eq!(
+            diffusion.elves().elves().collect::<Vec<_>>(),
+            [(0, -1), (1, -1)]
+        );
+
+        let mut diffusion = Diffusion::new(pair, "EWNS".parse().unwrap());
+        assert_eq!(diffusion.round(), 2);
+        assert_eq!(
+            diffusion.elves().elves().collect::<Vec<_>>(),
+            [(-1, 0), (2, 0)]
+        );
+    }
+
+    #[test]
+    fn elf_grid_grows_in_every_direction() {
+        let mut elves = ElfGrid::new((0, 0), 1, 1);
+        for elf in [(0, 0), (-100, 3), (70, -20), (5, 200)] {
+            elves.insert(elf);
+        }
+        elves.insert((70, -20));
+        elves.remove((0, 0));
+        elves.remove((1, 1));
+
+        assert_eq!(elves.len(), 3);
+        assert!(elves.contains((-100, 3)) && !elves.contains((0, 0)));
+        assert_eq!(elves.bounding_box(), Some(((-100, -20), (70, 200))));
+        assert_eq!(
+            elves.elves().collect::<Vec<_>>(),
+            [(70, -20), (-100, 3), (5, 200)]
+        );
+    }
+}
@@ -1,306 +1,163 @@
-use itertools::Itertools;
-use nom::{
-    bytes::complete::tag,
-    character::complete::anychar,
-    combinator::{map_res, opt},
-    multi::separated_list0,
-    multi::{many1, separated_list1},
-    IResult,
-};
-use std::{
-    cmp::Ordering,
-    collections::hash_map::DefaultHasher,
-    hash::{Hash, Hasher},
-};
-use std::{collections::BinaryHeap, time::SystemTime};
-use std::{
-    collections::{HashMap, HashSet},
-    ptr,
-};
-use std::{fmt, ops};
+use aoc_common::frame;
+use aoc_common::geometry::manhattan_distance;
+use aoc_common::grid::{Grid, Position};
+use aoc_common::parse::ParseError;
+use aoc_common::render::{Canvas, Cell, Colour};
+use aoc_common::search::a_star;
+use aoc_common::solution::{Answer, Solution};
 
-type Input = HashMap<(isize, isize), Spot>;
+type Input = Grid<Spot>;
 
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
-enum Spot {
+pub enum Spot {
     Open,
     MovingBlizzard(Vec<Blizzard>),
     Wall,
 }
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
-enum Blizzard {
+pub enum Blizzard {
     Left,
     Right,
     Up,
     Down,
 }
 
-#[derive(Clone, Eq, PartialEq)]
-struct Path {
-    cost: usize,
-    distance_to_finish: usize,
-    location: (isize, isize),
-}
+pub struct Day24;
 
-impl Ord for Path {
-    fn cmp(&self, other: &Self) -> Ordering {
-        other
-            .cost
-            .cmp(&self.cost)
-            .then(other.distance_to_finish.cmp(&self.distance_to_finish))
-    }
+impl Solution for Day24 {
+    type Input<'a> = Input;
 
-    // fn cmp(&self, other: &Self) -> Ordering {
-    //     other
-    //         .distance_to_finish
-    //         .cmp(&self.distance_to_finish)
-    //         .then(other.cost.cmp(&self.cost))
-    // }
-}
+    type Params = ();
 
-impl PartialOrd for Path {
-    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        Some(self.cmp(other))
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        Grid::parse(input, "'#', '.' or a blizzard", |c| match c {
+            '#' => Some(Spot::Wall),
+            '.' => Some(Spot::Open),
+            '<' => Some(Spot::MovingBlizzard(vec![Blizzard::Left])),
+            '>' => Some(Spot::MovingBlizzard(vec![Blizzard::Right])),
+            '^' => Some(Spot::MovingBlizzard(vec![Blizzard::Up])),
+            'v' => Some(Spot::MovingBlizzard(vec![Blizzard::Down])),
+            _ => None,
+        })
     }
-}
-
-pub fn part1() -> usize {
-    let input = include_str!("../../data/2022/24.txt");
-    // let input = include_str!("../../data/2022/24-example.txt");
-
-    match input_parser(input) {
-        Ok((remaining_input, mut map)) if remaining_input.is_empty() => {
-            println!("parsed entire input");
-
-            // let start = find_start(&map);
-            // let end = find_end(&map);
-
-            // let mut path_attempts: BinaryHeap<Path> = BinaryHeap::new();
-            // let mut map_history: HashMap<usize, Input> = HashMap::new();
-            // map_history.insert(0, map.clone());
-
-            // path_attempts.push(Path {
-            //     cost: 0,
-            //     location: start,
-            //     distance_to_finish: manhattan_distance(start, end),
-            // });
 
-            // let mut have_shortest_path: HashSet<((isize, isize), u64)> = HashSet::new();
-            // let mut is_duplication_path: HashSet<((isize, isize), usize)> = HashSet::new();
-            // let mut hash = DefaultHasher::new();
-            // map.iter().collect_vec().hash(&mut hash);
-            // have_shortest_path.insert((start, hash.finish()));
-            // is_duplication_path.insert((start, 0));
+    fn part1(map: &Self::Input<'_>, _params: &Self::Params) -> Answer {
+        let valley = Valley::new(map);
 
-            // while let Some(path) = path_attempts.pop() {
-            //     println!("current_cost: {}", path.cost);
-            //     let options = next_steps(&path, &mut map_history);
-
-            //     let last_map = map_history.get(&path.cost).unwrap();
-
-            //     for option in options {
-            //         if option == end {
-            //             return path.cost + 1;
-            //         }
-
-            //         let mut hash = DefaultHasher::new();
-            //         last_map.iter().collect_vec().hash(&mut hash);
-            //         let option_hash = (option, hash.finish());
-            //         if have_shortest_path.contains(&option_hash) {
-            //             continue;
-            //         }
-            //         have_shortest_path.insert(option_hash);
-
-            //         if is_duplication_path.contains(&(option, path.cost)) {
-            //             continue;
-            //         }
-
-            //         is_duplication_path.insert((option, path.cost));
-
-            //         path_attempts.push(Path {
-            //             cost: path.cost + 1,
-            //             location: option,
-            //             distance_to_finish: manhattan_distance(option, end),
-            //         })
-            //     }
-            // }
-
-            // println!("could not find a solution ");
-            0
-        }
-        Ok((remaining, _)) => {
-            println!("remaining unparsed \"{remaining}\"");
-            0
-        }
-        Err(e) => {
-            println!("error parsing \"{e}\"");
-            0
-        }
+        valley.shortest_path(valley.start, valley.end, 0).into()
     }
-}
-
-pub fn part2() -> usize {
-    let input = include_str!("../../data/2022/24.txt");
-    // let input = include_str!("../../data/2022/24-example.txt");
 
-    match input_parser(input) {
-        Ok((remaining_input, map)) if remaining_input.is_empty() => {
-            println!("parsed entire input");
+    fn part2(map: &Self::Input<'_>, _params: &Self::Params) -> Answer {
+        let valley = Valley::new(map);
 
-            let start = find_start(&map);
-            let end = find_end(&map);
+        let down = valley.shortest_path(valley.start, valley.end, 0);
+        let back = valley.shortest_path(valley.end, valley.start, down);
+        let down_again = valley.shortest_path(valley.start, valley.end, down + back);
 
-            let (down, ending_map) = shortest_path(start, end, map);
-            let (back, ending_map) = shortest_path(end, start, ending_map);
-            let (down_again, _) = shortest_path(start, end, ending_map);
-
-            down + back + down_again
-        }
-        Ok((remaining, _)) => {
-            println!("remaining unparsed \"{remaining}\"");
-            0
-        }
-        Err(e) => {
-            println!("error parsing \"{e}\"");
-            0
-        }
+        (down + back + down_again).into()
     }
 }
 
-fn shortest_path(
-    start: (isize, isize),
-    end: (isize, isize),
-    starting_map: Input,
-) -> (usize, Input) {
-    let mut path_attempts: BinaryHeap<Path> = BinaryHeap::new();
-    let mut map_history: HashMap<usize, Input> = HashMap::new();
-    map_history.insert(0, starting_map.clone());
-
-    path_attempts.push(Path {
-        cost: 0,
-        location: start,
-        distance_to_finish: manhattan_distance(start, end),
-    });
-
-    let mut have_shortest_path: HashSet<((isize, isize), u64)> = HashSet::new();
-    let mut hash = DefaultHasher::new();
-    starting_map.iter().collect_vec().hash(&mut hash);
-    have_shortest_path.insert((start, hash.finish()));
-
-    while let Some(path) = path_attempts.pop() {
-        // println!("current_cost: {}", path.cost);
-        let options = next_steps(&path, &mut map_history);
-
-        let this_map = map_history.get(&(path.cost + 1)).unwrap();
-
-        for option in options {
-            if option == end {
-                return (path.cost + 1, this_map.clone());
-            }
+// the blizzards are back where they started every `period` minutes, so the valley only has that
+// many different states and (position, minute within the period) is everything a search needs
+struct Valley {
+    start: Position,
+    end: Position,
+    maps: Vec<Input>,
+}
 
-            let mut hash = DefaultHasher::new();
-            this_map.iter().collect_vec().hash(&mut hash);
-            let option_hash = (option, hash.finish());
-            if have_shortest_path.contains(&option_hash) {
-                continue;
-            }
-            have_shortest_path.insert(option_hash);
+impl Valley {
+    fn new(map: &Input) -> Valley {
+        let inner_width = map.width() - 2;
+        let inner_height = map.height() - 2;
+        let period = inner_width / gcd(inner_width, inner_height) * inner_height;
 
-            path_attempts.push(Path {
-                cost: path.cost + 1,
-                location: option,
-                distance_to_finish: manhattan_distance(option, end),
-            })
+        let mut maps = vec![map.clone()];
+        for _ in 1..period {
+            maps.push(progress_map(maps.last().unwrap()));
         }
-    }
 
-    panic!("could not find a solution ");
-}
+        for map in &maps {
+            frame!(draw_valley(map));
+        }
 
-fn print_map(map: &Input) {
-    for y in 0..6 {
-        for x in 0..8 {
-            match map.get(&(x, y)) {
-                Some(Spot::Open) => print!("."),
-                Some(Spot::MovingBlizzard(blizzards)) => {
-                    if blizzards.len() > 1 {
-                        print!("{}", blizzards.len());
-                    } else {
-                        match blizzards[0] {
-                            Blizzard::Left => print!("<"),
-                            Blizzard::Right => print!(">"),
-                            Blizzard::Up => print!("^"),
-                            Blizzard::Down => print!("v"),
-                        }
-                    }
-                }
-                Some(Spot::Wall) => print!("#"),
-                None => {
-                    println!();
-                    println!("Could not find x: {x}, y: {y}");
-                    println!();
-                    panic!();
-                }
-            }
+        Valley {
+            start: find_start(map),
+            end: find_end(map),
+            maps,
         }
-        println!();
     }
-}
 
-fn next_steps(path: &Path, map_history: &mut HashMap<usize, Input>) -> Vec<(isize, isize)> {
-    let next_cost = path.cost + 1;
-    if !map_history.contains_key(&next_cost) {
-        progress_map_to_cost(next_cost, map_history);
+    // minutes to get from `start` to `end` setting off at `minute`
+    fn shortest_path(&self, start: Position, end: Position, minute: usize) -> usize {
+        let period = self.maps.len();
+
+        a_star(
+            [(start, minute % period)],
+            |&(location, minute)| {
+                let next_minute = (minute + 1) % period;
+                let next_map = &self.maps[next_minute];
+                next_map
+                    .orthogonal_neighbors(location)
+                    .chain([(location, &next_map[location])])
+                    .filter(|(_, spot)| **spot == Spot::Open)
+                    .map(|(option, _)| ((option, next_minute), 1))
+                    .collect::<Vec<_>>()
+            },
+            |&(location, _)| manhattan_distance(location, end),
+            |&(location, _)| location == end,
+        )
+        .expect("could not find a solution")
+        .cost
     }
+}
 
-    let next_map = map_history.get(&next_cost).unwrap();
-    let left = (path.location.0 - 1, path.location.1);
-    let right = (path.location.0 + 1, path.location.1);
-    let top = (path.location.0, path.location.1 + 1);
-    let bottom = (path.location.0, path.location.1 - 1);
-    let all_options = vec![left, right, top, bottom, path.location];
-
-    let mut moves = vec![];
-    for option in all_options {
-        if let Some(square) = next_map.get(&option) {
-            if *square == Spot::Open {
-                moves.push(option);
-            }
-        }
+fn gcd(a: usize, b: usize) -> usize {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
     }
-
-    moves
 }
 
-fn progress_map_to_cost(cost: usize, map_history: &mut HashMap<usize, Input>) {
-    let (current_max_cost, map_at_max) = map_history.iter().max_by(|a, b| a.0.cmp(b.0)).unwrap();
-    let mut last_map = map_at_max.to_owned();
-    for index in (current_max_cost + 1)..(cost + 1) {
-        let map_at_time = progress_map(&last_map);
-        map_history.insert(index, map_at_time.clone());
-        last_map = map_at_time;
-    }
+fn draw_valley(map: &Input) -> Canvas {
+    map.map(|spot| match spot {
+        Spot::Open => '.'.into(),
+        Spot::Wall => Cell::coloured('#', Colour::grey(128)),
+        Spot::MovingBlizzard(blizzards) if blizzards.len() > 1 => Cell::coloured(
+            char::from_digit(blizzards.len() as u32, 10).unwrap_or('*'),
+            Colour::WHITE,
+        ),
+        Spot::MovingBlizzard(blizzards) => {
+            let arrow = match blizzards[0] {
+                Blizzard::Left => '<',
+                Blizzard::Right => '>',
+                Blizzard::Up => '^',
+                Blizzard::Down => 'v',
+            };
+            Cell::coloured(arrow, Colour::BLUE)
+        }
+    })
 }
 
 fn progress_map(map: &Input) -> Input {
-    let mut new_map = HashMap::new();
-    let width = map.iter().max_by(|a, b| a.0 .0.cmp(&b.0 .0)).unwrap().0 .0;
-    let height = map.iter().max_by(|a, b| a.0 .1.cmp(&b.0 .1)).unwrap().0 .1;
-    for (&location, spot) in map.iter() {
+    // blizzards get added back in once they've moved
+    let mut new_map = map.map(|spot| match spot {
+        Spot::Wall => Spot::Wall,
+        _ => Spot::Open,
+    });
+    let width = map.width() as isize - 1;
+    let height = map.height() as isize - 1;
+    for (location, spot) in map.iter() {
         match spot {
-            Spot::Open => {
-                //in case a blizzard moved there
-                new_map.entry(location).or_insert(Spot::Open);
-            }
+            Spot::Open | Spot::Wall => {}
             Spot::MovingBlizzard(blizzards) => {
                 for blizzard in blizzards {
-                    new_map.entry(location).or_insert(Spot::Open);
-
                     match blizzard {
                         Blizzard::Left => {
                             let next_spot = (location.0 - 1, location.1);
-                            if map.get(&next_spot) == Some(&Spot::Wall) {
+                            if map[next_spot] == Spot::Wall {
                                 let wrapped = (width - 1, location.1);
                                 upsert_blizzard(Blizzard::Left, wrapped, &mut new_map);
                             } else {
@@ -309,7 +166,7 @@ fn progress_map(map: &Input) -> Input {
                         }
                         Blizzard::Right => {
                             let next_spot = (location.0 + 1, location.1);
-                            if map.get(&next_spot) == Some(&Spot::Wall) {
+                            if map[next_spot] == Spot::Wall {
                                 let wrapped = (1, location.1);
                                 upsert_blizzard(Blizzard::Right, wrapped, &mut new_map);
                             } else {
@@ -318,7 +175,7 @@ fn progress_map(map: &Input) -> Input {
                         }
                         Blizzard::Up => {
                             let next_spot = (location.0, location.1 - 1);
-                            if map.get(&next_spot) == Some(&Spot::Wall) {
+                            if map[next_spot] == Spot::Wall {
                                 let wrapped = (location.0, height - 1);
                                 upsert_blizzard(Blizzard::Up, wrapped, &mut new_map);
                             } else {
@@ -327,7 +184,7 @@ fn progress_map(map: &Input) -> Input {
                         }
                         Blizzard::Down => {
                             let next_spot = (location.0, location.1 + 1);
-                            if map.get(&next_spot) == Some(&Spot::Wall) {
+                            if map[next_spot] == Spot::Wall {
                                 let wrapped = (location.0, 1);
                                 upsert_blizzard(Blizzard::Down, wrapped, &mut new_map);
                             } else {
@@ -337,87 +194,49 @@ fn progress_map(map: &Input) -> Input {
                     }
                 }
             }
-            Spot::Wall => {
-                new_map.insert(location, Spot::Wall);
-            }
         }
     }
 
     new_map
 }
 
-fn upsert_blizzard(
-    blizzard: Blizzard,
-    location: (isize, isize),
-    map: &mut HashMap<(isize, isize), Spot>,
-) {
-    match map.get(&location) {
-        Some(Spot::MovingBlizzard(blizzards)) => {
-            let mut new_blizzards = blizzards.clone();
-            new_blizzards.push(blizzard);
-            map.insert(location, Spot::MovingBlizzard(new_blizzards));
-        }
-        _ => {
-            map.insert(location, Spot::MovingBlizzard(vec![blizzard]));
-        }
+fn upsert_blizzard(blizzard: Blizzard, location: Position, map: &mut Input) {
+    match &mut map[location] {
+        Spot::MovingBlizzard(blizzards) => blizzards.push(blizzard),
+        spot => *spot = Spot::MovingBlizzard(vec![blizzard]),
     }
 }
 
-fn manhattan_distance(a: (isize, isize), b: (isize, isize)) -> usize {
-    a.0.abs_diff(b.0) + a.1.abs_diff(b.1)
+fn find_start(map: &Input) -> Position {
+    map.iter().find(|(_, spot)| **spot == Spot::Open).unwrap().0
 }
 
-fn find_start(map: &HashMap<(isize, isize), Spot>) -> (isize, isize) {
-    *map.iter()
-        .filter(|(_, spot)| spot == &&Spot::Open)
-        .min_by(|a, b| a.0 .1.cmp(&b.0 .1).then(a.0 .0.cmp(&b.0 .0)))
+fn find_end(map: &Input) -> Position {
+    map.iter()
+        .filter(|(_, spot)| **spot == Spot::Open)
+        .last()
         .unwrap()
         .0
 }
 
-fn find_end(map: &HashMap<(isize, isize), Spot>) -> (isize, isize) {
-    *map.iter()
-        .filter(|(_, spot)| spot == &&Spot::Open)
-        .max_by(|a, b| a.0 .1.cmp(&b.0 .1).then(a.0 .0.cmp(&b.0 .0)))
-        .unwrap()
-        .0
-}
-
-fn map_line_parser(input: &str) -> IResult<&str, Vec<char>> {
-    let (input, output) = many1(nom::character::complete::satisfy(|c| {
-        c == '.' || c == '#' || c == '<' || c == '>' || c == 'v' || c == '^'
-    }))(input)?;
-    Ok((input, output))
-}
-
-fn input_parser(input: &str) -> IResult<&str, Input> {
-    let (input, lines) =
-        separated_list1(nom::character::complete::newline, map_line_parser)(input)?;
-
-    let mut map = HashMap::new();
-    for (y, row) in lines.iter().enumerate() {
-        for (x, c) in row.iter().enumerate() {
-            let spot = match c {
-                '#' => Spot::Wall,
-                '.' => Spot::Open,
-                '<' => Spot::MovingBlizzard(vec![Blizzard::Left]),
-                '>' => Spot::MovingBlizzard(vec![Blizzard::Right]),
-                '^' => Spot::MovingBlizzard(vec![Blizzard::Up]),
-                'v' => Spot::MovingBlizzard(vec![Blizzard::Down]),
-                _ => panic!("invalid spot"),
-            };
-
-            map.insert((x as isize, y as isize), spot);
-        }
+#[cfg(test)]
+mod day24_tests {
+    use crate::day24::Day24;
+    use aoc_common::solution::{solve, Answer};
+
+    const EXAMPLE: &str = "#.######
+#>>.<^<#
+#.<..<<#
+#>v.><>#
+#<^v^^>#
+######.#
+";
+
+    #[test]
+    fn example() {
+        assert_eq!(
+            solve::<Day24>(EXAMPLE, &[1, 2], &()).unwrap(),
+            [Answer::Integer(18), Answer::Integer(54)]
+        );
     }
-
-    Ok((input, map))
 }
-
-// #[cfg(test)]
-// mod day23_tests {
-//     use crate::day23;
-
-//     #[test]
-//     fn test_1() {}
-// }
@@ -1,153 +1,238 @@
-use itertools::Itertools;
+use aoc_common::parse::{parse_all, ParseError};
+use aoc_common::solution::{Answer, Solution};
+use ibig::{ops::DivRemEuclid, IBig};
 use nom::{
     bytes::complete::tag,
-    character::complete::anychar,
-    combinator::{map_res, opt},
-    multi::separated_list0,
+    combinator::map_res,
     multi::{many1, separated_list1},
     IResult,
 };
 use std::{
-    cmp::Ordering,
-    collections::hash_map::DefaultHasher,
-    hash::{Hash, Hasher},
+    fmt,
+    hash::Hash,
+    iter::Sum,
+    ops::{Add, Mul, Neg, Sub},
+    str::FromStr,
 };
-use std::{collections::BinaryHeap, time::SystemTime};
-use std::{
-    collections::{HashMap, HashSet},
-    ptr,
-};
-use std::{fmt, ops};
-
-type Input = Vec<SnafuNumber>;
 
-type SnafuNumber = Vec<SnafuDigit>;
+type Input = Vec<Snafu>;
 
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
-enum SnafuDigit {
+pub enum SnafuDigit {
     Positive(isize),
     Negative1,
     Negative2,
 }
 
-pub fn part1() -> isize {
-    let input = include_str!("../../data/2022/25.txt");
-    // let input = include_str!("../../data/2022/25-example.txt");
+impl SnafuDigit {
+    fn value(&self) -> isize {
+        match self {
+            SnafuDigit::Positive(number) => *number,
+            SnafuDigit::Negative1 => -1,
+            SnafuDigit::Negative2 => -2,
+        }
+    }
 
-    match input_parser(input) {
-        Ok((remaining_input, numbers)) if remaining_input.is_empty() => {
-            println!("parsed entire input");
+    fn from_value(value: isize) -> Self {
+        match value {
+            -2 => SnafuDigit::Negative2,
+            -1 => SnafuDigit::Negative1,
+            0..=2 => SnafuDigit::Positive(value),
+            _ => panic!("{} isn't a SNAFU digit", value),
+        }
+    }
+}
 
-            let mut sum = 0;
+// a SNAFU number of any length, most significant digit first. there are never any leading zeros,
+// and zero is a single `0`
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Snafu(Vec<SnafuDigit>);
+
+impl Snafu {
+    fn new(mut digits: Vec<SnafuDigit>) -> Self {
+        let leading_zeros = digits
+            .iter()
+            .take_while(|&digit| *digit == SnafuDigit::Positive(0))
+            .count()
+            .min(digits.len().saturating_sub(1));
+        digits.drain(..leading_zeros);
+        if digits.is_empty() {
+            digits.push(SnafuDigit::Positive(0));
+        }
 
-            for snafu in numbers {
-                let this_value = snafu_to_base_10(&snafu);
-                // println!("{}", this_value);
-                sum += this_value;
-            }
+        Snafu(digits)
+    }
 
-            for number in base_10_to_snafu(sum) {
-                match number {
-                    SnafuDigit::Positive(n) => print!("{n}"),
-                    SnafuDigit::Negative1 => print!("-"),
-                    SnafuDigit::Negative2 => print!("="),
-                }
-            }
-            println!();
+    pub fn zero() -> Self {
+        Snafu::new(vec![])
+    }
 
-            sum
-        }
-        Ok((remaining, _)) => {
-            println!("remaining unparsed \"{remaining}\"");
-            0
+    // digit values from least significant up, with whatever carry is left over folded back into
+    // SNAFU digits
+    fn from_values(values: impl IntoIterator<Item = isize>) -> Self {
+        let mut digits = vec![];
+        let mut carry = 0;
+        for value in values {
+            let (digit, next_carry) = balance(value + carry);
+            digits.push(SnafuDigit::from_value(digit));
+            carry = next_carry;
         }
-        Err(e) => {
-            println!("error parsing \"{e}\"");
-            0
+        while carry != 0 {
+            let (digit, next_carry) = balance(carry);
+            digits.push(SnafuDigit::from_value(digit));
+            carry = next_carry;
         }
+
+        digits.reverse();
+        Snafu::new(digits)
+    }
+
+    fn values(&self) -> impl Iterator<Item = isize> + '_ {
+        self.0.iter().rev().map(SnafuDigit::value)
+    }
+
+    fn times_digit(&self, digit: isize) -> Snafu {
+        Snafu::from_values(self.values().map(|value| value * digit))
     }
 }
 
-pub fn part2() -> isize {
-    // let input = include_str!("../../data/2022/25.txt");
-    let input = include_str!("../../data/2022/25-example.txt");
+// splits a value into a digit from -2 to 2 and what carries into the next place
+fn balance(value: isize) -> (isize, isize) {
+    let carry = (value + 2).div_euclid(5);
+    (value - 5 * carry, carry)
+}
 
-    match input_parser(input) {
-        Ok((remaining_input, numbers)) if remaining_input.is_empty() => {
-            println!("parsed entire input");
+impl FromStr for Snafu {
+    type Err = ParseError;
 
-            0
-        }
-        Ok((remaining, _)) => {
-            println!("remaining unparsed \"{remaining}\"");
-            0
-        }
-        Err(e) => {
-            println!("error parsing \"{e}\"");
-            0
-        }
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_all(s, snafu_parser)
     }
 }
 
-fn snafu_to_base_10(snafu: &Vec<SnafuDigit>) -> isize {
-    let mut position_value = 1;
-    let mut value = 0;
-    for digit in snafu.iter().rev() {
-        match digit {
-            SnafuDigit::Positive(number) => value += position_value * number,
-            SnafuDigit::Negative1 => value += position_value * -1,
-            SnafuDigit::Negative2 => value += position_value * -2,
+impl fmt::Display for Snafu {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for digit in &self.0 {
+            match digit {
+                SnafuDigit::Positive(n) => write!(f, "{}", n)?,
+                SnafuDigit::Negative1 => write!(f, "-")?,
+                SnafuDigit::Negative2 => write!(f, "=")?,
+            }
         }
 
-        position_value *= 5;
+        Ok(())
     }
+}
+
+// added a place at a time with carries, the way you would by hand
+impl Add for Snafu {
+    type Output = Snafu;
 
-    value
+    fn add(self, rhs: Self) -> Self::Output {
+        let (mut left, mut right) = (self.values(), rhs.values());
+        let values = std::iter::from_fn(|| match (left.next(), right.next()) {
+            (None, None) => None,
+            (a, b) => Some(a.unwrap_or(0) + b.unwrap_or(0)),
+        });
+
+        Snafu::from_values(values.collect::<Vec<isize>>())
+    }
 }
 
-fn base_10_to_snafu(number: isize) -> Vec<SnafuDigit> {
-    let mut snafu_number = vec![];
-    let mut working_number = number;
-    let mut carrying = 0;
-    while working_number > 0 {
-        match (working_number % 5) + carrying {
-            0 => {
-                carrying = 0;
-                snafu_number.push(SnafuDigit::Positive(0));
-            }
-            1 => {
-                carrying = 0;
-                snafu_number.push(SnafuDigit::Positive(1));
-            }
-            2 => {
-                carrying = 0;
-                snafu_number.push(SnafuDigit::Positive(2));
-            }
-            3 => {
-                carrying = 1;
-                snafu_number.push(SnafuDigit::Negative2);
-            }
-            4 => {
-                carrying = 1;
-                snafu_number.push(SnafuDigit::Negative1);
-            }
-            5 => {
-                carrying = 1;
-                snafu_number.push(SnafuDigit::Positive(0));
-            }
-            _ => panic!(),
+impl Neg for Snafu {
+    type Output = Snafu;
+
+    fn neg(self) -> Self::Output {
+        Snafu::new(
+            self.0
+                .iter()
+                .map(|digit| SnafuDigit::from_value(-digit.value()))
+                .collect(),
+        )
+    }
+}
+
+impl Sub for Snafu {
+    type Output = Snafu;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        self + -rhs
+    }
+}
+
+// long multiplication, one digit of the right hand side at a time
+impl Mul for Snafu {
+    type Output = Snafu;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        rhs.values()
+            .enumerate()
+            .map(|(place, digit)| {
+                let mut partial = self.times_digit(digit);
+                partial
+                    .0
+                    .extend((0..place).map(|_| SnafuDigit::Positive(0)));
+                Snafu::new(partial.0)
+            })
+            .sum()
+    }
+}
+
+impl Sum for Snafu {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Snafu::zero(), Add::add)
+    }
+}
+
+impl From<&Snafu> for IBig {
+    fn from(snafu: &Snafu) -> Self {
+        snafu
+            .0
+            .iter()
+            .fold(IBig::from(0), |value, digit| value * 5 + digit.value())
+    }
+}
+
+impl From<&IBig> for Snafu {
+    fn from(number: &IBig) -> Self {
+        let five = IBig::from(5);
+        let mut remaining = number.clone();
+        let mut values = vec![];
+        while remaining != IBig::from(0) {
+            let (quotient, remainder) = remaining.div_rem_euclid(&five);
+            let (digit, carry) = balance(isize::try_from(&remainder).unwrap());
+            values.push(digit);
+            remaining = quotient + carry;
         }
 
-        working_number /= 5;
+        Snafu::from_values(values)
     }
+}
 
-    if carrying > 0 {
-        snafu_number.push(SnafuDigit::Positive(carrying))
+impl From<isize> for Snafu {
+    fn from(number: isize) -> Self {
+        Snafu::from(&IBig::from(number))
     }
+}
+
+pub struct Day25;
+
+impl Solution for Day25 {
+    type Input<'a> = Input;
 
-    snafu_number.reverse();
+    type Params = ();
 
-    snafu_number
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse_all(input, input_parser)
+    }
+
+    fn part1(numbers: &Self::Input<'_>, _params: &Self::Params) -> Answer {
+        numbers.iter().cloned().sum::<Snafu>().to_string().into()
+    }
+
+    fn part2(_numbers: &Self::Input<'_>, _params: &Self::Params) -> Answer {
+        Answer::Unsolved
+    }
 }
 
 fn negative1_parser(input: &str) -> IResult<&str, SnafuDigit> {
@@ -173,13 +258,13 @@ fn positive_digit_parser(input: &str) -> IResult<&str, SnafuDigit> {
     Ok((input, SnafuDigit::Positive(digit)))
 }
 
-fn snafu_parser(input: &str) -> IResult<&str, Vec<SnafuDigit>> {
+fn snafu_parser(input: &str) -> IResult<&str, Snafu> {
     let (input, output) = many1(nom::branch::alt((
         negative1_parser,
         negative2_parser,
         positive_digit_parser,
     )))(input)?;
-    Ok((input, output))
+    Ok((input, Snafu::new(output)))
 }
 
 fn input_parser(input: &str) -> IResult<&str, Input> {
@@ -189,121 +274,90 @@ fn input_parser(input: &str) -> IResult<&str, Input> {
 
 #[cfg(test)]
 mod day25_tests {
-    use super::SnafuDigit;
-    use crate::day25::base_10_to_snafu;
-    use crate::day25::snafu_to_base_10;
+    use crate::day25::Day25;
+    use crate::day25::Snafu;
+    use aoc_common::solution::{solve, Answer};
+    use ibig::IBig;
+
+    fn snafu(text: &str) -> Snafu {
+        text.parse().unwrap()
+    }
+
+    #[test]
+    fn snafu_to_base_10() {
+        assert_eq!(IBig::from(&snafu("1")), IBig::from(1));
+        assert_eq!(IBig::from(&snafu("12111")), IBig::from(906));
+        assert_eq!(
+            IBig::from(&snafu("2-121-=10=200==2==21")),
+            IBig::from(35422591760336_i64)
+        );
+    }
+
+    #[test]
+    fn base_10_to_snafu() {
+        for (number, expected) in [
+            (5, "10"),
+            (6, "11"),
+            (4, "1-"),
+            (3, "1="),
+            (9, "2-"),
+            (8, "2="),
+            (15, "1=0"),
+            (906, "12111"),
+            (2022, "1=11-2"),
+            (12345, "1-0---0"),
+            (314159265, "1121-1110-1=0"),
+            (0, "0"),
+            (-3, "-2"),
+        ] {
+            assert_eq!(Snafu::from(number).to_string(), expected);
+        }
+    }
 
     #[test]
-    fn test_1() {
-        let test_snafu = vec![SnafuDigit::Positive(1)];
-        assert_eq!(snafu_to_base_10(&test_snafu), 1);
-
-        let test_snafu = vec![
-            SnafuDigit::Positive(1),
-            SnafuDigit::Positive(2),
-            SnafuDigit::Positive(1),
-            SnafuDigit::Positive(1),
-            SnafuDigit::Positive(1),
-        ];
-        assert_eq!(snafu_to_base_10(&test_snafu), 906);
-
-        let test_snafu = vec![
-            SnafuDigit::Positive(2),
-            SnafuDigit::Negative1,
-            SnafuDigit::Positive(1),
-            SnafuDigit::Positive(2),
-            SnafuDigit::Positive(1),
-            SnafuDigit::Negative1,
-            SnafuDigit::Negative2,
-            SnafuDigit::Positive(0),
-            SnafuDigit::Positive(0),
-            SnafuDigit::Negative2,
-            SnafuDigit::Positive(2),
-            SnafuDigit::Negative1,
-            SnafuDigit::Positive(0),
-            SnafuDigit::Negative2,
-            SnafuDigit::Negative2,
-            SnafuDigit::Positive(2),
-            SnafuDigit::Negative2,
-            SnafuDigit::Negative2,
-            SnafuDigit::Positive(2),
-            SnafuDigit::Positive(1),
-        ];
-        assert_eq!(snafu_to_base_10(&test_snafu), 35422591760336);
+    fn arithmetic_stays_in_snafu() {
+        assert_eq!(snafu("1=-0-2") + snafu("12111"), Snafu::from(1747 + 906));
+        assert_eq!(snafu("2=") - snafu("1=0"), Snafu::from(-7));
+        assert_eq!(-snafu("1-0---0"), Snafu::from(-12345));
+        assert_eq!(snafu("1=11-2") * snafu("-2"), Snafu::from(-2022 * 3));
+        assert_eq!(snafu("002=").to_string(), "2=");
+        assert_eq!(
+            [snafu("1"), snafu("2"), snafu("1=")]
+                .into_iter()
+                .sum::<Snafu>(),
+            Snafu::from(6)
+        );
+        assert!("12a".parse::<Snafu>().is_err());
     }
 
     #[test]
-    fn test_2() {
-        let test_snafu = vec![SnafuDigit::Positive(1), SnafuDigit::Positive(0)];
-        assert_eq!(base_10_to_snafu(5), test_snafu);
-
-        let test_snafu = vec![SnafuDigit::Positive(1), SnafuDigit::Positive(1)];
-        assert_eq!(base_10_to_snafu(6), test_snafu);
-
-        let test_snafu = vec![SnafuDigit::Positive(1), SnafuDigit::Negative1];
-        assert_eq!(base_10_to_snafu(4), test_snafu);
-
-        let test_snafu = vec![SnafuDigit::Positive(1), SnafuDigit::Negative2];
-        assert_eq!(base_10_to_snafu(3), test_snafu);
-
-        let test_snafu = vec![SnafuDigit::Positive(2), SnafuDigit::Negative1];
-        assert_eq!(base_10_to_snafu(9), test_snafu);
-
-        let test_snafu = vec![SnafuDigit::Positive(2), SnafuDigit::Negative2];
-        assert_eq!(base_10_to_snafu(8), test_snafu);
-
-        let test_snafu = vec![
-            SnafuDigit::Positive(1),
-            SnafuDigit::Negative2,
-            SnafuDigit::Positive(0),
-        ];
-        assert_eq!(base_10_to_snafu(15), test_snafu);
-
-        let test_snafu = vec![
-            SnafuDigit::Positive(1),
-            SnafuDigit::Positive(2),
-            SnafuDigit::Positive(1),
-            SnafuDigit::Positive(1),
-            SnafuDigit::Positive(1),
-        ];
-        assert_eq!(base_10_to_snafu(906), test_snafu);
-
-        let test_snafu = vec![
-            SnafuDigit::Positive(1),
-            SnafuDigit::Negative2,
-            SnafuDigit::Positive(1),
-            SnafuDigit::Positive(1),
-            SnafuDigit::Negative1,
-            SnafuDigit::Positive(2),
-        ];
-        assert_eq!(base_10_to_snafu(2022), test_snafu);
-
-        let test_snafu = vec![
-            SnafuDigit::Positive(1),
-            SnafuDigit::Negative1,
-            SnafuDigit::Positive(0),
-            SnafuDigit::Negative1,
-            SnafuDigit::Negative1,
-            SnafuDigit::Negative1,
-            SnafuDigit::Positive(0),
-        ];
-        assert_eq!(base_10_to_snafu(12345), test_snafu);
-
-        let test_snafu = vec![
-            SnafuDigit::Positive(1),
-            SnafuDigit::Positive(1),
-            SnafuDigit::Positive(2),
-            SnafuDigit::Positive(1),
-            SnafuDigit::Negative1,
-            SnafuDigit::Positive(1),
-            SnafuDigit::Positive(1),
-            SnafuDigit::Positive(1),
-            SnafuDigit::Positive(0),
-            SnafuDigit::Negative1,
-            SnafuDigit::Positive(1),
-            SnafuDigit::Negative2,
-            SnafuDigit::Positive(0),
-        ];
-        assert_eq!(base_10_to_snafu(314159265), test_snafu);
+    fn numbers_of_any_length() {
+        let big: IBig = "-123456789012345678901234567890".parse().unwrap();
+        let as_snafu = Snafu::from(&big);
+        assert_eq!(IBig::from(&as_snafu), big);
+        assert_eq!(IBig::from(&(as_snafu.clone() * as_snafu)), &big * &big);
+    }
+
+    const EXAMPLE: &str = "1=-0-2
+12111
+2=0=
+21
+2=01
+111
+20012
+112
+1=-1=
+1-12
+12
+1=
+122
+";
+
+    #[test]
+    fn example() {
+        assert_eq!(
+            solve::<Day25>(EXAMPLE, &[1, 2], &()).unwrap(),
+            [Answer::from("2=-1=0"), Answer::Unsolved]
+        );
     }
 }